@@ -0,0 +1,132 @@
+use crate::{
+    aabb::Aabb,
+    frame::Frame,
+    hitable::{HitRecord, Hitable},
+    material::Material,
+    ray::Ray,
+    vec3::Vec3,
+};
+
+/// Solid axis-aligned box, each face mapped to the full [0, 1] UV range.
+#[derive(Debug, Clone)]
+pub struct AaBox {
+    min: Vec3,
+    max: Vec3,
+    material: Material,
+}
+
+impl AaBox {
+    pub fn new(min: Vec3, max: Vec3, material: Material) -> Self {
+        AaBox { min, max, material }
+    }
+}
+
+impl Hitable for AaBox {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let (t, normal, u, v) = hit_box(&self.min, &self.max, ray, t_min, t_max)?;
+        Some(HitRecord::new(ray, t, normal, self.material.clone(), u, v))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(Aabb::new(self.min, self.max))
+    }
+}
+
+/// Box with arbitrary orientation, given by its centre, half extents and the
+/// directions of its local x and y axes.
+#[derive(Debug, Clone)]
+pub struct OrientedBox {
+    frame: Frame,
+    half_extents: Vec3,
+    material: Material,
+}
+
+impl OrientedBox {
+    pub fn new(center: Vec3, half_extents: Vec3, x_axis: Vec3, y_axis: Vec3, material: Material) -> Self {
+        OrientedBox {
+            frame: Frame::new(center, x_axis, y_axis),
+            half_extents,
+            material,
+        }
+    }
+}
+
+impl Hitable for OrientedBox {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let local_ray = Ray::new(
+            self.frame.point_to_local(&ray.origin),
+            self.frame.to_local(&ray.direction),
+        );
+        let min = self.half_extents.multiply(-1.0);
+        let (t, normal, u, v) = hit_box(&min, &self.half_extents, &local_ray, t_min, t_max)?;
+        Some(HitRecord::new(
+            ray,
+            t,
+            self.frame.to_world(&normal),
+            self.material.clone(),
+            u,
+            v,
+        ))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let mut corners = vec![];
+        for x in [-1.0, 1.0] {
+            for y in [-1.0, 1.0] {
+                for z in [-1.0, 1.0] {
+                    let local = Vec3::new(x, y, z).multiply_vec(&self.half_extents);
+                    corners.push(self.frame.point_to_world(&local));
+                }
+            }
+        }
+        Some(Aabb::from_points(&corners))
+    }
+}
+
+/// Slab test returning the closest hit in range, with the outward normal of
+/// the face and its UVs. Rays starting inside the box hit the far face.
+pub fn hit_box(min: &Vec3, max: &Vec3, ray: &Ray, t_min: f32, t_max: f32) -> Option<(f32, Vec3, f32, f32)> {
+    let origin = [ray.origin.x, ray.origin.y, ray.origin.z];
+    let direction = [ray.direction.x, ray.direction.y, ray.direction.z];
+    let min = [min.x, min.y, min.z];
+    let max = [max.x, max.y, max.z];
+
+    let (mut t_near, mut near_axis) = (f32::NEG_INFINITY, 0);
+    let (mut t_far, mut far_axis) = (f32::INFINITY, 0);
+    for axis in 0..3 {
+        let inv_direction = 1.0 / direction[axis];
+        let mut t0 = (min[axis] - origin[axis]) * inv_direction;
+        let mut t1 = (max[axis] - origin[axis]) * inv_direction;
+        if inv_direction < 0.0 {
+            std::mem::swap(&mut t0, &mut t1);
+        }
+        if t0 > t_near {
+            t_near = t0;
+            near_axis = axis;
+        }
+        if t1 < t_far {
+            t_far = t1;
+            far_axis = axis;
+        }
+    }
+    if t_near > t_far {
+        return None;
+    }
+
+    let (t, axis, sign) = if t_near > t_min && t_near < t_max {
+        (t_near, near_axis, -direction[near_axis].signum())
+    } else if t_far > t_min && t_far < t_max {
+        (t_far, far_axis, direction[far_axis].signum())
+    } else {
+        return None;
+    };
+
+    let mut normal = [0.0; 3];
+    normal[axis] = sign;
+    let point = ray.at(t);
+    let point = [point.x, point.y, point.z];
+    let (a, b) = ((axis + 1) % 3, (axis + 2) % 3);
+    let u = (point[a] - min[a]) / (max[a] - min[a]);
+    let v = (point[b] - min[b]) / (max[b] - min[b]);
+    Some((t, Vec3::new(normal[0], normal[1], normal[2]), u, v))
+}
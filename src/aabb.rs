@@ -0,0 +1,63 @@
+use crate::{ray::Ray, vec3::Vec3};
+
+/// Axis-aligned bounding box.
+#[derive(Debug, Clone, Copy)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Aabb { min, max }
+    }
+
+    pub fn from_points(points: &[Vec3]) -> Self {
+        let mut aabb = Aabb::new(points[0], points[0]);
+        for point in &points[1..] {
+            aabb = aabb.union(&Aabb::new(*point, *point));
+        }
+        aabb
+    }
+
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        Aabb {
+            min: Vec3::new(
+                self.min.x.min(other.min.x),
+                self.min.y.min(other.min.y),
+                self.min.z.min(other.min.z),
+            ),
+            max: Vec3::new(
+                self.max.x.max(other.max.x),
+                self.max.y.max(other.max.y),
+                self.max.z.max(other.max.z),
+            ),
+        }
+    }
+
+    pub fn center(&self) -> Vec3 {
+        self.min.add(&self.max).multiply(0.5)
+    }
+
+    pub fn surface_area(&self) -> f32 {
+        let d = self.max.sub(&self.min);
+        2.0 * (d.x * d.y + d.y * d.z + d.z * d.x)
+    }
+
+    /// Slab test, `inverse_direction` being 1 / ray.direction per axis.
+    pub fn hit(&self, ray: &Ray, inverse_direction: &Vec3, t_min: f32, t_max: f32) -> bool {
+        let mut t_min = t_min;
+        let mut t_max = t_max;
+        for (origin, inverse, min, max) in [
+            (ray.origin.x, inverse_direction.x, self.min.x, self.max.x),
+            (ray.origin.y, inverse_direction.y, self.min.y, self.max.y),
+            (ray.origin.z, inverse_direction.z, self.min.z, self.max.z),
+        ] {
+            let t0 = (min - origin) * inverse;
+            let t1 = (max - origin) * inverse;
+            t_min = t_min.max(t0.min(t1));
+            t_max = t_max.min(t0.max(t1));
+        }
+        t_min <= t_max
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter},
+};
+
+use image::{
+    Delay, DynamicImage, Frame,
+    codecs::gif::{GifEncoder, Repeat},
+};
+
+use crate::{camera::Camera, hitable_list::HitableList, output, vec3::Vec3};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Keyframe {
+    pub time: f32,
+    pub look_from: Vec3,
+    pub look_at: Vec3,
+    pub fov: f32,
+    pub focus_distance: f32,
+}
+
+impl Keyframe {
+    pub fn new(time: f32, look_from: Vec3, look_at: Vec3, fov: f32, focus_distance: f32) -> Self {
+        Keyframe {
+            time,
+            look_from,
+            look_at,
+            fov,
+            focus_distance,
+        }
+    }
+
+    fn to_array(self) -> [f32; 8] {
+        [
+            self.look_from.x,
+            self.look_from.y,
+            self.look_from.z,
+            self.look_at.x,
+            self.look_at.y,
+            self.look_at.z,
+            self.fov,
+            self.focus_distance,
+        ]
+    }
+
+    fn from_array(time: f32, values: [f32; 8]) -> Self {
+        Keyframe::new(
+            time,
+            Vec3::new(values[0], values[1], values[2]),
+            Vec3::new(values[3], values[4], values[5]),
+            values[6],
+            values[7],
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Interpolation {
+    Linear,
+    /// Smooth curve through every keyframe.
+    CatmullRom,
+    /// Cubic Bezier segments between neighbouring keyframes, passing through
+    /// each of them. The inner control points lie along tangents taken from
+    /// the keyframes on either side and scaled by the time between keys, so
+    /// the camera keeps a steady speed through unevenly spaced keyframes.
+    Bezier,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum AnimationFormat {
+    Gif,
+    Apng,
+}
+
+#[derive(Debug, Clone)]
+pub struct CameraPath {
+    keyframes: Vec<Keyframe>,
+    interpolation: Interpolation,
+}
+
+impl CameraPath {
+    pub fn new(interpolation: Interpolation) -> Self {
+        CameraPath {
+            keyframes: vec![],
+            interpolation,
+        }
+    }
+
+    pub fn add(&mut self, keyframe: Keyframe) {
+        let index = self
+            .keyframes
+            .partition_point(|existing| existing.time <= keyframe.time);
+        self.keyframes.insert(index, keyframe);
+    }
+
+    pub fn start_time(&self) -> f32 {
+        self.keyframes.first().map_or(0.0, |keyframe| keyframe.time)
+    }
+
+    pub fn end_time(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |keyframe| keyframe.time)
+    }
+
+    /// Interpolated camera parameters at `time`, clamped to the path's range,
+    /// or `None` for a path without keyframes.
+    pub fn sample(&self, time: f32) -> Option<Keyframe> {
+        let first = self.keyframes.first()?;
+        let last = self.keyframes.last()?;
+        if self.keyframes.len() == 1 || time <= first.time {
+            return Some(Keyframe { time, ..*first });
+        }
+        if time >= last.time {
+            return Some(Keyframe { time, ..*last });
+        }
+
+        let segment = self.keyframes.partition_point(|keyframe| keyframe.time <= time) - 1;
+        let k0 = &self.keyframes[segment.saturating_sub(1)];
+        let k1 = &self.keyframes[segment];
+        let k2 = &self.keyframes[segment + 1];
+        let k3 = &self.keyframes[(segment + 2).min(self.keyframes.len() - 1)];
+        let t = (time - k1.time) / (k2.time - k1.time);
+        let values = match self.interpolation {
+            Interpolation::Linear => lerp(&k1.to_array(), &k2.to_array(), t),
+            Interpolation::CatmullRom => catmull_rom(
+                &k0.to_array(),
+                &k1.to_array(),
+                &k2.to_array(),
+                &k3.to_array(),
+                t,
+            ),
+            Interpolation::Bezier => {
+                let duration = k2.time - k1.time;
+                let (p1, p2) = (k1.to_array(), k2.to_array());
+                let (m1, m2) = (tangent(k0, k2), tangent(k1, k3));
+                let c1 = std::array::from_fn(|i| p1[i] + m1[i] * duration / 3.0);
+                let c2 = std::array::from_fn(|i| p2[i] - m2[i] * duration / 3.0);
+                cubic_bezier(&p1, &c1, &c2, &p2, t)
+            }
+        };
+        Some(Keyframe::from_array(time, values))
+    }
+}
+
+/// Renders the path at `fps` frames per second into numbered PNGs named
+/// `<output_prefix>_0000.png` and onwards. Each frame's time is passed to
+/// `make_scene`, and the frames are optionally assembled into an animation.
+/// Fails with `InvalidInput` for a path without keyframes.
+pub fn render_sequence(
+    path: &CameraPath,
+    fps: f32,
+    output_prefix: &str,
+    make_camera: impl Fn(&Keyframe) -> Camera,
+    make_scene: impl Fn(f32) -> HitableList,
+    ambient_light: Vec3,
+    animation: Option<AnimationFormat>,
+) -> io::Result<()> {
+    let duration = path.end_time() - path.start_time();
+    let frame_count = ((duration * fps).round() as u32).max(1);
+
+    let mut frames = vec![];
+    for frame in 0..frame_count {
+        let time = path.start_time() + frame as f32 / fps;
+        let Some(keyframe) = path.sample(time) else {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "camera path has no keyframes"));
+        };
+        let camera = make_camera(&keyframe);
+        let world = make_scene(time);
+        let image = camera.render(&world, ambient_light);
+        let image = image.lock().unwrap();
+        output::encode_png(&image, &format!("{}_{:04}.png", output_prefix, frame))?;
+        if animation.is_some() {
+            frames.push(image.clone());
+        }
+    }
+
+    match animation {
+        Some(AnimationFormat::Gif) => encode_gif(&frames, &format!("{}.gif", output_prefix), fps),
+        Some(AnimationFormat::Apng) => encode_apng(&frames, &format!("{}.png", output_prefix), fps),
+        None => Ok(()),
+    }
+}
+
+fn encode_gif(frames: &[DynamicImage], filename: &str, fps: f32) -> io::Result<()> {
+    let file = File::create(filename)?;
+    let mut encoder = GifEncoder::new(BufWriter::new(file));
+    encoder.set_repeat(Repeat::Infinite).map_err(io::Error::other)?;
+    let delay = Delay::from_numer_denom_ms(1000, fps.round().max(1.0) as u32);
+    encoder
+        .encode_frames(
+            frames
+                .iter()
+                .map(|frame| Frame::from_parts(frame.to_rgba8(), 0, 0, delay)),
+        )
+        .map_err(io::Error::other)
+}
+
+// the image crate can only decode APNG, so this goes through png directly,
+// the same version image already uses for still PNGs
+fn encode_apng(frames: &[DynamicImage], filename: &str, fps: f32) -> io::Result<()> {
+    let Some(first) = frames.first() else {
+        return Ok(());
+    };
+    let file = File::create(filename)?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), first.width(), first.height());
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.set_animated(frames.len() as u32, 0)?;
+    encoder.set_frame_delay(1, fps.round().max(1.0) as u16)?;
+    let mut writer = encoder.write_header()?;
+    for frame in frames {
+        writer.write_image_data(frame.to_rgb8().as_raw())?;
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+fn lerp(a: &[f32; 8], b: &[f32; 8], t: f32) -> [f32; 8] {
+    std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+}
+
+/// Rate of change per unit of time between keyframes `before` and `after`.
+fn tangent(before: &Keyframe, after: &Keyframe) -> [f32; 8] {
+    let (a, b) = (before.to_array(), after.to_array());
+    let duration = after.time - before.time;
+    std::array::from_fn(|i| if duration > 0.0 { (b[i] - a[i]) / duration } else { 0.0 })
+}
+
+fn cubic_bezier(p0: &[f32; 8], p1: &[f32; 8], p2: &[f32; 8], p3: &[f32; 8], t: f32) -> [f32; 8] {
+    let s = 1.0 - t;
+    std::array::from_fn(|i| s * s * s * p0[i] + 3.0 * s * s * t * p1[i] + 3.0 * s * t * t * p2[i] + t * t * t * p3[i])
+}
+
+fn catmull_rom(p0: &[f32; 8], p1: &[f32; 8], p2: &[f32; 8], p3: &[f32; 8], t: f32) -> [f32; 8] {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    std::array::from_fn(|i| {
+        0.5 * (2.0 * p1[i]
+            + (p2[i] - p0[i]) * t
+            + (2.0 * p0[i] - 5.0 * p1[i] + 4.0 * p2[i] - p3[i]) * t2
+            + (3.0 * p1[i] - p0[i] - 3.0 * p2[i] + p3[i]) * t3)
+    })
+}
@@ -0,0 +1,541 @@
+use std::collections::HashMap;
+
+use crate::{material::Material, mesh::TriangleMesh, vec3::Vec3};
+
+/// Most segments a patch is split into along each direction.
+const MAX_SEGMENTS: usize = 64;
+
+/// Bicubic Bezier patch; `control[row * 4 + column]`, with u running along
+/// the columns and v along the rows.
+#[derive(Debug, Clone, Copy)]
+pub struct BezierPatch {
+    pub control: [Vec3; 16],
+}
+
+impl BezierPatch {
+    pub fn new(control: [Vec3; 16]) -> Self {
+        BezierPatch { control }
+    }
+
+    /// Position and partial derivatives along u and v.
+    pub fn evaluate(&self, u: f32, v: f32) -> (Vec3, Vec3, Vec3) {
+        let (bu, dbu) = (bernstein(u), bernstein_derivative(u));
+        let (bv, dbv) = (bernstein(v), bernstein_derivative(v));
+        let zero = Vec3::new(0.0, 0.0, 0.0);
+        let (mut point, mut du, mut dv) = (zero, zero, zero);
+        for row in 0..4 {
+            for column in 0..4 {
+                let control = self.control[row * 4 + column];
+                point = point.add(&control.multiply(bu[column] * bv[row]));
+                du = du.add(&control.multiply(dbu[column] * bv[row]));
+                dv = dv.add(&control.multiply(bu[column] * dbv[row]));
+            }
+        }
+        (point, du, dv)
+    }
+
+    /// Surface normal, nudged toward the middle of the patch where a
+    /// collapsed edge (e.g. the pole of a revolved patch) leaves it undefined.
+    pub fn normal(&self, u: f32, v: f32) -> Vec3 {
+        let (_, du, dv) = self.evaluate(u, v);
+        let normal = du.cross(&dv);
+        if normal.length_squared() > 1e-12 {
+            return normal.normalize();
+        }
+        let (_, du, dv) = self.evaluate(u + (0.5 - u) * 1e-3, v + (0.5 - v) * 1e-3);
+        du.cross(&dv).normalize()
+    }
+
+    /// How far the control net strays from the plane through its corners,
+    /// which bounds how far the patch does.
+    fn flatness(&self) -> f32 {
+        let corner = |row: usize, column: usize| self.control[row * 4 + column];
+        let mut flatness: f32 = 0.0;
+        for row in 0..4 {
+            for column in 0..4 {
+                let (s, t) = (column as f32 / 3.0, row as f32 / 3.0);
+                let bilinear = corner(0, 0)
+                    .multiply((1.0 - s) * (1.0 - t))
+                    .add(&corner(0, 3).multiply(s * (1.0 - t)))
+                    .add(&corner(3, 0).multiply((1.0 - s) * t))
+                    .add(&corner(3, 3).multiply(s * t));
+                flatness = flatness.max(self.control[row * 4 + column].sub(&bilinear).length());
+            }
+        }
+        flatness
+    }
+}
+
+fn bernstein(t: f32) -> [f32; 4] {
+    let s = 1.0 - t;
+    [s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t]
+}
+
+fn bernstein_derivative(t: f32) -> [f32; 4] {
+    let s = 1.0 - t;
+    [-3.0 * s * s, 3.0 * s * s - 6.0 * s * t, 6.0 * s * t - 3.0 * t * t, 3.0 * t * t]
+}
+
+/// Tessellates patches into a mesh with normals from the exact surface, so
+/// it shades as the smooth patch. Each patch gets enough segments to keep
+/// within `tolerance` of the surface, so flat patches stay cheap, but
+/// patches sharing an edge are split the same way along it so their grids
+/// meet without cracks. UVs are the patch parameters.
+pub fn tessellate(patches: &[BezierPatch], tolerance: f32, material: Material) -> TriangleMesh {
+    let mut positions = vec![];
+    let mut normals = vec![];
+    let mut uvs = vec![];
+    let mut indices = vec![];
+    for (patch, (columns, rows)) in patches.iter().zip(shared_segments(patches, tolerance)) {
+        let first = positions.len();
+        for row in 0..=rows {
+            for column in 0..=columns {
+                let (u, v) = (column as f32 / columns as f32, row as f32 / rows as f32);
+                positions.push(patch.evaluate(u, v).0);
+                normals.push(patch.normal(u, v));
+                uvs.push((u, v));
+            }
+        }
+        let vertex = |row: usize, column: usize| first + row * (columns + 1) + column;
+        for row in 0..rows {
+            for column in 0..columns {
+                indices.push([vertex(row, column), vertex(row, column + 1), vertex(row + 1, column + 1)]);
+                indices.push([vertex(row, column), vertex(row + 1, column + 1), vertex(row + 1, column)]);
+            }
+        }
+    }
+    TriangleMesh::new(positions, indices, material)
+        .with_normals(normals)
+        .with_uvs(uvs)
+}
+
+/// Segments along u and v for each patch. Opposite edges of a patch are
+/// split alike by its grid and shared edges must be split alike by both
+/// patches, so edges tied together either way form chains that all take the
+/// most segments any patch along the chain needs.
+fn shared_segments(patches: &[BezierPatch], tolerance: f32) -> Vec<(usize, usize)> {
+    let mut ids = HashMap::new();
+    let mut chains = vec![];
+    // edges at v = 0 and 1, along u, then at u = 0 and 1, along v
+    let edges: Vec<[usize; 4]> = patches
+        .iter()
+        .map(|patch| {
+            [(0, 1), (12, 1), (0, 4), (3, 4)].map(|(start, step)| {
+                edge_id(std::array::from_fn(|i| patch.control[start + i * step]), &mut ids, &mut chains)
+            })
+        })
+        .collect();
+    for &[bottom, top, left, right] in &edges {
+        join(&mut chains, bottom, top);
+        join(&mut chains, left, right);
+    }
+
+    let mut segments = vec![1; chains.len()];
+    for (patch, patch_edges) in patches.iter().zip(&edges) {
+        // a grid of n segments follows a curve to within about 1/n² of its bend
+        let needed = ((patch.flatness() / tolerance).sqrt().ceil() as usize).clamp(1, MAX_SEGMENTS);
+        for &edge in patch_edges {
+            let chain = find(&mut chains, edge);
+            segments[chain] = segments[chain].max(needed);
+        }
+    }
+    edges
+        .iter()
+        .map(|&[bottom, _, left, _]| (segments[find(&mut chains, bottom)], segments[find(&mut chains, left)]))
+        .collect()
+}
+
+/// Identifies an edge by its control points in either direction. Edges
+/// collapsed to a point, like the pole of a revolved patch, are never
+/// shared, as the patches around them are split along other edges.
+fn edge_id(curve: [Vec3; 4], ids: &mut HashMap<[[u32; 3]; 4], usize>, chains: &mut Vec<usize>) -> usize {
+    // adding 0 turns -0 into 0 so both have the same bits
+    let bits = |point: &Vec3| [point.x + 0.0, point.y + 0.0, point.z + 0.0].map(f32::to_bits);
+    let forward = curve.each_ref().map(bits);
+    let mut backward = forward;
+    backward.reverse();
+    let mut new_id = || {
+        chains.push(chains.len());
+        chains.len() - 1
+    };
+    if forward.iter().all(|point| *point == forward[0]) {
+        return new_id();
+    }
+    *ids.entry(forward.min(backward)).or_insert_with(new_id)
+}
+
+/// Chain an edge belongs to, in a union-find forest of edges.
+fn find(chains: &mut [usize], mut edge: usize) -> usize {
+    while chains[edge] != edge {
+        chains[edge] = chains[chains[edge]];
+        edge = chains[edge];
+    }
+    edge
+}
+
+fn join(chains: &mut [usize], a: usize, b: usize) {
+    let (a, b) = (find(chains, a), find(chains, b));
+    chains[a] = b;
+}
+
+/// Newell's teapot from his 32 patches, turned to stand on the origin with
+/// y up: 3.15 units tall, with the spout along +x.
+pub fn teapot() -> Vec<BezierPatch> {
+    TEAPOT_PATCHES
+        .iter()
+        .map(|patch| {
+            BezierPatch::new(patch.map(|index| {
+                let [x, y, z] = TEAPOT_VERTICES[index - 1];
+                Vec3::new(x, z, -y)
+            }))
+        })
+        .collect()
+}
+
+/// Control points of each of Newell's 32 patches, numbered from 1 as in
+/// the published data: rim, body, handle, spout, lid and bottom.
+const TEAPOT_PATCHES: [[usize; 16]; 32] = [
+    [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
+    [4, 17, 18, 19, 8, 20, 21, 22, 12, 23, 24, 25, 16, 26, 27, 28],
+    [19, 29, 30, 31, 22, 32, 33, 34, 25, 35, 36, 37, 28, 38, 39, 40],
+    [31, 41, 42, 1, 34, 43, 44, 5, 37, 45, 46, 9, 40, 47, 48, 13],
+    [13, 14, 15, 16, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60],
+    [16, 26, 27, 28, 52, 61, 62, 63, 56, 64, 65, 66, 60, 67, 68, 69],
+    [28, 38, 39, 40, 63, 70, 71, 72, 66, 73, 74, 75, 69, 76, 77, 78],
+    [40, 47, 48, 13, 72, 79, 80, 49, 75, 81, 82, 53, 78, 83, 84, 57],
+    [57, 58, 59, 60, 85, 86, 87, 88, 89, 90, 91, 92, 93, 94, 95, 96],
+    [60, 67, 68, 69, 88, 97, 98, 99, 92, 100, 101, 102, 96, 103, 104, 105],
+    [69, 76, 77, 78, 99, 106, 107, 108, 102, 109, 110, 111, 105, 112, 113, 114],
+    [78, 83, 84, 57, 108, 115, 116, 85, 111, 117, 118, 89, 114, 119, 120, 93],
+    [121, 122, 123, 124, 125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135, 136],
+    [124, 137, 138, 121, 128, 139, 140, 125, 132, 141, 142, 129, 136, 143, 144, 133],
+    [133, 134, 135, 136, 145, 146, 147, 148, 149, 150, 151, 152, 69, 153, 154, 155],
+    [136, 143, 144, 133, 148, 156, 157, 145, 152, 158, 159, 149, 155, 160, 161, 69],
+    [162, 163, 164, 165, 166, 167, 168, 169, 170, 171, 172, 173, 174, 175, 176, 177],
+    [165, 178, 179, 162, 169, 180, 181, 166, 173, 182, 183, 170, 177, 184, 185, 174],
+    [174, 175, 176, 177, 186, 187, 188, 189, 190, 191, 192, 193, 194, 195, 196, 197],
+    [177, 184, 185, 174, 189, 198, 199, 186, 193, 200, 201, 190, 197, 202, 203, 194],
+    [204, 204, 204, 204, 207, 208, 209, 210, 211, 211, 211, 211, 212, 213, 214, 215],
+    [204, 204, 204, 204, 210, 217, 218, 219, 211, 211, 211, 211, 215, 220, 221, 222],
+    [204, 204, 204, 204, 219, 224, 225, 226, 211, 211, 211, 211, 222, 227, 228, 229],
+    [204, 204, 204, 204, 226, 230, 231, 207, 211, 211, 211, 211, 229, 232, 233, 212],
+    [212, 213, 214, 215, 234, 235, 236, 237, 238, 239, 240, 241, 242, 243, 244, 245],
+    [215, 220, 221, 222, 237, 246, 247, 248, 241, 249, 250, 251, 245, 252, 253, 254],
+    [222, 227, 228, 229, 248, 255, 256, 257, 251, 258, 259, 260, 254, 261, 262, 263],
+    [229, 232, 233, 212, 257, 264, 265, 234, 260, 266, 267, 238, 263, 268, 269, 242],
+    [270, 270, 270, 270, 279, 280, 281, 282, 275, 276, 277, 278, 271, 272, 273, 274],
+    [270, 270, 270, 270, 282, 289, 290, 291, 278, 286, 287, 288, 274, 283, 284, 285],
+    [270, 270, 270, 270, 291, 298, 299, 300, 288, 295, 296, 297, 285, 292, 293, 294],
+    [270, 270, 270, 270, 300, 305, 306, 279, 297, 303, 304, 275, 294, 301, 302, 271],
+];
+
+/// Newell's 306 control points, with z up. Four of them, nudged off the
+/// top of the lid, are not used by any patch.
+const TEAPOT_VERTICES: [[f32; 3]; 306] = [
+    [1.4, 0.0, 2.4],
+    [1.4, -0.784, 2.4],
+    [0.784, -1.4, 2.4],
+    [0.0, -1.4, 2.4],
+    [1.3375, 0.0, 2.53125],
+    [1.3375, -0.749, 2.53125],
+    [0.749, -1.3375, 2.53125],
+    [0.0, -1.3375, 2.53125],
+    [1.4375, 0.0, 2.53125],
+    [1.4375, -0.805, 2.53125],
+    [0.805, -1.4375, 2.53125],
+    [0.0, -1.4375, 2.53125],
+    [1.5, 0.0, 2.4],
+    [1.5, -0.84, 2.4],
+    [0.84, -1.5, 2.4],
+    [0.0, -1.5, 2.4],
+    [-0.784, -1.4, 2.4],
+    [-1.4, -0.784, 2.4],
+    [-1.4, 0.0, 2.4],
+    [-0.749, -1.3375, 2.53125],
+    [-1.3375, -0.749, 2.53125],
+    [-1.3375, 0.0, 2.53125],
+    [-0.805, -1.4375, 2.53125],
+    [-1.4375, -0.805, 2.53125],
+    [-1.4375, 0.0, 2.53125],
+    [-0.84, -1.5, 2.4],
+    [-1.5, -0.84, 2.4],
+    [-1.5, 0.0, 2.4],
+    [-1.4, 0.784, 2.4],
+    [-0.784, 1.4, 2.4],
+    [0.0, 1.4, 2.4],
+    [-1.3375, 0.749, 2.53125],
+    [-0.749, 1.3375, 2.53125],
+    [0.0, 1.3375, 2.53125],
+    [-1.4375, 0.805, 2.53125],
+    [-0.805, 1.4375, 2.53125],
+    [0.0, 1.4375, 2.53125],
+    [-1.5, 0.84, 2.4],
+    [-0.84, 1.5, 2.4],
+    [0.0, 1.5, 2.4],
+    [0.784, 1.4, 2.4],
+    [1.4, 0.784, 2.4],
+    [0.749, 1.3375, 2.53125],
+    [1.3375, 0.749, 2.53125],
+    [0.805, 1.4375, 2.53125],
+    [1.4375, 0.805, 2.53125],
+    [0.84, 1.5, 2.4],
+    [1.5, 0.84, 2.4],
+    [1.75, 0.0, 1.875],
+    [1.75, -0.98, 1.875],
+    [0.98, -1.75, 1.875],
+    [0.0, -1.75, 1.875],
+    [2.0, 0.0, 1.35],
+    [2.0, -1.12, 1.35],
+    [1.12, -2.0, 1.35],
+    [0.0, -2.0, 1.35],
+    [2.0, 0.0, 0.9],
+    [2.0, -1.12, 0.9],
+    [1.12, -2.0, 0.9],
+    [0.0, -2.0, 0.9],
+    [-0.98, -1.75, 1.875],
+    [-1.75, -0.98, 1.875],
+    [-1.75, 0.0, 1.875],
+    [-1.12, -2.0, 1.35],
+    [-2.0, -1.12, 1.35],
+    [-2.0, 0.0, 1.35],
+    [-1.12, -2.0, 0.9],
+    [-2.0, -1.12, 0.9],
+    [-2.0, 0.0, 0.9],
+    [-1.75, 0.98, 1.875],
+    [-0.98, 1.75, 1.875],
+    [0.0, 1.75, 1.875],
+    [-2.0, 1.12, 1.35],
+    [-1.12, 2.0, 1.35],
+    [0.0, 2.0, 1.35],
+    [-2.0, 1.12, 0.9],
+    [-1.12, 2.0, 0.9],
+    [0.0, 2.0, 0.9],
+    [0.98, 1.75, 1.875],
+    [1.75, 0.98, 1.875],
+    [1.12, 2.0, 1.35],
+    [2.0, 1.12, 1.35],
+    [1.12, 2.0, 0.9],
+    [2.0, 1.12, 0.9],
+    [2.0, 0.0, 0.45],
+    [2.0, -1.12, 0.45],
+    [1.12, -2.0, 0.45],
+    [0.0, -2.0, 0.45],
+    [1.5, 0.0, 0.225],
+    [1.5, -0.84, 0.225],
+    [0.84, -1.5, 0.225],
+    [0.0, -1.5, 0.225],
+    [1.5, 0.0, 0.15],
+    [1.5, -0.84, 0.15],
+    [0.84, -1.5, 0.15],
+    [0.0, -1.5, 0.15],
+    [-1.12, -2.0, 0.45],
+    [-2.0, -1.12, 0.45],
+    [-2.0, 0.0, 0.45],
+    [-0.84, -1.5, 0.225],
+    [-1.5, -0.84, 0.225],
+    [-1.5, 0.0, 0.225],
+    [-0.84, -1.5, 0.15],
+    [-1.5, -0.84, 0.15],
+    [-1.5, 0.0, 0.15],
+    [-2.0, 1.12, 0.45],
+    [-1.12, 2.0, 0.45],
+    [0.0, 2.0, 0.45],
+    [-1.5, 0.84, 0.225],
+    [-0.84, 1.5, 0.225],
+    [0.0, 1.5, 0.225],
+    [-1.5, 0.84, 0.15],
+    [-0.84, 1.5, 0.15],
+    [0.0, 1.5, 0.15],
+    [1.12, 2.0, 0.45],
+    [2.0, 1.12, 0.45],
+    [0.84, 1.5, 0.225],
+    [1.5, 0.84, 0.225],
+    [0.84, 1.5, 0.15],
+    [1.5, 0.84, 0.15],
+    [-1.6, 0.0, 2.025],
+    [-1.6, -0.3, 2.025],
+    [-1.5, -0.3, 2.25],
+    [-1.5, 0.0, 2.25],
+    [-2.3, 0.0, 2.025],
+    [-2.3, -0.3, 2.025],
+    [-2.5, -0.3, 2.25],
+    [-2.5, 0.0, 2.25],
+    [-2.7, 0.0, 2.025],
+    [-2.7, -0.3, 2.025],
+    [-3.0, -0.3, 2.25],
+    [-3.0, 0.0, 2.25],
+    [-2.7, 0.0, 1.8],
+    [-2.7, -0.3, 1.8],
+    [-3.0, -0.3, 1.8],
+    [-3.0, 0.0, 1.8],
+    [-1.5, 0.3, 2.25],
+    [-1.6, 0.3, 2.025],
+    [-2.5, 0.3, 2.25],
+    [-2.3, 0.3, 2.025],
+    [-3.0, 0.3, 2.25],
+    [-2.7, 0.3, 2.025],
+    [-3.0, 0.3, 1.8],
+    [-2.7, 0.3, 1.8],
+    [-2.7, 0.0, 1.575],
+    [-2.7, -0.3, 1.575],
+    [-3.0, -0.3, 1.35],
+    [-3.0, 0.0, 1.35],
+    [-2.5, 0.0, 1.125],
+    [-2.5, -0.3, 1.125],
+    [-2.65, -0.3, 0.9375],
+    [-2.65, 0.0, 0.9375],
+    [-2.0, -0.3, 0.9],
+    [-1.9, -0.3, 0.6],
+    [-1.9, 0.0, 0.6],
+    [-3.0, 0.3, 1.35],
+    [-2.7, 0.3, 1.575],
+    [-2.65, 0.3, 0.9375],
+    [-2.5, 0.3, 1.125],
+    [-1.9, 0.3, 0.6],
+    [-2.0, 0.3, 0.9],
+    [1.7, 0.0, 1.425],
+    [1.7, -0.66, 1.425],
+    [1.7, -0.66, 0.6],
+    [1.7, 0.0, 0.6],
+    [2.6, 0.0, 1.425],
+    [2.6, -0.66, 1.425],
+    [3.1, -0.66, 0.825],
+    [3.1, 0.0, 0.825],
+    [2.3, 0.0, 2.1],
+    [2.3, -0.25, 2.1],
+    [2.4, -0.25, 2.025],
+    [2.4, 0.0, 2.025],
+    [2.7, 0.0, 2.4],
+    [2.7, -0.25, 2.4],
+    [3.3, -0.25, 2.4],
+    [3.3, 0.0, 2.4],
+    [1.7, 0.66, 0.6],
+    [1.7, 0.66, 1.425],
+    [3.1, 0.66, 0.825],
+    [2.6, 0.66, 1.425],
+    [2.4, 0.25, 2.025],
+    [2.3, 0.25, 2.1],
+    [3.3, 0.25, 2.4],
+    [2.7, 0.25, 2.4],
+    [2.8, 0.0, 2.475],
+    [2.8, -0.25, 2.475],
+    [3.525, -0.25, 2.49375],
+    [3.525, 0.0, 2.49375],
+    [2.9, 0.0, 2.475],
+    [2.9, -0.15, 2.475],
+    [3.45, -0.15, 2.5125],
+    [3.45, 0.0, 2.5125],
+    [2.8, 0.0, 2.4],
+    [2.8, -0.15, 2.4],
+    [3.2, -0.15, 2.4],
+    [3.2, 0.0, 2.4],
+    [3.525, 0.25, 2.49375],
+    [2.8, 0.25, 2.475],
+    [3.45, 0.15, 2.5125],
+    [2.9, 0.15, 2.475],
+    [3.2, 0.15, 2.4],
+    [2.8, 0.15, 2.4],
+    [0.0, 0.0, 3.15],
+    [0.0, -0.002, 3.15],
+    [0.002, 0.0, 3.15],
+    [0.8, 0.0, 3.15],
+    [0.8, -0.45, 3.15],
+    [0.45, -0.8, 3.15],
+    [0.0, -0.8, 3.15],
+    [0.0, 0.0, 2.85],
+    [0.2, 0.0, 2.7],
+    [0.2, -0.112, 2.7],
+    [0.112, -0.2, 2.7],
+    [0.0, -0.2, 2.7],
+    [-0.002, 0.0, 3.15],
+    [-0.45, -0.8, 3.15],
+    [-0.8, -0.45, 3.15],
+    [-0.8, 0.0, 3.15],
+    [-0.112, -0.2, 2.7],
+    [-0.2, -0.112, 2.7],
+    [-0.2, 0.0, 2.7],
+    [0.0, 0.002, 3.15],
+    [-0.8, 0.45, 3.15],
+    [-0.45, 0.8, 3.15],
+    [0.0, 0.8, 3.15],
+    [-0.2, 0.112, 2.7],
+    [-0.112, 0.2, 2.7],
+    [0.0, 0.2, 2.7],
+    [0.45, 0.8, 3.15],
+    [0.8, 0.45, 3.15],
+    [0.112, 0.2, 2.7],
+    [0.2, 0.112, 2.7],
+    [0.4, 0.0, 2.55],
+    [0.4, -0.224, 2.55],
+    [0.224, -0.4, 2.55],
+    [0.0, -0.4, 2.55],
+    [1.3, 0.0, 2.55],
+    [1.3, -0.728, 2.55],
+    [0.728, -1.3, 2.55],
+    [0.0, -1.3, 2.55],
+    [1.3, 0.0, 2.4],
+    [1.3, -0.728, 2.4],
+    [0.728, -1.3, 2.4],
+    [0.0, -1.3, 2.4],
+    [-0.224, -0.4, 2.55],
+    [-0.4, -0.224, 2.55],
+    [-0.4, 0.0, 2.55],
+    [-0.728, -1.3, 2.55],
+    [-1.3, -0.728, 2.55],
+    [-1.3, 0.0, 2.55],
+    [-0.728, -1.3, 2.4],
+    [-1.3, -0.728, 2.4],
+    [-1.3, 0.0, 2.4],
+    [-0.4, 0.224, 2.55],
+    [-0.224, 0.4, 2.55],
+    [0.0, 0.4, 2.55],
+    [-1.3, 0.728, 2.55],
+    [-0.728, 1.3, 2.55],
+    [0.0, 1.3, 2.55],
+    [-1.3, 0.728, 2.4],
+    [-0.728, 1.3, 2.4],
+    [0.0, 1.3, 2.4],
+    [0.224, 0.4, 2.55],
+    [0.4, 0.224, 2.55],
+    [0.728, 1.3, 2.55],
+    [1.3, 0.728, 2.55],
+    [0.728, 1.3, 2.4],
+    [1.3, 0.728, 2.4],
+    [0.0, 0.0, 0.0],
+    [1.5, 0.0, 0.15],
+    [1.5, 0.84, 0.15],
+    [0.84, 1.5, 0.15],
+    [0.0, 1.5, 0.15],
+    [1.5, 0.0, 0.075],
+    [1.5, 0.84, 0.075],
+    [0.84, 1.5, 0.075],
+    [0.0, 1.5, 0.075],
+    [1.425, 0.0, 0.0],
+    [1.425, 0.798, 0.0],
+    [0.798, 1.425, 0.0],
+    [0.0, 1.425, 0.0],
+    [-0.84, 1.5, 0.15],
+    [-1.5, 0.84, 0.15],
+    [-1.5, 0.0, 0.15],
+    [-0.84, 1.5, 0.075],
+    [-1.5, 0.84, 0.075],
+    [-1.5, 0.0, 0.075],
+    [-0.798, 1.425, 0.0],
+    [-1.425, 0.798, 0.0],
+    [-1.425, 0.0, 0.0],
+    [-1.5, -0.84, 0.15],
+    [-0.84, -1.5, 0.15],
+    [0.0, -1.5, 0.15],
+    [-1.5, -0.84, 0.075],
+    [-0.84, -1.5, 0.075],
+    [0.0, -1.5, 0.075],
+    [-1.425, -0.798, 0.0],
+    [-0.798, -1.425, 0.0],
+    [0.0, -1.425, 0.0],
+    [0.84, -1.5, 0.15],
+    [1.5, -0.84, 0.15],
+    [0.84, -1.5, 0.075],
+    [1.5, -0.84, 0.075],
+    [0.798, -1.425, 0.0],
+    [1.425, -0.798, 0.0],
+];
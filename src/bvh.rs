@@ -0,0 +1,163 @@
+use crate::{aabb::Aabb, ray::Ray, vec3::Vec3};
+
+const BINS: usize = 12;
+const MAX_LEAF_SIZE: usize = 4;
+
+/// Bounding volume hierarchy over items identified by their index, built with
+/// binned surface area heuristic splits. Used by primitives that are made of
+/// many small parts, like triangle meshes.
+#[derive(Debug, Clone)]
+pub struct Bvh {
+    nodes: Vec<BvhNode>,
+    items: Vec<usize>,
+}
+
+/// Leaves hold `count` items starting at `first`, inner nodes have `count` 0
+/// and their children at `first` and `first + 1`.
+#[derive(Debug, Clone)]
+struct BvhNode {
+    bounds: Aabb,
+    first: usize,
+    count: usize,
+}
+
+impl Bvh {
+    pub fn new(bounds: &[Aabb]) -> Self {
+        let mut bvh = Bvh {
+            nodes: vec![],
+            items: (0..bounds.len()).collect(),
+        };
+        if bounds.is_empty() {
+            return bvh;
+        }
+        let centers: Vec<Vec3> = bounds.iter().map(|aabb| aabb.center()).collect();
+        bvh.nodes.push(BvhNode {
+            bounds: bounds[0],
+            first: 0,
+            count: bounds.len(),
+        });
+        bvh.subdivide(0, bounds, &centers);
+        bvh
+    }
+
+    pub fn bounds(&self) -> Option<Aabb> {
+        self.nodes.first().map(|node| node.bounds)
+    }
+
+    fn subdivide(&mut self, node: usize, bounds: &[Aabb], centers: &[Vec3]) {
+        let (first, count) = (self.nodes[node].first, self.nodes[node].count);
+        let items = &mut self.items[first..first + count];
+        let node_bounds = items
+            .iter()
+            .skip(1)
+            .fold(bounds[items[0]], |aabb, &item| aabb.union(&bounds[item]));
+        self.nodes[node].bounds = node_bounds;
+        if count <= MAX_LEAF_SIZE {
+            return;
+        }
+
+        let center_bounds = Aabb::from_points(&items.iter().map(|&item| centers[item]).collect::<Vec<_>>());
+        let extent = center_bounds.max.sub(&center_bounds.min);
+        let axis_value = |v: &Vec3, axis: usize| [v.x, v.y, v.z][axis];
+
+        // best split as (cost, axis, bin)
+        let mut best: Option<(f32, usize, usize)> = None;
+        for axis in 0..3 {
+            let axis_extent = axis_value(&extent, axis);
+            if axis_extent <= 0.0 {
+                continue;
+            }
+            let axis_min = axis_value(&center_bounds.min, axis);
+            let bin_of = |item: usize| {
+                (((axis_value(&centers[item], axis) - axis_min) / axis_extent * BINS as f32) as usize).min(BINS - 1)
+            };
+            let mut bin_bounds: [Option<Aabb>; BINS] = [None; BINS];
+            let mut bin_counts = [0; BINS];
+            for &item in items.iter() {
+                let bin = bin_of(item);
+                bin_counts[bin] += 1;
+                bin_bounds[bin] = Some(bin_bounds[bin].map_or(bounds[item], |aabb| aabb.union(&bounds[item])));
+            }
+            for split in 1..BINS {
+                let side_cost = |range: std::ops::Range<usize>| {
+                    let count: usize = bin_counts[range.clone()].iter().sum();
+                    let area = bin_bounds[range]
+                        .iter()
+                        .flatten()
+                        .copied()
+                        .reduce(|a, b| a.union(&b))
+                        .map_or(0.0, |aabb| aabb.surface_area());
+                    count as f32 * area
+                };
+                let cost = side_cost(0..split) + side_cost(split..BINS);
+                if best.is_none_or(|(best_cost, _, _)| cost < best_cost) {
+                    best = Some((cost, axis, split));
+                }
+            }
+        }
+        let Some((cost, axis, split)) = best else {
+            return;
+        };
+        if cost >= count as f32 * node_bounds.surface_area() {
+            return;
+        }
+
+        let axis_min = axis_value(&center_bounds.min, axis);
+        let axis_extent = axis_value(&extent, axis);
+        let split_value = axis_min + axis_extent * split as f32 / BINS as f32;
+        let mut left = 0;
+        for i in 0..count {
+            if axis_value(&centers[items[i]], axis) < split_value {
+                items.swap(i, left);
+                left += 1;
+            }
+        }
+        if left == 0 || left == count {
+            return;
+        }
+
+        let children = self.nodes.len();
+        self.nodes.push(BvhNode {
+            bounds: node_bounds,
+            first,
+            count: left,
+        });
+        self.nodes.push(BvhNode {
+            bounds: node_bounds,
+            first: first + left,
+            count: count - left,
+        });
+        self.nodes[node].first = children;
+        self.nodes[node].count = 0;
+        self.subdivide(children, bounds, centers);
+        self.subdivide(children + 1, bounds, centers);
+    }
+
+    /// Calls `hit_item` with every item whose bounds the ray might hit before
+    /// the closest hit so far. It returns the item's hit distance, if any,
+    /// which then shortens the ray.
+    pub fn traverse(&self, ray: &Ray, t_min: f32, t_max: f32, mut hit_item: impl FnMut(usize, f32) -> Option<f32>) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let inverse_direction = Vec3::new(1.0 / ray.direction.x, 1.0 / ray.direction.y, 1.0 / ray.direction.z);
+        let mut closest = t_max;
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !node.bounds.hit(ray, &inverse_direction, t_min, closest) {
+                continue;
+            }
+            if node.count == 0 {
+                stack.push(node.first);
+                stack.push(node.first + 1);
+                continue;
+            }
+            for &item in &self.items[node.first..node.first + node.count] {
+                if let Some(t) = hit_item(item, closest) {
+                    closest = t;
+                }
+            }
+        }
+    }
+}
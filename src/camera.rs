@@ -7,17 +7,23 @@ use noise::{NoiseFn, Perlin};
 use rand::rand_core::le;
 
 use crate::{
+    filter::Filter,
+    frame::Frame,
     hitable::{self, Hitable},
     hitable_list::{self, HitableList},
+    lens::LensSystem,
     material::Material,
-    ray,
-    util::random_double_range,
+    microfacet, ray,
+    spectrum::{self, SampledSpectrum, Wavelengths},
+    texture::{NormalMap, ScalarTexture},
+    util::random_double,
     vec3::{self, Vec3},
 };
 
 const MAX_DEPTH: u32 = 10;
 const THREADS: usize = 16;
 
+#[derive(Clone)]
 pub struct Camera {
     pub image_width: u32,
     pub image_height: u32,
@@ -37,6 +43,58 @@ pub struct Camera {
     pub focus_distance: f32,
     defocus_dist_u: Vec3,
     defocus_dist_v: Vec3,
+    lens_system: Option<LensSystem>,
+    film_diagonal: f32,
+    sensor_shift: (f32, f32),
+    focal_plane_normal: Vec3,
+    distortion: Distortion,
+    filter: Filter,
+    spectral: bool,
+}
+
+/// What `Camera::autofocus` puts the plane of focus on.
+#[derive(Debug, Clone)]
+pub enum FocusTarget {
+    /// Whatever is visible through the centre of pixel (x, y).
+    Pixel(u32, u32),
+    /// An object added with `HitableList::add_named`.
+    Object(String),
+}
+
+/// Brown–Conrady lens distortion with radial (k1, k2, k3) and tangential
+/// (p1, p2) coefficients, in the convention used by OpenCV calibrations.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Distortion {
+    pub k1: f32,
+    pub k2: f32,
+    pub k3: f32,
+    pub p1: f32,
+    pub p2: f32,
+}
+
+impl Distortion {
+    pub fn new(k1: f32, k2: f32, k3: f32, p1: f32, p2: f32) -> Self {
+        Distortion { k1, k2, k3, p1, p2 }
+    }
+
+    fn is_identity(&self) -> bool {
+        self.k1 == 0.0 && self.k2 == 0.0 && self.k3 == 0.0 && self.p1 == 0.0 && self.p2 == 0.0
+    }
+
+    /// Maps distorted normalized image coordinates back to undistorted ones,
+    /// inverting the model by fixed point iteration.
+    pub fn undistort(&self, x: f32, y: f32) -> (f32, f32) {
+        let (mut ux, mut uy) = (x, y);
+        for _ in 0..20 {
+            let r2 = ux * ux + uy * uy;
+            let radial = 1.0 + r2 * (self.k1 + r2 * (self.k2 + r2 * self.k3));
+            let dx = 2.0 * self.p1 * ux * uy + self.p2 * (r2 + 2.0 * ux * ux);
+            let dy = self.p1 * (r2 + 2.0 * uy * uy) + 2.0 * self.p2 * ux * uy;
+            ux = (x - dx) / radial;
+            uy = (y - dy) / radial;
+        }
+        (ux, uy)
+    }
 }
 
 impl Camera {
@@ -96,9 +154,249 @@ impl Camera {
             focus_distance,
             defocus_dist_u,
             defocus_dist_v,
+            lens_system: None,
+            film_diagonal: 0.0,
+            sensor_shift: (0.0, 0.0),
+            focal_plane_normal: w,
+            distortion: Distortion::default(),
+            filter: Filter::default(),
+            spectral: false,
+        }
+    }
+
+    /// Replaces the thin lens model with a traced lens prescription. The lens is
+    /// focused at `focus_distance` and `fov` is implied by the film size and
+    /// the lens' focal length; `film_diagonal` is in millimetres. The traced
+    /// lens brings its own distortion and plane of focus, so `with_tilt` and
+    /// `with_distortion` have no effect on a camera with a lens system.
+    pub fn with_lens_system(mut self, mut lens_system: LensSystem, film_diagonal: f32) -> Self {
+        lens_system.focus(self.focus_distance);
+        self.lens_system = Some(lens_system);
+        self.film_diagonal = film_diagonal * 0.001;
+        self
+    }
+
+    /// Moves the plane of focus, keeping the field of view and all other settings.
+    pub fn with_focus_distance(self, focus_distance: f32) -> Self {
+        let refocused = Camera::new(
+            self.defocus_angle,
+            focus_distance,
+            self.fov,
+            self.camera_center,
+            self.look_at,
+            self.up,
+            self.image_width,
+            self.samples_per_pixel,
+        );
+        let mut camera = Camera {
+            pixel_00_location: refocused.pixel_00_location,
+            pixel_delta_u: refocused.pixel_delta_u,
+            pixel_delta_v: refocused.pixel_delta_v,
+            focus_distance,
+            defocus_dist_u: refocused.defocus_dist_u,
+            defocus_dist_v: refocused.defocus_dist_v,
+            ..self
+        };
+        if let Some(lens_system) = camera.lens_system.as_mut() {
+            lens_system.focus(focus_distance);
+        }
+        camera
+    }
+
+    /// Casts a probe ray at `target` and focuses on the first surface it hits.
+    /// The camera is left unchanged if the probe misses. Call it after
+    /// `with_tilt`, `with_sensor_shift` and `with_distortion` so the tilted
+    /// plane of focus goes through the target.
+    pub fn autofocus(self, world: &HitableList, target: FocusTarget) -> Self {
+        let hit = match &target {
+            FocusTarget::Pixel(x, y) => {
+                let ray = self.probe_ray(*x as f32 + 0.5, *y as f32 + 0.5);
+                world.hit(&ray, 0.001, f32::INFINITY).map(|hit_record| hit_record.point)
+            }
+            FocusTarget::Object(name) => world.get(name).and_then(|object| {
+                let center = object.bounding_box()?.center();
+                let ray = ray::Ray::new(self.camera_center, center.sub(&self.camera_center));
+                Some(
+                    object
+                        .hit(&ray, 0.001, f32::INFINITY)
+                        .map_or(center, |hit_record| hit_record.point),
+                )
+            }),
+        };
+        match hit {
+            // focus distance is where the plane of focus, tilted or not,
+            // crosses the viewing axis
+            Some(point) => {
+                // a lens system brings its own, untilted plane of focus
+                let normal = if self.lens_system.is_some() {
+                    self.w
+                } else {
+                    self.focal_plane_normal
+                };
+                let focus_distance = -point.sub(&self.camera_center).dot(&normal) / self.w.dot(&normal);
+                self.with_focus_distance(focus_distance)
+            }
+            None => self,
         }
     }
 
+    /// Chief ray through a pixel position, without depth of field.
+    fn probe_ray(&self, x: f32, y: f32) -> ray::Ray {
+        let (x, y) = self.sensor_position(x, y);
+        if let Some(lens_system) = &self.lens_system
+            && let Some((ray, _)) = self.trace_lens_system(lens_system, x, y, 0.0, 0.0)
+        {
+            return ray;
+        }
+        let pixel_center = self
+            .pixel_00_location
+            .add(&self.pixel_delta_u.multiply(x - 0.5))
+            .add(&self.pixel_delta_v.multiply(y - 0.5));
+        ray::Ray::new(self.camera_center, pixel_center.sub(&self.camera_center))
+    }
+
+    /// Shifts the sensor parallel to the image plane, as a fraction of the image
+    /// width and height. Positive values move the view right and up while
+    /// keeping verticals parallel, like an architectural shift lens.
+    pub fn with_sensor_shift(mut self, shift_x: f32, shift_y: f32) -> Self {
+        self.sensor_shift = (shift_x, shift_y);
+        self
+    }
+
+    /// Tilts the plane of focus (Scheimpflug principle). `tilt` rotates it around
+    /// the horizontal axis so the top recedes for positive angles, `swing`
+    /// around the vertical axis so the right side recedes. Angles in degrees.
+    /// Only applies to the thin lens model, not to a lens system.
+    pub fn with_tilt(mut self, tilt: f32, swing: f32) -> Self {
+        let (tilt, swing) = (tilt.to_radians(), swing.to_radians());
+        self.focal_plane_normal = self
+            .w
+            .multiply(tilt.cos() * swing.cos())
+            .add(&self.v.multiply(tilt.sin()))
+            .add(&self.u.multiply(swing.sin()))
+            .normalize();
+        self
+    }
+
+    /// Applies Brown–Conrady distortion to the thin lens projection, so renders
+    /// line up with footage from a calibrated camera. Ignored once a lens
+    /// system is set.
+    pub fn with_distortion(mut self, distortion: Distortion) -> Self {
+        self.distortion = distortion;
+        self
+    }
+
+    /// Sets the pixel reconstruction filter, replacing the default 1.5 pixel box.
+    pub fn with_filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Traces a few wavelengths along each path instead of RGB, for
+    /// dispersion and blackbody light. Colours are turned into spectra where
+    /// they are used and the result back into RGB at the film.
+    pub fn with_spectral(mut self) -> Self {
+        self.spectral = true;
+        self
+    }
+
+    /// Maps a continuous pixel position to the undistorted position on the
+    /// viewport, applying sensor shift and lens distortion.
+    fn sensor_position(&self, x: f32, y: f32) -> (f32, f32) {
+        let x = x + self.sensor_shift.0 * self.image_width as f32;
+        let y = y - self.sensor_shift.1 * self.image_height as f32;
+        if self.distortion.is_identity() || self.lens_system.is_some() {
+            return (x, y);
+        }
+
+        // normalized coordinates around the optical axis, at unit distance,
+        // with y pointing down as in OpenCV
+        let scale = self.pixel_delta_u.length() / self.focus_distance;
+        let half_width = self.image_width as f32 / 2.0;
+        let half_height = self.image_height as f32 / 2.0;
+        let (ux, uy) = self
+            .distortion
+            .undistort((x - half_width) * scale, (y - half_height) * scale);
+        (ux / scale + half_width, uy / scale + half_height)
+    }
+
+    /// Generates a camera ray through pixel (i, j), offset from its centre by the
+    /// filter sample, and the weight of the ray. Returns `None` for rays blocked
+    /// inside the lens system.
+    fn get_ray(&self, i: u32, j: u32, offset_x: f32, offset_y: f32) -> Option<(ray::Ray, f32)> {
+        let (x, y) = self.sensor_position(i as f32 + 0.5 + offset_x, j as f32 + 0.5 + offset_y);
+        if let Some(lens_system) = &self.lens_system {
+            return self.get_lens_system_ray(lens_system, x, y);
+        }
+
+        let pixel_center = self
+            .pixel_00_location
+            .add(&self.pixel_delta_u.multiply(x - 0.5))
+            .add(&self.pixel_delta_v.multiply(y - 0.5));
+        // move the target onto the tilted plane of focus, along the pinhole ray
+        let pinhole_direction = pixel_center.sub(&self.camera_center);
+        let plane_offset = -self.focus_distance * self.w.dot(&self.focal_plane_normal);
+        let focus_point = self.camera_center.add(
+            &pinhole_direction.multiply(plane_offset / pinhole_direction.dot(&self.focal_plane_normal)),
+        );
+        let ray_origin = if self.defocus_angle < 0.0 {
+            self.camera_center
+        } else {
+            let random = Vec3::random_in_unit_disk();
+            self.camera_center
+                .add(&self.defocus_dist_u.multiply(random.x))
+                .add(&self.defocus_dist_v.multiply(random.y))
+        };
+        let ray_direction = focus_point.sub(&ray_origin);
+        Some((ray::Ray::new(ray_origin, ray_direction), 1.0))
+    }
+
+    fn get_lens_system_ray(&self, lens_system: &LensSystem, x: f32, y: f32) -> Option<(ray::Ray, f32)> {
+        let random = Vec3::random_in_unit_disk();
+        self.trace_lens_system(lens_system, x, y, random.x, random.y)
+    }
+
+    /// Traces from pixel position (x, y) on the film towards a point on the rear
+    /// element, given in coordinates relative to its aperture.
+    fn trace_lens_system(
+        &self,
+        lens_system: &LensSystem,
+        x: f32,
+        y: f32,
+        rear_x: f32,
+        rear_y: f32,
+    ) -> Option<(ray::Ray, f32)> {
+        let image_diagonal = (self.image_width as f32).hypot(self.image_height as f32);
+        let film_width = self.film_diagonal * self.image_width as f32 / image_diagonal;
+        let film_height = self.film_diagonal * self.image_height as f32 / image_diagonal;
+
+        // the lens flips the image, so the film is mirrored in both axes
+        let film_point = Vec3::new(
+            -(x / self.image_width as f32 - 0.5) * film_width,
+            (y / self.image_height as f32 - 0.5) * film_height,
+            0.0,
+        );
+        let rear_point = Vec3::new(
+            rear_x * lens_system.rear_aperture(),
+            rear_y * lens_system.rear_aperture(),
+            lens_system.rear_z(),
+        );
+        let film_ray = ray::Ray::new(film_point, rear_point.sub(&film_point));
+        let lens_ray = lens_system.trace_from_film(&film_ray)?;
+
+        let origin = self
+            .camera_center
+            .add(&self.u.multiply(lens_ray.origin.x))
+            .add(&self.v.multiply(lens_ray.origin.y))
+            .add(&self.w.multiply(lens_ray.origin.z));
+        let direction = self
+            .u
+            .multiply(lens_ray.direction.x)
+            .add(&self.v.multiply(lens_ray.direction.y))
+            .add(&self.w.multiply(lens_ray.direction.z));
+        Some((ray::Ray::new(origin, direction), lens_system.exposure()))
+    }
+
     pub fn render(&self, world: &HitableList, ambient_light: Vec3) -> Arc<Mutex<DynamicImage>> {
         let dynamic_image = DynamicImage::new_rgb8(self.image_width, self.image_height);
 
@@ -118,51 +416,36 @@ impl Camera {
         fn render_chunk(
             image: Arc<Mutex<DynamicImage>>,
             world: &HitableList,
-            pixel_00_location: Vec3,
-            pixel_delta_u: Vec3,
-            pixel_delta_v: Vec3,
+            camera: &Camera,
             start: usize,
             end: usize,
-            camera_center: Vec3,
-            image_height: u32,
-            samples_per_pixel: u32,
-            bar: Arc<Mutex<ProgressBar>>,
-            defocus_angle: f32,
-            defocus_dist_u: Vec3,
-            defocus_dist_v: Vec3,
-            ambient_light: Vec3
+            ambient_light: Vec3,
         ) {
-            for j in 0..image_height {
+            for j in 0..camera.image_height {
                 for i in start..end {
                     let mut pixel_color = Vec3::new(0.0, 0.0, 0.0);
-                    for _ in 0..samples_per_pixel {
-                        let (offset_x, offset_y) = (
-                            random_double_range(-0.75, 0.75),
-                            random_double_range(-0.75, 0.75),
-                        );
-                        let pixel_center = pixel_00_location
-                            .add(&pixel_delta_u.multiply(i as f32 + offset_x))
-                            .add(&pixel_delta_v.multiply(j as f32 + offset_y));
-                        let ray_origin = if defocus_angle < 0.0 {
-                            camera_center
-                        } else {
-                            let random = Vec3::random_in_unit_disk();
-                            let sample = camera_center
-                                .add(&defocus_dist_u.multiply(random.x))
-                                .add(&defocus_dist_v.multiply(random.y));
-                            sample
-                        };
-                        let ray_direction = pixel_center.sub(&ray_origin);
-                        let ray = ray::Ray::new(ray_origin, ray_direction);
-                        let ray_color = ray_color(&ray, world, 0, ambient_light);
-                        pixel_color = pixel_color.add(&ray_color);
+                    let mut weight_sum = 0.0;
+                    for _ in 0..camera.samples_per_pixel {
+                        let (offset_x, offset_y, filter_weight) = camera.filter.sample();
+                        weight_sum += filter_weight;
+                        if let Some((ray, weight)) = camera.get_ray(i as u32, j, offset_x, offset_y) {
+                            let ray_color = if camera.spectral {
+                                let wavelengths = Wavelengths::sample(random_double());
+                                wavelengths.to_rgb(&spectral_color(&ray, world, 0, ambient_light, &wavelengths))
+                            } else {
+                                ray_color(&ray, world, 0, ambient_light)
+                            };
+                            pixel_color = pixel_color.add(&ray_color.multiply(weight * filter_weight));
+                        }
+                    }
+                    if weight_sum != 0.0 {
+                        pixel_color = pixel_color.divide(weight_sum);
                     }
-                    pixel_color = pixel_color.divide(samples_per_pixel as f32);
-                    // linear to gamma correction
+                    // linear to gamma correction, negative filter lobes can undershoot
                     pixel_color = Vec3::new(
-                        pixel_color.x.sqrt(),
-                        pixel_color.y.sqrt(),
-                        pixel_color.z.sqrt(),
+                        pixel_color.x.max(0.0).sqrt(),
+                        pixel_color.y.max(0.0).sqrt(),
+                        pixel_color.z.max(0.0).sqrt(),
                     );
 
                     let mut dynamic_image = image.lock().unwrap();
@@ -178,7 +461,7 @@ impl Camera {
                         j as u32,
                     );
                 }
-                bar.lock().unwrap().inc(1);
+                camera.bar.lock().unwrap().inc(1);
             }
         }
 
@@ -187,35 +470,16 @@ impl Camera {
         for (start, end) in ranges {
             let image_clone = image_mutex.clone();
             let world_clone = world.clone();
-            let pixel_00_location_clone = self.pixel_00_location.clone();
-            let pixel_delta_u_clone = self.pixel_delta_u.clone();
-            let pixel_delta_v_clone = self.pixel_delta_v.clone();
-            let camera_center_clone = self.camera_center.clone();
-            let bar = self.bar.clone();
-            let image_height_clone = self.image_height.clone();
-            let samples_per_pixel_clone = self.samples_per_pixel.clone();
-            let defocus_angle_clone = self.defocus_angle.clone();
-            let defocus_dist_u_clone = self.defocus_dist_u.clone();
-            let defocus_dist_v_clone = self.defocus_dist_v.clone();
-            let ambient_light_clone = ambient_light.clone();
+            let camera_clone = self.clone();
 
             let handle = std::thread::spawn(move || {
                 render_chunk(
                     image_clone,
                     &world_clone,
-                    pixel_00_location_clone,
-                    pixel_delta_u_clone,
-                    pixel_delta_v_clone,
+                    &camera_clone,
                     start,
                     end,
-                    camera_center_clone,
-                    image_height_clone,
-                    samples_per_pixel_clone,
-                    bar,
-                    defocus_angle_clone,
-                    defocus_dist_u_clone,
-                    defocus_dist_v_clone,
-                    ambient_light_clone,
+                    ambient_light,
                 );
             });
             handles.push(handle);
@@ -259,36 +523,30 @@ fn handle_material(
         Material::Light(color) => {
             return *color;
         }
+        Material::Blackbody { temperature, strength } => spectrum::blackbody_color(*temperature).multiply(*strength),
         Material::Metallic(metal) => {
-            let diffuse = if (metal.diffuse) {
-            
-             handle_material(
-                hit_record,
-                &Material::Diffuse(metal.albedo),
-                hitables,
-                depth,
-                ray,
-                ambient_light,
-             )
-        } else {
-            Vec3::new(0.0, 0.0, 0.0)
-        };
-
-            let reflected = ray.direction.reflect(&hit_record.normal);
-            let scattered = ray::Ray::new(
-                hit_record.point,
-                reflected.add(&Vec3::random_unit().multiply(metal.fuzz)),
-            );
-            if scattered.direction.dot(&hit_record.normal) > 0.0 {
-                let reflected = ray_color(&scattered, hitables, depth + 1, ambient_light);
-                let pairwise_multiply = Vec3::new(
-                    reflected.x * metal.albedo.x,
-                    reflected.y * metal.albedo.y,
-                    reflected.z * metal.albedo.z,
-                );
-                return pairwise_multiply.multiply(0.5).add(&diffuse.multiply(0.5));
-            }
-            return Vec3::new(0.0, 0.0, 0.0);
+            handle_material(hit_record, &metal.to_material(), hitables, depth, ray, ambient_light)
+        }
+        Material::Conductor(conductor) => sample_local(hit_record, hitables, depth, ray, ambient_light, |wo| {
+            conductor.sample(wo, hit_record)
+        }),
+        Material::Dielectric(dielectric) => sample_local(hit_record, hitables, depth, ray, ambient_light, |wo| {
+            dielectric.sample(wo, hit_record)
+        }),
+        Material::Principled(principled) => sample_local(hit_record, hitables, depth, ray, ambient_light, |wo| {
+            principled.sample(wo, hit_record)
+        }),
+        Material::Layered(layered) => sample_local(hit_record, hitables, depth, ray, ambient_light, |wo| {
+            layered.sample(wo, hit_record)
+        }),
+        Material::Mix { first, second, weight } => {
+            let cos_theta = -ray.direction.normalize().dot(&hit_record.normal);
+            let material = if random_double() < weight.value(hit_record, cos_theta) {
+                second
+            } else {
+                first
+            };
+            handle_material(hit_record, material, hitables, depth, ray, ambient_light)
         }
         Material::Diffuse(color) => {
             let next_ray_direction = hit_record.normal.add(&Vec3::random_unit());
@@ -305,23 +563,70 @@ fn handle_material(
             );
             return pairwise_multiply;
         }
-        Material::CheckerBoard(mat1, mat2, length) => {
-            let material = if (hit_record.point.x * length).floor().abs() as i32 % 2
-                == (hit_record.point.z * length).floor().abs() as i32 % 2
-            {
-                if (hit_record.point.y * length).floor().abs() as i32 % 2 == 0 {
-                    mat1
-                } else {
-                    mat2
-                }
+        Material::Textured(texture) => handle_material(
+            hit_record,
+            &Material::Diffuse(texture.value(hit_record)),
+            hitables,
+            depth,
+            ray,
+            ambient_light,
+        ),
+        Material::Subsurface(subsurface) => {
+            let direction = ray.direction.normalize();
+            let cos_theta = -direction.dot(&hit_record.normal);
+            let eta = if hit_record.front_face { subsurface.ior } else { 1.0 / subsurface.ior };
+            let next = if random_double() < microfacet::fresnel_dielectric(cos_theta, eta) {
+                Some((
+                    ray::Ray::new(hit_record.point, direction.reflect(&hit_record.normal)),
+                    Vec3::new(1.0, 1.0, 1.0),
+                ))
+            } else if hit_record.front_face {
+                direction
+                    .refract(&hit_record.normal, 1.0 / eta)
+                    .and_then(|inward| subsurface.walk(hitables, &hit_record.point, &inward))
             } else {
-                if (hit_record.point.y * length).floor().abs() as i32 % 2 == 0 {
-                    mat2
-                } else {
-                    mat1
-                }
+                // rays that start inside just leave
+                direction
+                    .refract(&hit_record.normal, 1.0 / eta)
+                    .map(|out| (ray::Ray::new(hit_record.point, out), Vec3::new(1.0, 1.0, 1.0)))
             };
-            handle_material(hit_record, &material, hitables, depth, ray, ambient_light)
+            match next {
+                Some((next_ray, weight)) => ray_color(&next_ray, hitables, depth + 1, ambient_light).multiply_vec(&weight),
+                None => Vec3::new(0.0, 0.0, 0.0),
+            }
+        }
+        Material::Hair(hair) => {
+            let tangent = hit_record
+                .tangent
+                .unwrap_or_else(|| hit_record.normal.orthonormal_basis().0);
+            let (direction, weight) = hair.sample(
+                &ray.direction.normalize().multiply(-1.0),
+                &tangent,
+                hit_record.v * 2.0 - 1.0,
+            );
+            // start off the fiber, on the side the ray leaves through, so it
+            // doesn't hit the same fiber again right away
+            let side = if direction.dot(&hit_record.normal) > 0.0 { 1.0 } else { -1.0 };
+            let origin = hit_record.point.add(&hit_record.normal.multiply(side * 1e-4));
+            let incoming = ray_color(
+                &ray::Ray::new(origin, direction),
+                hitables,
+                depth + 1,
+                ambient_light,
+            );
+            incoming.multiply_vec(&weight)
+        }
+        Material::NormalMapped { material, map, strength } => {
+            let record = normal_mapped(hit_record, map, *strength);
+            handle_material(&record, material, hitables, depth, ray, ambient_light)
+        }
+        Material::BumpMapped { material, bump, scale } => {
+            let record = bump_mapped(hit_record, bump, *scale);
+            handle_material(&record, material, hitables, depth, ray, ambient_light)
+        }
+        Material::CheckerBoard(mat1, mat2, length) => {
+            let material = checker(hit_record, mat1, mat2, *length);
+            handle_material(hit_record, material, hitables, depth, ray, ambient_light)
         },
         Material::Crazy() => {
             let noise = Perlin::new(4);
@@ -339,24 +644,233 @@ fn handle_material(
     }
 }
 
-fn hsv_to_rgb(h: f32, s: f32, v: f32) -> Vec3 {
-    let c = v * s;
-    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
-    let m = v - c;
-
-    let (r, g, b) = if h < 60.0 {
-        (c, x, 0.0)
-    } else if h < 120.0 {
-        (x, c, 0.0)
-    } else if h < 240.0 {
-        (0.0, c, x)
-    } else if h < 300.0 {
-        (x, 0.0, c)
+/// `ray_color` for the light at `wavelengths` only.
+fn spectral_color(
+    ray: &ray::Ray,
+    hitables: &HitableList,
+    depth: u32,
+    ambient_light: Vec3,
+    wavelengths: &Wavelengths,
+) -> SampledSpectrum {
+    if depth >= MAX_DEPTH {
+        return SampledSpectrum::zero();
+    }
+
+    if let Some(hit_record) = hitables.hit(ray, 0.001, f32::INFINITY) {
+        let material = hit_record.material.clone();
+        return handle_material_spectral(&hit_record, &material, hitables, depth, ray, ambient_light, wavelengths);
+    }
+
+    SampledSpectrum::from_rgb(&ambient_light, wavelengths)
+}
+
+/// `handle_material` for the light at `wavelengths`. Materials sampled in
+/// the shading frame without a spectral model of their own get their RGB
+/// weight turned into a spectrum; the rest finish the path in RGB.
+fn handle_material_spectral(
+    hit_record: &hitable::HitRecord,
+    material: &Material,
+    hitables: &hitable_list::HitableList,
+    depth: u32,
+    ray: &ray::Ray,
+    ambient_light: Vec3,
+    wavelengths: &Wavelengths,
+) -> SampledSpectrum {
+    match material {
+        Material::Light(color) => SampledSpectrum::from_rgb(color, wavelengths),
+        Material::Blackbody { temperature, strength } => {
+            SampledSpectrum::blackbody(*temperature, wavelengths).multiply(*strength)
+        }
+        Material::Dielectric(dielectric) => {
+            // the other wavelengths would have been bent elsewhere, so the
+            // hero carries on for all of them
+            let split = dielectric.abbe_number.is_some() && !wavelengths.hero_only;
+            let wavelengths = if split { wavelengths.drop_secondary() } else { *wavelengths };
+            let incoming = sample_local_spectral(hit_record, hitables, depth, ray, ambient_light, &wavelengths, |wo| {
+                dielectric.sample_spectral(wo, hit_record, &wavelengths)
+            });
+            if split { incoming.hero_only() } else { incoming }
+        }
+        Material::Metallic(metal) => {
+            handle_material_spectral(hit_record, &metal.to_material(), hitables, depth, ray, ambient_light, wavelengths)
+        }
+        Material::Conductor(conductor) => {
+            sample_local_spectral(hit_record, hitables, depth, ray, ambient_light, wavelengths, |wo| {
+                conductor.sample_spectral(wo, hit_record, wavelengths)
+            })
+        }
+        Material::Diffuse(_)
+        | Material::Textured(_)
+        | Material::Principled(_)
+        | Material::Layered(_) => {
+            sample_local_spectral(hit_record, hitables, depth, ray, ambient_light, wavelengths, |wo| {
+                let (wi, weight) = material.sample_local(wo, hit_record)?;
+                Some((wi, SampledSpectrum::from_rgb(&weight, wavelengths)))
+            })
+        }
+        Material::Subsurface(subsurface) => {
+            let direction = ray.direction.normalize();
+            let cos_theta = -direction.dot(&hit_record.normal);
+            let eta = if hit_record.front_face { subsurface.ior } else { 1.0 / subsurface.ior };
+            let next = if random_double() < microfacet::fresnel_dielectric(cos_theta, eta) {
+                Some((ray::Ray::new(hit_record.point, direction.reflect(&hit_record.normal)), None))
+            } else if hit_record.front_face {
+                direction
+                    .refract(&hit_record.normal, 1.0 / eta)
+                    .and_then(|inward| subsurface.walk_spectral(hitables, &hit_record.point, &inward, wavelengths))
+                    .map(|(next_ray, weight)| (next_ray, Some(weight)))
+            } else {
+                // rays that start inside just leave
+                direction
+                    .refract(&hit_record.normal, 1.0 / eta)
+                    .map(|out| (ray::Ray::new(hit_record.point, out), None))
+            };
+            match next {
+                Some((next_ray, weight)) => {
+                    let incoming = spectral_color(&next_ray, hitables, depth + 1, ambient_light, wavelengths);
+                    weight.map_or(incoming, |weight| incoming.multiply_spectrum(&weight))
+                }
+                None => SampledSpectrum::zero(),
+            }
+        }
+        Material::Mix { first, second, weight } => {
+            let cos_theta = -ray.direction.normalize().dot(&hit_record.normal);
+            let material = if random_double() < weight.value(hit_record, cos_theta) {
+                second
+            } else {
+                first
+            };
+            handle_material_spectral(hit_record, material, hitables, depth, ray, ambient_light, wavelengths)
+        }
+        Material::NormalMapped { material, map, strength } => {
+            let record = normal_mapped(hit_record, map, *strength);
+            handle_material_spectral(&record, material, hitables, depth, ray, ambient_light, wavelengths)
+        }
+        Material::BumpMapped { material, bump, scale } => {
+            let record = bump_mapped(hit_record, bump, *scale);
+            handle_material_spectral(&record, material, hitables, depth, ray, ambient_light, wavelengths)
+        }
+        Material::CheckerBoard(mat1, mat2, length) => {
+            let material = checker(hit_record, mat1, mat2, *length);
+            handle_material_spectral(hit_record, material, hitables, depth, ray, ambient_light, wavelengths)
+        }
+        _ => SampledSpectrum::from_rgb(
+            &handle_material(hit_record, material, hitables, depth, ray, ambient_light),
+            wavelengths,
+        ),
+    }
+}
+
+/// `sample_local` for the light at `wavelengths`.
+fn sample_local_spectral(
+    hit_record: &hitable::HitRecord,
+    hitables: &hitable_list::HitableList,
+    depth: u32,
+    ray: &ray::Ray,
+    ambient_light: Vec3,
+    wavelengths: &Wavelengths,
+    sample: impl FnOnce(&Vec3) -> Option<(Vec3, SampledSpectrum)>,
+) -> SampledSpectrum {
+    let (tangent, bitangent) = tangent_frame(hit_record);
+    let frame = Frame::new(hit_record.point, tangent, bitangent);
+    let Some((direction, weight)) = sample(&frame.to_local(&ray.direction.normalize().multiply(-1.0))) else {
+        return SampledSpectrum::zero();
+    };
+    let incoming = spectral_color(
+        &ray::Ray::new(hit_record.point, frame.to_world(&direction)),
+        hitables,
+        depth + 1,
+        ambient_light,
+        wavelengths,
+    );
+    incoming.multiply_spectrum(&weight)
+}
+
+/// The hit with its shading normal tilted by a tangent-space normal map.
+fn normal_mapped(hit_record: &hitable::HitRecord, map: &NormalMap, strength: f32) -> hitable::HitRecord {
+    let (tangent, bitangent) = tangent_frame(hit_record);
+    let local = map.value(hit_record);
+    let mut record = hit_record.clone();
+    record.normal = tangent
+        .multiply(local.x * strength)
+        .add(&bitangent.multiply(local.y * strength))
+        .add(&hit_record.normal.multiply(local.z))
+        .normalize();
+    record
+}
+
+/// The hit with its shading normal tilted down the slopes of a bump map.
+fn bump_mapped(hit_record: &hitable::HitRecord, bump: &ScalarTexture, scale: f32) -> hitable::HitRecord {
+    let (tangent, bitangent) = tangent_frame(hit_record);
+    let (slope_u, slope_v) = bump.gradient(
+        &hit_record.point,
+        (hit_record.u, hit_record.v),
+        &tangent,
+        &bitangent,
+    );
+    let mut record = hit_record.clone();
+    record.normal = hit_record
+        .normal
+        .sub(&tangent.multiply(slope_u * scale))
+        .sub(&bitangent.multiply(slope_v * scale))
+        .normalize();
+    record
+}
+
+/// Which of the two materials of a 3D checkerboard with squares `1 / length`
+/// wide the hit lands on.
+fn checker<'a>(hit_record: &hitable::HitRecord, mat1: &'a Material, mat2: &'a Material, length: f32) -> &'a Material {
+    if (hit_record.point.x * length).floor().abs() as i32 % 2
+        == (hit_record.point.z * length).floor().abs() as i32 % 2
+    {
+        if (hit_record.point.y * length).floor().abs() as i32 % 2 == 0 {
+            mat1
+        } else {
+            mat2
+        }
     } else {
-        (c, 0.0, x)
+        if (hit_record.point.y * length).floor().abs() as i32 % 2 == 0 {
+            mat2
+        } else {
+            mat1
+        }
+    }
+}
+
+/// Light arriving along a direction picked by `sample`, which works in the
+/// hit's shading frame and returns the local direction and its weight.
+fn sample_local(
+    hit_record: &hitable::HitRecord,
+    hitables: &hitable_list::HitableList,
+    depth: u32,
+    ray: &ray::Ray,
+    ambient_light: Vec3,
+    sample: impl FnOnce(&Vec3) -> Option<(Vec3, Vec3)>,
+) -> Vec3 {
+    let (tangent, bitangent) = tangent_frame(hit_record);
+    let frame = Frame::new(hit_record.point, tangent, bitangent);
+    let Some((direction, weight)) = sample(&frame.to_local(&ray.direction.normalize().multiply(-1.0))) else {
+        return Vec3::new(0.0, 0.0, 0.0);
     };
+    let incoming = ray_color(
+        &ray::Ray::new(hit_record.point, frame.to_world(&direction)),
+        hitables,
+        depth + 1,
+        ambient_light,
+    );
+    incoming.multiply_vec(&weight)
+}
 
-    Vec3::new(r + m, g + m, b + m)
+/// Tangent and bitangent around the hit's normal, from the surface tangent
+/// where there is one.
+fn tangent_frame(hit_record: &hitable::HitRecord) -> (Vec3, Vec3) {
+    let normal = hit_record.normal;
+    let tangent = hit_record
+        .tangent
+        .map(|tangent| tangent.sub(&normal.multiply(normal.dot(&tangent))))
+        .filter(|tangent| tangent.length_squared() > 1e-12)
+        .map_or_else(|| normal.orthonormal_basis().0, |tangent| tangent.normalize());
+    (tangent, normal.cross(&tangent))
 }
 
 fn lava_gradient(x: f32) -> Vec3 {
@@ -0,0 +1,318 @@
+use crate::{
+    hitable::HitRecord,
+    microfacet::{self, TrowbridgeReitz},
+    spectrum::{SampledSpectrum, Wavelengths},
+    thin_film::ThinFilm,
+    util::random_double,
+    vec3::Vec3,
+};
+
+/// Wavelengths in nm that stand in for the red, green and blue channels
+/// when measured spectral data is reduced to RGB.
+pub const RGB_WAVELENGTHS: [f32; 3] = [630.0, 532.0, 465.0];
+
+/// Measured complex index of refraction as (wavelength in nm, n, k), from
+/// Johnson and Christy 1972.
+pub const GOLD: &[(f32, f32, f32)] = &[
+    (400.0, 1.658, 1.956),
+    (450.0, 1.380, 1.916),
+    (500.0, 0.970, 1.870),
+    (550.0, 0.430, 2.455),
+    (600.0, 0.250, 2.980),
+    (650.0, 0.170, 3.600),
+    (700.0, 0.160, 4.000),
+];
+
+/// Measured complex index of refraction, from Johnson and Christy 1972.
+pub const COPPER: &[(f32, f32, f32)] = &[
+    (400.0, 1.180, 2.210),
+    (450.0, 1.240, 2.400),
+    (500.0, 1.130, 2.560),
+    (550.0, 1.020, 2.580),
+    (600.0, 0.300, 3.000),
+    (650.0, 0.210, 3.670),
+    (700.0, 0.210, 4.200),
+];
+
+/// Measured complex index of refraction, from Johnson and Christy 1972.
+pub const SILVER: &[(f32, f32, f32)] = &[
+    (400.0, 0.050, 2.100),
+    (450.0, 0.040, 2.660),
+    (500.0, 0.050, 3.090),
+    (550.0, 0.060, 3.590),
+    (600.0, 0.060, 4.000),
+    (650.0, 0.050, 4.480),
+    (700.0, 0.040, 4.800),
+];
+
+/// Measured complex index of refraction, from Rakić 1995.
+pub const ALUMINIUM: &[(f32, f32, f32)] = &[
+    (400.0, 0.490, 4.860),
+    (450.0, 0.620, 5.470),
+    (500.0, 0.770, 6.080),
+    (550.0, 0.960, 6.690),
+    (600.0, 1.200, 7.260),
+    (650.0, 1.470, 7.790),
+    (700.0, 1.830, 8.310),
+];
+
+/// Rough metal: GGX microfacets reflecting with the Fresnel equations of a
+/// complex index of refraction `eta` + i`k` per channel. Light the single
+/// scattering model loses between microfacets is added back after Turquin
+/// 2019, so rough metals don't darken.
+#[derive(Debug, Clone)]
+pub struct Conductor {
+    pub eta: Vec3,
+    pub k: Vec3,
+    /// Full measured spectrum for metals made with `measured`, used instead
+    /// of `eta` and `k` wherever single wavelengths are needed.
+    pub spectrum: Option<&'static [(f32, f32, f32)]>,
+    pub distribution: TrowbridgeReitz,
+    /// Coating on top, like the oxide of anodised metal.
+    pub film: Option<ThinFilm>,
+}
+
+impl Conductor {
+    pub fn new(eta: Vec3, k: Vec3, roughness: f32, anisotropy: f32) -> Self {
+        Conductor {
+            eta,
+            k,
+            spectrum: None,
+            distribution: TrowbridgeReitz::from_roughness(roughness, anisotropy),
+            film: None,
+        }
+    }
+
+    /// Metal from one of the measured tables like `GOLD`, looked up at
+    /// `RGB_WAVELENGTHS`.
+    pub fn measured(ior: &'static [(f32, f32, f32)], roughness: f32, anisotropy: f32) -> Self {
+        let [red, green, blue] = RGB_WAVELENGTHS.map(|wavelength| index_at(ior, wavelength));
+        Conductor {
+            spectrum: Some(ior),
+            ..Conductor::new(
+                Vec3::new(red.0, green.0, blue.0),
+                Vec3::new(red.1, green.1, blue.1),
+                roughness,
+                anisotropy,
+            )
+        }
+    }
+
+    pub fn with_film(mut self, film: ThinFilm) -> Self {
+        self.film = Some(film);
+        self
+    }
+
+    /// Metal that reflects `color` head on, for when there is no measured
+    /// data. The index is real, which gets the colour right but not quite
+    /// how it shifts toward grazing angles.
+    pub fn from_reflectance(color: Vec3, roughness: f32, anisotropy: f32) -> Self {
+        let eta = |r: f32| {
+            let r = r.clamp(0.0, 0.999).sqrt();
+            (1.0 + r) / (1.0 - r)
+        };
+        Conductor::new(
+            Vec3::new(eta(color.x), eta(color.y), eta(color.z)),
+            Vec3::new(0.0, 0.0, 0.0),
+            roughness,
+            anisotropy,
+        )
+    }
+
+    /// n and k at `wavelength` in nm, from the measured spectrum where there
+    /// is one and otherwise interpolated between the channels.
+    pub fn index_at(&self, wavelength: f32) -> (f32, f32) {
+        match self.spectrum {
+            Some(spectrum) => index_at(spectrum, wavelength),
+            None => {
+                let [red, green, blue] = RGB_WAVELENGTHS;
+                let channels = [
+                    (blue, self.eta.z, self.k.z),
+                    (green, self.eta.y, self.k.y),
+                    (red, self.eta.x, self.k.x),
+                ];
+                index_at(&channels, wavelength)
+            }
+        }
+    }
+
+    /// Fresnel reflectance of each channel at `cos_theta` to the normal.
+    pub fn fresnel(&self, cos_theta: f32) -> Vec3 {
+        Vec3::new(
+            fresnel_conductor(cos_theta, self.eta.x, self.k.x),
+            fresnel_conductor(cos_theta, self.eta.y, self.k.y),
+            fresnel_conductor(cos_theta, self.eta.z, self.k.z),
+        )
+    }
+
+    /// Picks an incoming direction for light leaving toward `wo`, in the
+    /// shading frame of `hit_record` with the normal along z and the tangent
+    /// along x. Returns the direction and the weight to multiply the light
+    /// arriving from it by, or `None` when the sample leaves below the
+    /// surface.
+    pub fn sample(&self, wo: &Vec3, hit_record: &HitRecord) -> Option<(Vec3, Vec3)> {
+        let film = self.film.as_ref().map(|film| (film, film.thickness_at(hit_record)));
+        let (wi, [red, green, blue]) = self.sample_channels(wo, |cos_theta| {
+            let rgb = match film {
+                Some((film, thickness)) => {
+                    film.reflectance(cos_theta, 1.0, thickness, |wavelength| self.index_at(wavelength))
+                }
+                None => self.fresnel(cos_theta),
+            };
+            [rgb.x, rgb.y, rgb.z]
+        })?;
+        Some((wi, Vec3::new(red, green, blue)))
+    }
+
+    /// `sample` for light at `wavelengths`, with the reflectance of the
+    /// measured spectrum and of the film at each of them.
+    pub fn sample_spectral(
+        &self,
+        wo: &Vec3,
+        hit_record: &HitRecord,
+        wavelengths: &Wavelengths,
+    ) -> Option<(Vec3, SampledSpectrum)> {
+        let thickness = self.film.as_ref().map(|film| film.thickness_at(hit_record));
+        let indices = wavelengths.values.map(|wavelength| self.index_at(wavelength));
+        let (wi, values) = self.sample_channels(wo, |cos_theta| {
+            std::array::from_fn(|i| {
+                let (n, k) = indices[i];
+                match (&self.film, thickness) {
+                    (Some(film), Some(thickness)) => {
+                        film.reflectance_at(wavelengths.values[i], cos_theta, 1.0, thickness, (n, k))
+                    }
+                    _ => fresnel_conductor(cos_theta, n, k),
+                }
+            })
+        })?;
+        Some((wi, SampledSpectrum::new(values)))
+    }
+
+    /// Reflects off a sampled microfacet and weighs each channel by
+    /// `reflectance` at a cosine to the normal.
+    fn sample_channels<const N: usize>(
+        &self,
+        wo: &Vec3,
+        reflectance: impl Fn(f32) -> [f32; N],
+    ) -> Option<(Vec3, [f32; N])> {
+        if wo.z <= 0.0 {
+            return None;
+        }
+        let m = self
+            .distribution
+            .sample_visible_normal(wo, random_double(), random_double());
+        let wi = microfacet::reflect(wo, &m);
+        if wi.z <= 0.0 {
+            return None;
+        }
+        let shadowing = self.distribution.g(wo, &wi) / self.distribution.g1(wo);
+        // scale up by what the missing bounces would add, assuming they keep
+        // getting the head-on reflectance
+        let albedo = self.distribution.albedo(wo.z).max(1e-3);
+        let head_on = reflectance(1.0);
+        let single = reflectance(wo.dot(&m));
+        Some((
+            wi,
+            std::array::from_fn(|i| single[i] * shadowing * (1.0 + head_on[i] * (1.0 - albedo) / albedo)),
+        ))
+    }
+}
+
+/// n and k of a measured table at `wavelength` in nm, linearly interpolated
+/// and held constant past either end.
+pub fn index_at(ior: &[(f32, f32, f32)], wavelength: f32) -> (f32, f32) {
+    let after = ior.partition_point(|&(sample, _, _)| sample < wavelength);
+    if after == 0 {
+        return (ior[0].1, ior[0].2);
+    }
+    if after == ior.len() {
+        let last = ior[ior.len() - 1];
+        return (last.1, last.2);
+    }
+    let (w0, n0, k0) = ior[after - 1];
+    let (w1, n1, k1) = ior[after];
+    let t = (wavelength - w0) / (w1 - w0);
+    (n0 + (n1 - n0) * t, k0 + (k1 - k0) * t)
+}
+
+/// Unpolarized Fresnel reflectance of a conductor with complex index
+/// `eta` + i`k`, in closed form.
+fn fresnel_conductor(cos_theta: f32, eta: f32, k: f32) -> f32 {
+    let cos2 = cos_theta.clamp(0.0, 1.0).powi(2);
+    let sin2 = 1.0 - cos2;
+    let (eta2, k2) = (eta * eta, k * k);
+    let t0 = eta2 - k2 - sin2;
+    let a2_plus_b2 = (t0 * t0 + 4.0 * eta2 * k2).sqrt();
+    let t1 = a2_plus_b2 + cos2;
+    let a = ((a2_plus_b2 + t0) / 2.0).max(0.0).sqrt();
+    let t2 = 2.0 * cos_theta * a;
+    let perpendicular = (t1 - t2) / (t1 + t2);
+    let t3 = cos2 * a2_plus_b2 + sin2 * sin2;
+    let t4 = t2 * sin2;
+    let parallel = perpendicular * (t3 - t4) / (t3 + t4);
+    (parallel + perpendicular) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{material::Material, ray::Ray};
+
+    fn hit_record() -> HitRecord {
+        let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+        HitRecord::new(&ray, 1.0, Vec3::new(0.0, 0.0, 1.0), Material::Diffuse(Vec3::new(0.5, 0.5, 0.5)), 0.0, 0.0)
+    }
+
+    #[test]
+    fn reflectance_head_on_is_the_requested_colour() {
+        let color = Vec3::new(0.9, 0.6, 0.2);
+        let fresnel = Conductor::from_reflectance(color, 0.3, 0.0).fresnel(1.0);
+        for (channel, expected) in [(fresnel.x, color.x), (fresnel.y, color.y), (fresnel.z, color.z)] {
+            assert!((channel - expected).abs() < 1e-4, "{:?}", fresnel);
+        }
+    }
+
+    #[test]
+    fn measured_index_matches_the_table() {
+        assert_eq!(index_at(GOLD, 550.0), (0.430, 2.455));
+        let (n, k) = index_at(GOLD, 575.0);
+        assert!((n - 0.34).abs() < 1e-4 && (k - 2.7175).abs() < 1e-4);
+        // held at the ends of the table
+        assert_eq!(index_at(GOLD, 380.0), (1.658, 1.956));
+        assert_eq!(index_at(GOLD, 780.0), (0.160, 4.000));
+    }
+
+    #[test]
+    fn rough_white_metal_loses_no_energy() {
+        let record = hit_record();
+        let wo = Vec3::new(0.6, 0.0, 0.8);
+        for roughness in [0.2, 0.6, 1.0] {
+            let conductor = Conductor::from_reflectance(Vec3::new(1.0, 1.0, 1.0), roughness, 0.0);
+            let samples = 20000;
+            let mut total = 0.0;
+            for _ in 0..samples {
+                if let Some((_, weight)) = conductor.sample(&wo, &record) {
+                    total += weight.y;
+                }
+            }
+            let albedo = total / samples as f32;
+            assert!((albedo - 1.0).abs() < 0.05, "{} at roughness {}", albedo, roughness);
+        }
+    }
+
+    #[test]
+    fn spectral_sample_matches_rgb_at_the_channel_wavelengths() {
+        let gold = Conductor::measured(GOLD, 0.0, 0.0);
+        let record = hit_record();
+        let wo = Vec3::new(0.0, 0.0, 1.0);
+        let wavelengths = Wavelengths {
+            values: [RGB_WAVELENGTHS[0], RGB_WAVELENGTHS[1], RGB_WAVELENGTHS[2], RGB_WAVELENGTHS[1]],
+            hero_only: false,
+        };
+        let (_, rgb) = gold.sample(&wo, &record).unwrap();
+        let (_, spectrum) = gold.sample_spectral(&wo, &record, &wavelengths).unwrap();
+        for (channel, value) in [rgb.x, rgb.y, rgb.z].into_iter().zip(spectrum.values) {
+            assert!((channel - value).abs() < 1e-4, "{:?} {:?}", rgb, spectrum);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::{
+    aabb::Aabb,
+    hitable::{HitRecord, Hitable, Span},
+    interval::Interval,
+    ray::Ray,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsgOperation {
+    Union,
+    Intersection,
+    /// The right object carved out of the left one.
+    Difference,
+}
+
+impl CsgOperation {
+    fn inside(&self, left: bool, right: bool) -> bool {
+        match self {
+            CsgOperation::Union => left || right,
+            CsgOperation::Intersection => left && right,
+            CsgOperation::Difference => left && !right,
+        }
+    }
+}
+
+/// Boolean combination of two closed objects, built from the spans the ray
+/// spends inside each of them. Csg nodes can be nested, and surfaces keep the
+/// material of the object they came from.
+pub struct Csg {
+    operation: CsgOperation,
+    left: Box<dyn Hitable>,
+    right: Box<dyn Hitable>,
+}
+
+impl Clone for Csg {
+    fn clone(&self) -> Self {
+        Csg {
+            operation: self.operation,
+            left: dyn_clone::clone_box(self.left.as_ref()),
+            right: dyn_clone::clone_box(self.right.as_ref()),
+        }
+    }
+}
+
+impl Csg {
+    pub fn new(operation: CsgOperation, left: Box<dyn Hitable>, right: Box<dyn Hitable>) -> Self {
+        Csg { operation, left, right }
+    }
+
+    pub fn union(left: Box<dyn Hitable>, right: Box<dyn Hitable>) -> Self {
+        Csg::new(CsgOperation::Union, left, right)
+    }
+
+    pub fn intersection(left: Box<dyn Hitable>, right: Box<dyn Hitable>) -> Self {
+        Csg::new(CsgOperation::Intersection, left, right)
+    }
+
+    pub fn difference(left: Box<dyn Hitable>, right: Box<dyn Hitable>) -> Self {
+        Csg::new(CsgOperation::Difference, left, right)
+    }
+}
+
+impl Hitable for Csg {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        // a span without an entry started behind t_min, so its exit is the
+        // first surface the ray meets
+        let span = self.hit_intervals(ray, t_min, t_max).into_iter().next()?;
+        span.entry.or(span.exit)
+    }
+
+    fn hit_intervals(&self, ray: &Ray, t_min: f32, t_max: f32) -> Vec<Span> {
+        let left = self.left.hit_intervals(ray, t_min, t_max);
+        let right = self.right.hit_intervals(ray, t_min, t_max);
+
+        // boundaries as (t, is left, entering, record)
+        let mut inside = [false, false];
+        let mut events = vec![];
+        for (side, spans) in [left, right].into_iter().enumerate() {
+            for span in spans {
+                match span.entry {
+                    Some(entry) => events.push((span.interval.min, side, true, entry)),
+                    None => inside[side] = true,
+                }
+                if let Some(exit) = span.exit {
+                    events.push((span.interval.max, side, false, exit));
+                }
+            }
+        }
+        events.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut spans = vec![];
+        let mut was_inside = self.operation.inside(inside[0], inside[1]);
+        let mut entry: Option<HitRecord> = None;
+        let mut start = t_min;
+        for (t, side, entering, mut record) in events {
+            inside[side] = entering;
+            let now_inside = self.operation.inside(inside[0], inside[1]);
+            if now_inside == was_inside {
+                continue;
+            }
+            // leaving the carved out object enters the result and vice versa
+            record.front_face = now_inside;
+            if now_inside {
+                start = t;
+                entry = Some(record);
+            } else {
+                spans.push(Span {
+                    interval: Interval::new(start, t),
+                    entry: entry.take(),
+                    exit: Some(record),
+                });
+            }
+            was_inside = now_inside;
+        }
+        if was_inside {
+            spans.push(Span {
+                interval: Interval::new(start, t_max),
+                entry,
+                exit: None,
+            });
+        }
+        spans
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        match self.operation {
+            CsgOperation::Union => Some(self.left.bounding_box()?.union(&self.right.bounding_box()?)),
+            CsgOperation::Intersection => self.left.bounding_box().or(self.right.bounding_box()),
+            CsgOperation::Difference => self.left.bounding_box(),
+        }
+    }
+}
@@ -0,0 +1,307 @@
+use std::sync::Arc;
+
+use crate::{
+    aabb::Aabb,
+    bvh::Bvh,
+    hitable::{HitRecord, Hitable},
+    material::Material,
+    ray::Ray,
+    vec3::Vec3,
+};
+
+/// Parameter ranges each segment is cut into for the BVH, so long or bent
+/// segments don't sit in one loose box.
+const PIECES_PER_SEGMENT: usize = 4;
+
+/// Deepest the intersection test splits a curve before treating it as a line.
+const MAX_SPLIT_DEPTH: i32 = 10;
+
+/// How the control points of a strand are joined up.
+#[derive(Debug, Clone, Copy)]
+pub enum CurveBasis {
+    /// Piecewise cubic Bezier, neighbouring segments sharing an end point
+    /// (3n + 1 points for n segments).
+    Bezier,
+    /// Uniform cubic B-spline, one segment for every four consecutive points.
+    BSpline,
+}
+
+/// How a curve looks across its width. Both are intersected as a flat
+/// ribbon facing the ray.
+#[derive(Debug, Clone, Copy)]
+pub enum CurveShape {
+    /// Flat shading, as for grass blades.
+    Ribbon,
+    /// Normals of a round tube, as for hair.
+    Cylinder,
+}
+
+/// Cubic Bezier segment whose width changes linearly from one end to the other.
+#[derive(Debug, Clone, Copy)]
+pub struct CurveSegment {
+    pub control: [Vec3; 4],
+    pub widths: [f32; 2],
+}
+
+impl CurveSegment {
+    pub fn new(control: [Vec3; 4], widths: [f32; 2]) -> Self {
+        CurveSegment { control, widths }
+    }
+
+    /// Segments of one strand through `points`, tapering from `root_width`
+    /// to `tip_width`.
+    pub fn strand(points: &[Vec3], basis: CurveBasis, root_width: f32, tip_width: f32) -> Vec<CurveSegment> {
+        let controls: Vec<[Vec3; 4]> = match basis {
+            CurveBasis::Bezier => points
+                .windows(4)
+                .step_by(3)
+                .map(|window| [window[0], window[1], window[2], window[3]])
+                .collect(),
+            CurveBasis::BSpline => points
+                .windows(4)
+                .map(|p| {
+                    [
+                        p[0].add(&p[1].multiply(4.0)).add(&p[2]).divide(6.0),
+                        p[1].multiply(4.0).add(&p[2].multiply(2.0)).divide(6.0),
+                        p[1].multiply(2.0).add(&p[2].multiply(4.0)).divide(6.0),
+                        p[1].add(&p[2].multiply(4.0)).add(&p[3]).divide(6.0),
+                    ]
+                })
+                .collect(),
+        };
+        let count = controls.len() as f32;
+        let width_at = |s: f32| root_width + (tip_width - root_width) * s;
+        controls
+            .into_iter()
+            .enumerate()
+            .map(|(i, control)| CurveSegment::new(control, [width_at(i as f32 / count), width_at((i + 1) as f32 / count)]))
+            .collect()
+    }
+
+    fn width(&self, u: f32) -> f32 {
+        self.widths[0] + (self.widths[1] - self.widths[0]) * u
+    }
+}
+
+/// A set of curve segments sharing a material, e.g. all hairs on a head,
+/// with their own BVH over pieces of the segments.
+#[derive(Debug, Clone)]
+pub struct Curves {
+    segments: Arc<Vec<CurveSegment>>,
+    /// Segment index and parameter range of each BVH item.
+    pieces: Arc<Vec<(usize, f32, f32)>>,
+    bvh: Arc<Bvh>,
+    shape: CurveShape,
+    material: Material,
+}
+
+/// Closest hit found so far while splitting a curve.
+struct CurveHit {
+    /// Distance along the ray direction in ray space.
+    z: f32,
+    u: f32,
+    /// Ray-space offset from the curve to where the ray passes it.
+    offset: (f32, f32),
+    width: f32,
+}
+
+impl Curves {
+    pub fn new(segments: Vec<CurveSegment>, shape: CurveShape, material: Material) -> Self {
+        let mut pieces = vec![];
+        let mut bounds = vec![];
+        for (index, segment) in segments.iter().enumerate() {
+            for piece in 0..PIECES_PER_SEGMENT {
+                let (u0, u1) = (
+                    piece as f32 / PIECES_PER_SEGMENT as f32,
+                    (piece + 1) as f32 / PIECES_PER_SEGMENT as f32,
+                );
+                let radius = segment.width(u0).max(segment.width(u1)) / 2.0;
+                let padding = Vec3::new(radius, radius, radius);
+                let hull = Aabb::from_points(&subcurve(&segment.control, u0, u1));
+                bounds.push(Aabb::new(hull.min.sub(&padding), hull.max.add(&padding)));
+                pieces.push((index, u0, u1));
+            }
+        }
+        Curves {
+            segments: Arc::new(segments),
+            pieces: Arc::new(pieces),
+            bvh: Arc::new(Bvh::new(&bounds)),
+            shape,
+            material,
+        }
+    }
+}
+
+impl Hitable for Curves {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let length = ray.direction.length();
+        let z_axis = ray.direction.divide(length);
+        let (x_axis, y_axis) = z_axis.orthonormal_basis();
+        let to_ray_space = |point: &Vec3| {
+            let relative = point.sub(&ray.origin);
+            Vec3::new(relative.dot(&x_axis), relative.dot(&y_axis), relative.dot(&z_axis))
+        };
+
+        let mut closest: Option<(CurveHit, usize)> = None;
+        self.bvh.traverse(ray, t_min, t_max, |item, closest_t| {
+            let (index, u0, u1) = self.pieces[item];
+            let segment = &self.segments[index];
+            let control = subcurve(&segment.control, u0, u1).map(|point| to_ray_space(&point));
+            let mut hit = None;
+            intersect(
+                segment,
+                &control,
+                (u0, u1),
+                split_depth(&control, segment),
+                (t_min * length, closest_t * length),
+                &mut hit,
+            );
+            let hit = hit?;
+            let t = hit.z / length;
+            closest = Some((hit, index));
+            Some(t)
+        });
+        let (hit, index) = closest?;
+
+        let segment = &self.segments[index];
+        let tangent = derivative(&segment.control, hit.u).normalize();
+        // flat normal of the ribbon, facing back along the ray
+        let facing = z_axis.sub(&tangent.multiply(z_axis.dot(&tangent)));
+        let flat_normal = if facing.length_squared() > 1e-12 {
+            facing.normalize().multiply(-1.0)
+        } else {
+            tangent.orthonormal_basis().0
+        };
+        let side = flat_normal.cross(&tangent);
+        let offset = x_axis.multiply(hit.offset.0).add(&y_axis.multiply(hit.offset.1));
+        // where across the width the ray passed, from -1 to 1
+        let h = (offset.dot(&side) / (hit.width / 2.0)).clamp(-1.0, 1.0);
+        let normal = match self.shape {
+            CurveShape::Ribbon => flat_normal,
+            CurveShape::Cylinder => flat_normal
+                .multiply((1.0 - h * h).sqrt())
+                .add(&side.multiply(h))
+                .normalize(),
+        };
+        let mut record = HitRecord::new(ray, hit.z / length, normal, self.material.clone(), hit.u, (h + 1.0) / 2.0);
+        record.tangent = Some(tangent);
+        Some(record)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.bvh.bounds()
+    }
+}
+
+/// Splits a ray-space curve until it is close enough to a line, keeping
+/// the closest crossing with z in `z_min..z_max` (after Nakamaru and
+/// Ohno, as in pbrt).
+fn intersect(
+    segment: &CurveSegment,
+    control: &[Vec3; 4],
+    (u0, u1): (f32, f32),
+    depth: i32,
+    (z_min, z_max): (f32, f32),
+    closest: &mut Option<CurveHit>,
+) {
+    let z_max = closest.as_ref().map_or(z_max, |hit| hit.z);
+    let radius = segment.width(u0).max(segment.width(u1)) / 2.0;
+    let bounds = Aabb::from_points(control);
+    if bounds.min.x - radius > 0.0
+        || bounds.max.x + radius < 0.0
+        || bounds.min.y - radius > 0.0
+        || bounds.max.y + radius < 0.0
+        || bounds.min.z - radius > z_max
+        || bounds.max.z + radius < z_min
+    {
+        return;
+    }
+
+    if depth > 0 {
+        let (left, right) = split_at(control, 0.5);
+        let middle = (u0 + u1) / 2.0;
+        intersect(segment, &left, (u0, middle), depth - 1, (z_min, z_max), closest);
+        intersect(segment, &right, (middle, u1), depth - 1, (z_min, z_max), closest);
+        return;
+    }
+
+    // the ray has to pass between the lines through each end
+    // perpendicular to the curve
+    let [p0, p1, p2, p3] = *control;
+    if (p1.y - p0.y) * -p0.y + p0.x * (p0.x - p1.x) < 0.0 || (p2.y - p3.y) * -p3.y + p3.x * (p3.x - p2.x) < 0.0 {
+        return;
+    }
+    let (dx, dy) = (p3.x - p0.x, p3.y - p0.y);
+    let length_squared = dx * dx + dy * dy;
+    if length_squared == 0.0 {
+        return;
+    }
+    let w = ((-p0.x * dx - p0.y * dy) / length_squared).clamp(0.0, 1.0);
+    let u = u0 + (u1 - u0) * w;
+    let width = segment.width(u);
+    let point = evaluate(control, w);
+    if point.x * point.x + point.y * point.y > width * width / 4.0 {
+        return;
+    }
+    if point.z <= z_min || point.z >= z_max {
+        return;
+    }
+    *closest = Some(CurveHit {
+        z: point.z,
+        u,
+        offset: (-point.x, -point.y),
+        width,
+    });
+}
+
+/// Splits until the curve deviates from a straight line by less than a
+/// twentieth of its width.
+fn split_depth(control: &[Vec3; 4], segment: &CurveSegment) -> i32 {
+    let mut bend: f32 = 0.0;
+    for i in 0..2 {
+        let second_difference = control[i].sub(&control[i + 1].multiply(2.0)).add(&control[i + 2]);
+        bend = bend
+            .max(second_difference.x.abs())
+            .max(second_difference.y.abs())
+            .max(second_difference.z.abs());
+    }
+    let tolerance = segment.widths[0].max(segment.widths[1]) * 0.05;
+    ((std::f32::consts::SQRT_2 * 6.0 * bend / (8.0 * tolerance)).log2() / 2.0).clamp(0.0, MAX_SPLIT_DEPTH as f32) as i32
+}
+
+fn evaluate(control: &[Vec3; 4], u: f32) -> Vec3 {
+    let s = 1.0 - u;
+    control[0]
+        .multiply(s * s * s)
+        .add(&control[1].multiply(3.0 * s * s * u))
+        .add(&control[2].multiply(3.0 * s * u * u))
+        .add(&control[3].multiply(u * u * u))
+}
+
+fn derivative(control: &[Vec3; 4], u: f32) -> Vec3 {
+    let s = 1.0 - u;
+    control[1]
+        .sub(&control[0])
+        .multiply(3.0 * s * s)
+        .add(&control[2].sub(&control[1]).multiply(6.0 * s * u))
+        .add(&control[3].sub(&control[2]).multiply(3.0 * u * u))
+}
+
+/// De Casteljau split at `u`.
+fn split_at(control: &[Vec3; 4], u: f32) -> ([Vec3; 4], [Vec3; 4]) {
+    let lerp = |a: &Vec3, b: &Vec3| a.add(&b.sub(a).multiply(u));
+    let [p0, p1, p2, p3] = control;
+    let (a, b, c) = (lerp(p0, p1), lerp(p1, p2), lerp(p2, p3));
+    let (d, e) = (lerp(&a, &b), lerp(&b, &c));
+    let f = lerp(&d, &e);
+    ([*p0, a, d, f], [f, e, c, *p3])
+}
+
+/// Control points of the part of a curve between `u0` and `u1`.
+fn subcurve(control: &[Vec3; 4], u0: f32, u1: f32) -> [Vec3; 4] {
+    let (head, _) = split_at(control, u1);
+    if u0 <= 0.0 {
+        return head;
+    }
+    split_at(&head, u0 / u1).1
+}
@@ -0,0 +1,150 @@
+use crate::{
+    hitable::HitRecord,
+    microfacet::{self, TrowbridgeReitz},
+    spectrum::{SampledSpectrum, Wavelengths},
+    thin_film::ThinFilm,
+    util::random_double,
+    vec3::Vec3,
+};
+
+/// Glass, water or any other clear material, smooth or frosted by GGX
+/// microfacets. An index of 1 with a film makes a soap bubble, and an Abbe
+/// number makes it split white light into colours in spectral mode.
+#[derive(Debug, Clone)]
+pub struct Dielectric {
+    pub ior: f32,
+    /// How little the index varies with wavelength, 60 for crown glass down
+    /// to about 30 for dense flint; `None` for none at all.
+    pub abbe_number: Option<f32>,
+    pub distribution: TrowbridgeReitz,
+    /// Coating on the outside, like a soap film or oil on water.
+    pub film: Option<ThinFilm>,
+}
+
+impl Dielectric {
+    pub fn new(ior: f32, roughness: f32) -> Self {
+        Dielectric {
+            ior,
+            abbe_number: None,
+            distribution: TrowbridgeReitz::from_roughness(roughness, 0.0),
+            film: None,
+        }
+    }
+
+    pub fn with_film(mut self, film: ThinFilm) -> Self {
+        self.film = Some(film);
+        self
+    }
+
+    pub fn with_dispersion(mut self, abbe_number: f32) -> Self {
+        self.abbe_number = Some(abbe_number);
+        self
+    }
+
+    /// Index at `wavelength` in nm: `ior` at the sodium d line, 587.6 nm,
+    /// and spread by the Abbe number along Cauchy's equation where the
+    /// glass disperses.
+    pub fn ior_at(&self, wavelength: f32) -> f32 {
+        let Some(abbe_number) = self.abbe_number else {
+            return self.ior;
+        };
+        // Fraunhofer d, F and C lines
+        let (d, f, c): (f32, f32, f32) = (587.6, 486.1, 656.3);
+        let b = (self.ior - 1.0) / (abbe_number * (1.0 / (f * f) - 1.0 / (c * c)));
+        self.ior + b * (1.0 / (wavelength * wavelength) - 1.0 / (d * d))
+    }
+
+    /// Picks an incoming direction for light leaving toward `wo`, in the
+    /// shading frame of `hit_record` with the normal along z. Light is
+    /// reflected or refracted in proportion to the Fresnel reflectance, so
+    /// the direction may be below the surface. Returns the direction and the
+    /// weight to multiply the light arriving from it by.
+    pub fn sample(&self, wo: &Vec3, hit_record: &HitRecord) -> Option<(Vec3, Vec3)> {
+        let thickness = self.film.as_ref().map(|film| film.thickness_at(hit_record));
+        let (wi, [red, green, blue]) =
+            self.sample_channels(wo, hit_record, self.ior, |cos_theta, outside, inside| {
+                match (&self.film, thickness) {
+                    (Some(film), Some(thickness)) => {
+                        let rgb = film.reflectance(cos_theta, outside, thickness, |_| (inside, 0.0));
+                        [rgb.x, rgb.y, rgb.z]
+                    }
+                    _ => [microfacet::fresnel_dielectric(cos_theta, inside / outside); 3],
+                }
+            })?;
+        Some((wi, Vec3::new(red, green, blue)))
+    }
+
+    /// `sample` for light at `wavelengths`. Directions are bent by the
+    /// index at the hero wavelength, so where the glass disperses the other
+    /// wavelengths have to be dropped.
+    pub fn sample_spectral(
+        &self,
+        wo: &Vec3,
+        hit_record: &HitRecord,
+        wavelengths: &Wavelengths,
+    ) -> Option<(Vec3, SampledSpectrum)> {
+        let thickness = self.film.as_ref().map(|film| film.thickness_at(hit_record));
+        let (wi, values) =
+            self.sample_channels(wo, hit_record, self.ior_at(wavelengths.hero()), |cos_theta, outside, inside| {
+                wavelengths.values.map(|wavelength| match (&self.film, thickness) {
+                    (Some(film), Some(thickness)) => {
+                        film.reflectance_at(wavelength, cos_theta, outside, thickness, (inside, 0.0))
+                    }
+                    _ => microfacet::fresnel_dielectric(cos_theta, inside / outside),
+                })
+            })?;
+        Some((wi, SampledSpectrum::new(values)))
+    }
+
+    /// Reflects or refracts through a sampled microfacet by the average of
+    /// `reflectance`, given the cosine and the indices on either side, and
+    /// makes up each channel in the weight. What the surface doesn't reflect
+    /// it lets through.
+    fn sample_channels<const N: usize>(
+        &self,
+        wo: &Vec3,
+        hit_record: &HitRecord,
+        ior: f32,
+        reflectance: impl Fn(f32, f32, f32) -> [f32; N],
+    ) -> Option<(Vec3, [f32; N])> {
+        if wo.z <= 0.0 {
+            return None;
+        }
+        let m = self
+            .distribution
+            .sample_visible_normal(wo, random_double(), random_double());
+        let (outside, inside) = if hit_record.front_face { (1.0, ior) } else { (ior, 1.0) };
+        let reflectance = reflectance(wo.dot(&m), outside, inside);
+        let chance = reflectance.iter().sum::<f32>() / N as f32;
+        let (wi, weight) = if random_double() < chance {
+            let wi = microfacet::reflect(wo, &m);
+            (wi.z > 0.0).then_some((wi, reflectance.map(|r| r / chance)))?
+        } else {
+            let wi = wo.multiply(-1.0).refract(&m, outside / inside).filter(|wi| wi.z < 0.0)?;
+            (wi, reflectance.map(|r| (1.0 - r) / (1.0 - chance)))
+        };
+        let wi_above = Vec3::new(wi.x, wi.y, wi.z.abs());
+        let shadowing = self.distribution.g(wo, &wi_above) / self.distribution.g1(wo);
+        Some((wi, weight.map(|w| w * shadowing)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_at_the_d_line_is_the_nominal_one() {
+        let glass = Dielectric::new(1.52, 0.0).with_dispersion(40.0);
+        assert!((glass.ior_at(587.6) - 1.52).abs() < 1e-6);
+    }
+
+    #[test]
+    fn dispersion_bends_blue_more_than_red() {
+        let glass = Dielectric::new(1.52, 0.0).with_dispersion(40.0);
+        assert!(glass.ior_at(450.0) > glass.ior_at(650.0));
+        // the Abbe number is (n_d - 1) / (n_F - n_C)
+        let abbe = (glass.ior_at(587.6) - 1.0) / (glass.ior_at(486.1) - glass.ior_at(656.3));
+        assert!((abbe - 40.0).abs() < 0.1, "{}", abbe);
+    }
+}
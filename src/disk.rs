@@ -0,0 +1,73 @@
+use crate::{
+    aabb::Aabb,
+    hitable::{HitRecord, Hitable},
+    material::Material,
+    ray::Ray,
+    vec3::Vec3,
+};
+
+/// Flat disk; u is the angle around the centre and v the distance from it,
+/// both in [0, 1].
+#[derive(Debug, Clone)]
+pub struct Disk {
+    center: Vec3,
+    normal: Vec3,
+    radius: f32,
+    tangent: Vec3,
+    bitangent: Vec3,
+    material: Material,
+}
+
+impl Disk {
+    pub fn new(center: Vec3, normal: Vec3, radius: f32, material: Material) -> Self {
+        let normal = normal.normalize();
+        let (tangent, bitangent) = normal.orthonormal_basis();
+        Disk {
+            center,
+            normal,
+            radius,
+            tangent,
+            bitangent,
+            material,
+        }
+    }
+}
+
+impl Hitable for Disk {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let denominator = self.normal.dot(&ray.direction);
+        if denominator.abs() < 1e-8 {
+            return None;
+        }
+        let t = self.center.sub(&ray.origin).dot(&self.normal) / denominator;
+        if t <= t_min || t >= t_max {
+            return None;
+        }
+
+        let offset = ray.at(t).sub(&self.center);
+        let distance_squared = offset.length_squared();
+        if distance_squared > self.radius * self.radius {
+            return None;
+        }
+        let phi = offset.dot(&self.bitangent).atan2(offset.dot(&self.tangent)) + std::f32::consts::PI;
+        Some(HitRecord::new(
+            ray,
+            t,
+            self.normal,
+            self.material.clone(),
+            phi / (2.0 * std::f32::consts::PI),
+            distance_squared.sqrt() / self.radius,
+        ))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let n = self.normal;
+        let extent = Vec3::new(
+            (1.0 - n.x * n.x).max(0.0).sqrt(),
+            (1.0 - n.y * n.y).max(0.0).sqrt(),
+            (1.0 - n.z * n.z).max(0.0).sqrt(),
+        )
+        .multiply(self.radius);
+        Some(Aabb::new(self.center.sub(&extent), self.center.add(&extent)))
+    }
+}
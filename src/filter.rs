@@ -0,0 +1,123 @@
+use std::f32::consts::PI;
+
+use crate::util::random_double;
+
+const TABLE_SIZE: usize = 64;
+const MIN_RADIUS: f32 = 1e-3;
+
+#[derive(Debug, Clone, Copy)]
+pub enum FilterKind {
+    Box,
+    Tent,
+    Gaussian { sigma: f32 },
+    MitchellNetravali { b: f32, c: f32 },
+    Lanczos { tau: f32 },
+    BlackmanHarris,
+}
+
+/// Separable pixel reconstruction filter. Sample offsets are importance sampled
+/// from a table of |f|, and each sample carries the weight f / pdf so that
+/// filters with negative lobes stay unbiased once pixels are normalized by
+/// the sum of weights.
+#[derive(Debug, Clone)]
+pub struct Filter {
+    pub kind: FilterKind,
+    pub radius: f32,
+    cdf: Vec<f32>,
+}
+
+impl Filter {
+    /// Filter of `kind` reaching `radius` pixels out from the sample. Radii
+    /// below a thousandth of a pixel, including zero, negative and NaN, are
+    /// clamped up to that, which behaves like no filtering at all.
+    pub fn new(kind: FilterKind, radius: f32) -> Self {
+        let radius = radius.max(MIN_RADIUS);
+        let mut filter = Filter {
+            kind,
+            radius,
+            cdf: vec![0.0; TABLE_SIZE + 1],
+        };
+
+        let bin_width = 2.0 * radius / TABLE_SIZE as f32;
+        for i in 0..TABLE_SIZE {
+            let x = -radius + (i as f32 + 0.5) * bin_width;
+            filter.cdf[i + 1] = filter.cdf[i] + filter.evaluate_1d(x).abs() * bin_width;
+        }
+        let integral = filter.cdf[TABLE_SIZE];
+        for value in filter.cdf.iter_mut() {
+            *value /= integral;
+        }
+        filter
+    }
+
+    /// Returns a pixel offset and the weight of a sample taken there.
+    pub fn sample(&self) -> (f32, f32, f32) {
+        let (x, weight_x) = self.sample_1d(random_double());
+        let (y, weight_y) = self.sample_1d(random_double());
+        (x, y, weight_x * weight_y)
+    }
+
+    fn sample_1d(&self, u: f32) -> (f32, f32) {
+        let bin = self.cdf.partition_point(|&value| value <= u).clamp(1, TABLE_SIZE) - 1;
+        let bin_width = 2.0 * self.radius / TABLE_SIZE as f32;
+        let bin_probability = self.cdf[bin + 1] - self.cdf[bin];
+        let offset = if bin_probability > 0.0 {
+            (u - self.cdf[bin]) / bin_probability
+        } else {
+            0.5
+        };
+        let x = -self.radius + (bin as f32 + offset) * bin_width;
+        let pdf = bin_probability / bin_width;
+        (x, self.evaluate_1d(x) / pdf)
+    }
+
+    fn evaluate_1d(&self, x: f32) -> f32 {
+        let x = x.abs();
+        if x > self.radius {
+            return 0.0;
+        }
+        match self.kind {
+            FilterKind::Box => 1.0,
+            FilterKind::Tent => self.radius - x,
+            FilterKind::Gaussian { sigma } => {
+                let gaussian = |x: f32| (-x * x / (2.0 * sigma * sigma)).exp();
+                (gaussian(x) - gaussian(self.radius)).max(0.0)
+            }
+            FilterKind::MitchellNetravali { b, c } => mitchell(2.0 * x / self.radius, b, c),
+            FilterKind::Lanczos { tau } => sinc(x) * sinc(x / tau),
+            FilterKind::BlackmanHarris => {
+                let t = (x + self.radius) / (2.0 * self.radius);
+                0.35875 - 0.48829 * (2.0 * PI * t).cos() + 0.14128 * (4.0 * PI * t).cos()
+                    - 0.01168 * (6.0 * PI * t).cos()
+            }
+        }
+    }
+}
+
+impl Default for Filter {
+    /// The 1.5 pixel wide box the renderer has always used.
+    fn default() -> Self {
+        Filter::new(FilterKind::Box, 0.75)
+    }
+}
+
+fn mitchell(x: f32, b: f32, c: f32) -> f32 {
+    let x = x.abs();
+    if x <= 1.0 {
+        ((12.0 - 9.0 * b - 6.0 * c) * x * x * x + (-18.0 + 12.0 * b + 6.0 * c) * x * x + (6.0 - 2.0 * b))
+            / 6.0
+    } else if x <= 2.0 {
+        ((-b - 6.0 * c) * x * x * x + (6.0 * b + 30.0 * c) * x * x + (-12.0 * b - 48.0 * c) * x
+            + (8.0 * b + 24.0 * c))
+            / 6.0
+    } else {
+        0.0
+    }
+}
+
+fn sinc(x: f32) -> f32 {
+    if x.abs() < 1e-5 {
+        return 1.0;
+    }
+    (PI * x).sin() / (PI * x)
+}
@@ -0,0 +1,48 @@
+use crate::vec3::Vec3;
+
+/// Orthonormal local coordinate system placed at `origin`, used by primitives
+/// that are easier to intersect in their own object space.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    pub origin: Vec3,
+    pub x: Vec3,
+    pub y: Vec3,
+    pub z: Vec3,
+}
+
+impl Frame {
+    /// Frame from the directions of the local x and y axes, which are
+    /// orthonormalized around x.
+    pub fn new(origin: Vec3, x_axis: Vec3, y_axis: Vec3) -> Self {
+        let x = x_axis.normalize();
+        let z = x.cross(&y_axis).normalize();
+        let y = z.cross(&x);
+        Frame { origin, x, y, z }
+    }
+
+    /// Frame whose local z axis points along `axis`.
+    pub fn from_axis(origin: Vec3, axis: Vec3) -> Self {
+        let z = axis.normalize();
+        let (x, y) = z.orthonormal_basis();
+        Frame { origin, x, y, z }
+    }
+
+    pub fn to_local(&self, vector: &Vec3) -> Vec3 {
+        Vec3::new(vector.dot(&self.x), vector.dot(&self.y), vector.dot(&self.z))
+    }
+
+    pub fn to_world(&self, vector: &Vec3) -> Vec3 {
+        self.x
+            .multiply(vector.x)
+            .add(&self.y.multiply(vector.y))
+            .add(&self.z.multiply(vector.z))
+    }
+
+    pub fn point_to_local(&self, point: &Vec3) -> Vec3 {
+        self.to_local(&point.sub(&self.origin))
+    }
+
+    pub fn point_to_world(&self, point: &Vec3) -> Vec3 {
+        self.origin.add(&self.to_world(point))
+    }
+}
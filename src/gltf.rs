@@ -0,0 +1,446 @@
+use std::{f32::consts::PI, fs, io, path::Path, sync::Arc};
+
+use gltf::{
+    Document,
+    buffer,
+    image::{Data, Format},
+    khr_lights_punctual::Kind,
+    mesh::Mode,
+};
+use image::{DynamicImage, ImageBuffer, Rgb32FImage};
+
+use crate::{
+    camera::Camera,
+    disk::Disk,
+    hitable_list::HitableList,
+    material::Material,
+    mesh::TriangleMesh,
+    principled::Principled,
+    sphere::Sphere,
+    texture::{self, NormalMap, Texture},
+    transform::Transform,
+    vec3::Vec3,
+};
+
+/// Radius of the spheres that stand in for point and spot lights.
+const LIGHT_RADIUS: f32 = 0.05;
+/// Angular radius of the disks that stand in for directional lights.
+const SUN_ANGLE: f32 = 5.0;
+const SUN_DISTANCE: f32 = 1000.0;
+
+/// Scene imported from glTF 2.0 (`.gltf` with external or embedded buffers,
+/// or binary `.glb`).
+///
+/// Every triangle primitive becomes a `TriangleMesh` placed by its node's
+/// world transform, and mesh nodes are added to the world under their name.
+/// Metallic-roughness materials map to `Principled`, together with the
+/// factors of the anisotropy, clearcoat, ior, sheen, specular and
+/// transmission extensions, wrapped in `NormalMapped` when they have a
+/// normal texture; emissive ones become lights. Lights from
+/// `KHR_lights_punctual` become small emissive spheres (spot cones are not
+/// modelled), or distant disks for directional lights, with their radiance
+/// derived from the intensity in candela or lux.
+pub struct GltfScene {
+    pub world: HitableList,
+    cameras: Vec<CameraPlacement>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CameraPlacement {
+    look_from: Vec3,
+    look_at: Vec3,
+    up: Vec3,
+    fov: f32,
+}
+
+impl GltfScene {
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let data = fs::read(&path)?;
+        let base = path.as_ref().parent().unwrap_or(Path::new(""));
+        GltfScene::parse(&data, base)
+    }
+
+    /// Parses a glTF or GLB file; relative URIs are resolved against `base`.
+    pub fn parse(data: &[u8], base: &Path) -> io::Result<Self> {
+        let gltf = gltf::Gltf::from_slice(data).map_err(gltf_error)?;
+        let buffers = gltf::import_buffers(&gltf.document, Some(base), gltf.blob).map_err(gltf_error)?;
+        // colour textures are stored in sRGB and data textures as they are, so
+        // each image is converted the way its materials use it
+        let image_count = gltf.document.images().len();
+        let mut srgb = vec![false; image_count];
+        let mut linear = vec![false; image_count];
+        for material in gltf.document.materials() {
+            let pbr = material.pbr_metallic_roughness();
+            for texture in [pbr.base_color_texture(), material.emissive_texture()].into_iter().flatten() {
+                srgb[texture.texture().source().index()] = true;
+            }
+            let data_textures = [
+                pbr.metallic_roughness_texture().map(|info| info.texture()),
+                material.normal_texture().map(|info| info.texture()),
+                material.occlusion_texture().map(|info| info.texture()),
+            ];
+            for texture in data_textures.into_iter().flatten() {
+                linear[texture.source().index()] = true;
+            }
+        }
+        let mut images = vec![None; image_count];
+        let mut linear_images = vec![None; image_count];
+        for image in gltf.document.images() {
+            let index = image.index();
+            if !srgb[index] && !linear[index] {
+                continue;
+            }
+            let decoded = decode(Data::from_source(image.source(), Some(base), &buffers).map_err(gltf_error)?)?;
+            if srgb[index] {
+                images[index] = Some(Arc::new(texture::srgb_to_linear(&decoded)));
+            }
+            if linear[index] {
+                linear_images[index] = Some(Arc::new(decoded.to_rgb32f()));
+            }
+        }
+        let document = Loaded {
+            document: gltf.document,
+            buffers,
+            images,
+            linear_images,
+        };
+
+        let mut scene = GltfScene {
+            world: HitableList::new(),
+            cameras: vec![],
+        };
+        let roots: Vec<gltf::Node> = match document.document.default_scene().or_else(|| document.document.scenes().next()) {
+            Some(root_scene) => root_scene.nodes().collect(),
+            // without scenes, every node that is nobody's child is a root
+            None => {
+                let children: Vec<usize> = document
+                    .document
+                    .nodes()
+                    .flat_map(|node| node.children())
+                    .map(|child| child.index())
+                    .collect();
+                document
+                    .document
+                    .nodes()
+                    .filter(|node| !children.contains(&node.index()))
+                    .collect()
+            }
+        };
+        for root in roots {
+            document.visit(&root, &Transform::identity(), 0, &mut scene)?;
+        }
+        Ok(scene)
+    }
+
+    pub fn camera_count(&self) -> usize {
+        self.cameras.len()
+    }
+
+    /// Camera for the `index`th camera node. Images are always square, so the
+    /// file's aspect ratio is ignored and its vertical field of view is kept.
+    pub fn camera(&self, index: usize, width: u32, samples_per_pixel: u32) -> Option<Camera> {
+        let placement = self.cameras.get(index)?;
+        Some(Camera::new(
+            0.0,
+            1.0,
+            placement.fov,
+            placement.look_from,
+            placement.look_at,
+            placement.up,
+            width,
+            samples_per_pixel,
+        ))
+    }
+}
+
+/// Parsed document plus the loaded buffers and images it refers to. Images
+/// are decoded from sRGB where materials use them for colour and as stored
+/// where they hold data such as normal maps; unused images are skipped.
+struct Loaded {
+    document: Document,
+    buffers: Vec<buffer::Data>,
+    images: Vec<Option<Arc<Rgb32FImage>>>,
+    linear_images: Vec<Option<Arc<Rgb32FImage>>>,
+}
+
+impl Loaded {
+    fn local_transform(node: &gltf::Node) -> Transform {
+        match node.transform() {
+            gltf::scene::Transform::Matrix { matrix } => {
+                // glTF matrices are column major
+                let mut transform = Transform::identity();
+                for (column, values) in matrix.iter().enumerate() {
+                    for (row, value) in values.iter().enumerate() {
+                        transform.m[row][column] = *value;
+                    }
+                }
+                transform
+            }
+            gltf::scene::Transform::Decomposed {
+                translation: [tx, ty, tz],
+                rotation: [qx, qy, qz, qw],
+                scale: [sx, sy, sz],
+            } => Transform::translate(Vec3::new(tx, ty, tz))
+                .multiply(&Transform::from_quaternion(qx, qy, qz, qw))
+                .multiply(&Transform::scale(Vec3::new(sx, sy, sz))),
+        }
+    }
+
+    fn visit(&self, node: &gltf::Node, parent: &Transform, depth: usize, scene: &mut GltfScene) -> io::Result<()> {
+        if depth > 256 {
+            return Err(invalid_data("glTF node hierarchy contains a cycle".to_string()));
+        }
+        let transform = parent.multiply(&Loaded::local_transform(node));
+        let name = node.name();
+
+        if let Some(mesh) = node.mesh() {
+            let primitive_count = mesh.primitives().len();
+            for (primitive_index, primitive) in mesh.primitives().enumerate() {
+                let Some(mesh) = self.primitive(&primitive)? else {
+                    continue;
+                };
+                let mesh = Box::new(mesh.transformed(&transform));
+                match name {
+                    Some(name) if primitive_count == 1 => scene.world.add_named(name, mesh),
+                    Some(name) => scene.world.add_named(&format!("{}/{}", name, primitive_index), mesh),
+                    None => scene.world.add(mesh),
+                }
+            }
+        }
+
+        if let Some(camera) = node.camera()
+            && let gltf::camera::Projection::Perspective(perspective) = camera.projection()
+        {
+            let look_from = transform.transform_point(&Vec3::new(0.0, 0.0, 0.0));
+            let forward = transform.transform_vector(&Vec3::new(0.0, 0.0, -1.0)).normalize();
+            scene.cameras.push(CameraPlacement {
+                look_from,
+                look_at: look_from.add(&forward),
+                up: transform.transform_vector(&Vec3::new(0.0, 1.0, 0.0)).normalize(),
+                // the camera's fov is half the vertical angle
+                fov: perspective.yfov().to_degrees() / 2.0,
+            });
+        }
+
+        if let Some(light) = node.light() {
+            add_light(&light, &transform, scene);
+        }
+
+        for child in node.children() {
+            self.visit(&child, &transform, depth + 1, scene)?;
+        }
+        Ok(())
+    }
+
+    /// Mesh for a triangle primitive, `None` for points and lines.
+    fn primitive(&self, primitive: &gltf::Primitive) -> io::Result<Option<TriangleMesh>> {
+        if primitive.mode() != Mode::Triangles {
+            return Ok(None);
+        }
+        let reader = primitive.reader(|buffer| self.buffers.get(buffer.index()).map(|data| &data.0[..]));
+        let vec3 = |[x, y, z]: [f32; 3]| Vec3::new(x, y, z);
+        let positions: Vec<Vec3> = reader
+            .read_positions()
+            .ok_or_else(|| invalid_data("glTF primitive without positions".to_string()))?
+            .map(vec3)
+            .collect();
+        let indices: Vec<usize> = match reader.read_indices() {
+            Some(indices) => indices.into_u32().map(|index| index as usize).collect(),
+            None => (0..positions.len()).collect(),
+        };
+        if let Some(index) = indices.iter().find(|&&index| index >= positions.len()) {
+            return Err(invalid_data(format!("glTF primitive refers to missing vertex {}", index)));
+        }
+        let indices = indices.chunks_exact(3).map(|face| [face[0], face[1], face[2]]).collect();
+
+        // every attribute needs one value per position
+        let vertex_count = positions.len();
+        let check = |name: &str, len: usize| {
+            if len == vertex_count {
+                Ok(())
+            } else {
+                Err(invalid_data(format!(
+                    "glTF primitive has {} positions but {} {}",
+                    vertex_count, len, name
+                )))
+            }
+        };
+        let normals: Option<Vec<Vec3>> = reader.read_normals().map(|normals| normals.map(vec3).collect());
+        let uvs: Option<Vec<(f32, f32)>> = reader
+            .read_tex_coords(0)
+            .map(|uvs| uvs.into_f32().map(|[u, v]| (u, v)).collect());
+        let tangents: Option<Vec<Vec3>> = reader
+            .read_tangents()
+            .map(|tangents| tangents.map(|[x, y, z, _]| Vec3::new(x, y, z)).collect());
+        let colors: Vec<Vec3> = reader
+            .read_colors(0)
+            .map_or(vec![], |colors| colors.into_rgb_f32().map(vec3).collect());
+        if let Some(normals) = &normals {
+            check("normals", normals.len())?;
+        }
+        if let Some(uvs) = &uvs {
+            check("UVs", uvs.len())?;
+        }
+        if let Some(tangents) = &tangents {
+            check("tangents", tangents.len())?;
+        }
+        if !colors.is_empty() {
+            check("colours", colors.len())?;
+        }
+        let material = self.material(&primitive.material(), !colors.is_empty());
+
+        let mut mesh = TriangleMesh::new(positions, indices, material);
+        if let Some(normals) = normals {
+            mesh = mesh.with_normals(normals);
+        }
+        if let Some(uvs) = uvs {
+            mesh = mesh.with_uvs(uvs);
+        }
+        if let Some(tangents) = tangents {
+            mesh = mesh.with_tangents(tangents);
+        }
+        if !colors.is_empty() {
+            mesh = mesh.with_colors(colors);
+        }
+        Ok(Some(mesh))
+    }
+
+    fn material(&self, material: &gltf::Material, has_vertex_colors: bool) -> Material {
+        let [red, green, blue] = material.emissive_factor();
+        let strength = material.emissive_strength().unwrap_or(1.0);
+        if red > 0.0 || green > 0.0 || blue > 0.0 {
+            return Material::Light(Vec3::new(red, green, blue).multiply(strength));
+        }
+
+        let pbr = material.pbr_metallic_roughness();
+        let [red, green, blue, _] = pbr.base_color_factor();
+        let base_texture = pbr
+            .base_color_texture()
+            .and_then(|info| self.images.get(info.texture().source().index())?.as_ref());
+
+        // extensions the gltf crate doesn't model, read from the raw JSON
+        let factor = |name: &str, key: &str, default: f32| {
+            material
+                .extension_value(name)
+                .and_then(|extension| extension.get(key))
+                .and_then(|value| value.as_f64())
+                .map_or(default, |value| value as f32)
+        };
+        let sheen = material
+            .extension_value("KHR_materials_sheen")
+            .and_then(|extension| extension.get("sheenColorFactor"))
+            .and_then(|value| value.as_array())
+            .map_or(0.0, |color| {
+                color.iter().filter_map(|channel| channel.as_f64()).fold(0.0, f64::max) as f32
+            });
+
+        let surface = Material::Principled(Box::new(Principled {
+            base_color: Vec3::new(red, green, blue),
+            base_texture: match base_texture {
+                Some(image) => Some(Texture::Image {
+                    image: image.clone(),
+                    tint: Vec3::new(1.0, 1.0, 1.0),
+                }),
+                None => has_vertex_colors.then_some(Texture::VertexColor),
+            },
+            metallic: pbr.metallic_factor(),
+            roughness: pbr.roughness_factor(),
+            anisotropy: factor("KHR_materials_anisotropy", "anisotropyStrength", 0.0),
+            specular: 0.5 * material.specular().map_or(1.0, |specular| specular.specular_factor()),
+            specular_tint: 0.0,
+            ior: material.ior().unwrap_or(1.5),
+            sheen,
+            sheen_tint: 0.0,
+            clearcoat: factor("KHR_materials_clearcoat", "clearcoatFactor", 0.0),
+            clearcoat_roughness: factor("KHR_materials_clearcoat", "clearcoatRoughnessFactor", 0.0),
+            transmission: material
+                .transmission()
+                .map_or(0.0, |transmission| transmission.transmission_factor()),
+            subsurface: 0.0,
+        }));
+
+        match material.normal_texture().and_then(|normal_texture| {
+            let map = self.linear_images.get(normal_texture.texture().source().index())?.as_ref()?;
+            Some((map, normal_texture.scale()))
+        }) {
+            Some((map, strength)) => Material::NormalMapped {
+                material: Box::new(surface),
+                map: NormalMap::new(map.clone()),
+                strength,
+            },
+            None => surface,
+        }
+    }
+}
+
+fn add_light(light: &gltf::khr_lights_punctual::Light, transform: &Transform, scene: &mut GltfScene) {
+    let [red, green, blue] = light.color();
+    let color = Vec3::new(red, green, blue).multiply(light.intensity());
+    let position = transform.transform_point(&Vec3::new(0.0, 0.0, 0.0));
+
+    match light.kind() {
+        Kind::Directional => {
+            // lux spread over the solid angle of the disk
+            let direction = transform.transform_vector(&Vec3::new(0.0, 0.0, -1.0)).normalize();
+            let angle = SUN_ANGLE.to_radians();
+            let radiance = color.divide(PI * angle.sin() * angle.sin());
+            scene.world.add(Box::new(Disk::new(
+                direction.multiply(-SUN_DISTANCE),
+                direction,
+                SUN_DISTANCE * angle.tan(),
+                Material::Light(radiance),
+            )));
+        }
+        // candela over the projected area of the sphere
+        _ => scene.world.add(Box::new(Sphere::new(
+            position,
+            LIGHT_RADIUS,
+            Material::Light(color.divide(PI * LIGHT_RADIUS * LIGHT_RADIUS)),
+        ))),
+    }
+}
+
+/// Pixels decoded by the gltf crate, back in an `image` buffer.
+fn decode(data: Data) -> io::Result<DynamicImage> {
+    let (width, height, pixels) = (data.width, data.height, data.pixels);
+    let words = |pixels: &[u8]| {
+        pixels
+            .chunks_exact(2)
+            .map(|bytes| u16::from_ne_bytes([bytes[0], bytes[1]]))
+            .collect()
+    };
+    let floats = |pixels: &[u8]| {
+        pixels
+            .chunks_exact(4)
+            .map(|bytes| f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .collect()
+    };
+    let image = match data.format {
+        Format::R8 => ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageLuma8),
+        Format::R8G8 => ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageLumaA8),
+        Format::R8G8B8 => ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgb8),
+        Format::R8G8B8A8 => ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgba8),
+        Format::R16 => ImageBuffer::from_raw(width, height, words(&pixels)).map(DynamicImage::ImageLuma16),
+        Format::R16G16 => ImageBuffer::from_raw(width, height, words(&pixels)).map(DynamicImage::ImageLumaA16),
+        Format::R16G16B16 => ImageBuffer::from_raw(width, height, words(&pixels)).map(DynamicImage::ImageRgb16),
+        Format::R16G16B16A16 => ImageBuffer::from_raw(width, height, words(&pixels)).map(DynamicImage::ImageRgba16),
+        Format::R32G32B32FLOAT => ImageBuffer::from_raw(width, height, floats(&pixels)).map(DynamicImage::ImageRgb32F),
+        Format::R32G32B32A32FLOAT => {
+            ImageBuffer::from_raw(width, height, floats(&pixels)).map(DynamicImage::ImageRgba32F)
+        }
+    };
+    image.ok_or_else(|| invalid_data("glTF image has fewer pixels than its size".to_string()))
+}
+
+/// Missing files stay `NotFound`; everything else the gltf crate rejects is
+/// invalid data.
+fn gltf_error(error: gltf::Error) -> io::Error {
+    match error {
+        gltf::Error::Io(error) => error,
+        error => invalid_data(error.to_string()),
+    }
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,312 @@
+use std::f32::consts::PI;
+
+use crate::{
+    microfacet::fresnel_dielectric,
+    util::{luminance, random_double},
+    vec3::Vec3,
+};
+
+/// Scattering orders modelled separately: R, TT, TRT, and everything after.
+const P_MAX: usize = 3;
+
+/// Hair fibre scattering after Chiang et al. 2016, as in pbrt: dielectric
+/// cylinders with tilted cuticle scales and a pigmented interior. Meant for
+/// `Curves`, which give the fibre direction in the hit record's tangent and
+/// the offset across the fibre in its v.
+#[derive(Debug, Clone, Copy)]
+pub struct Hair {
+    /// Absorption inside the fibre per unit of its diameter.
+    pub sigma_a: Vec3,
+    pub eta: f32,
+    /// Longitudinal roughness in [0, 1].
+    pub beta_m: f32,
+    /// Azimuthal roughness in [0, 1].
+    pub beta_n: f32,
+    /// Tilt of the cuticle scales in degrees.
+    pub alpha: f32,
+}
+
+impl Hair {
+    /// Hair coloured by its eumelanin (brown to black, about 0 to 8) and
+    /// pheomelanin (red) concentrations.
+    pub fn from_melanin(eumelanin: f32, pheomelanin: f32) -> Self {
+        let eumelanin_sigma_a = Vec3::new(0.419, 0.697, 1.37);
+        let pheomelanin_sigma_a = Vec3::new(0.187, 0.4, 1.05);
+        Hair {
+            sigma_a: eumelanin_sigma_a
+                .multiply(eumelanin)
+                .add(&pheomelanin_sigma_a.multiply(pheomelanin)),
+            ..Hair::default()
+        }
+    }
+
+    /// Hair whose multiply scattered colour is roughly `color`, e.g. for
+    /// dyed hair or fur.
+    pub fn from_color(color: Vec3, beta_n: f32) -> Self {
+        let denominator = 5.969 - 0.215 * beta_n + 2.532 * beta_n.powi(2) - 10.73 * beta_n.powi(3)
+            + 5.574 * beta_n.powi(4)
+            + 0.245 * beta_n.powi(5);
+        let sigma_a = |c: f32| (c.max(1e-4).ln() / denominator).powi(2);
+        Hair {
+            sigma_a: Vec3::new(sigma_a(color.x), sigma_a(color.y), sigma_a(color.z)),
+            beta_n,
+            ..Hair::default()
+        }
+    }
+
+    /// Picks an incoming direction for light leaving toward `wo`. The fibre
+    /// runs along `tangent` and `h` in [-1, 1] is where across it the
+    /// surface was hit. Returns the direction and the weight to multiply the
+    /// light arriving from it by.
+    pub fn sample(&self, wo: &Vec3, tangent: &Vec3, h: f32) -> (Vec3, Vec3) {
+        // local frame: x along the fibre, z toward the viewer across it
+        let x_axis = tangent.normalize();
+        let facing = wo.sub(&x_axis.multiply(wo.dot(&x_axis)));
+        let z_axis = if facing.length_squared() > 1e-12 {
+            facing.normalize()
+        } else {
+            x_axis.orthonormal_basis().0
+        };
+        let y_axis = z_axis.cross(&x_axis);
+        let local_wo = Vec3::new(wo.dot(&x_axis), wo.dot(&y_axis), wo.dot(&z_axis)).normalize();
+
+        let lobes = Lobes::new(self, h);
+        let sin_theta_o = local_wo.x;
+        let cos_theta_o = safe_sqrt(1.0 - sin_theta_o * sin_theta_o);
+        let phi_o = local_wo.z.atan2(local_wo.y);
+
+        // pick a lobe by how much each one reflects
+        let attenuation = lobes.attenuation(cos_theta_o, sin_theta_o);
+        let luminances = attenuation.map(|a| luminance(&a));
+        let total: f32 = luminances.iter().sum();
+        let mut choice = random_double() * total;
+        let mut p = 0;
+        while p < P_MAX && choice >= luminances[p] {
+            choice -= luminances[p];
+            p += 1;
+        }
+
+        // longitudinal angle from M_p
+        let (sin_theta_op, cos_theta_op) = lobes.tilt(p, sin_theta_o, cos_theta_o);
+        let v = lobes.variance[p];
+        let u = random_double().max(1e-5);
+        let cos_theta = 1.0 + v * (u + (1.0 - u) * (-2.0 / v).exp()).ln();
+        let sin_theta = safe_sqrt(1.0 - cos_theta * cos_theta);
+        let cos_phi = (2.0 * PI * random_double()).cos();
+        let sin_theta_i = (-cos_theta * sin_theta_op + sin_theta * cos_phi * cos_theta_op).clamp(-1.0, 1.0);
+        let cos_theta_i = safe_sqrt(1.0 - sin_theta_i * sin_theta_i);
+
+        // azimuth from N_p
+        let gamma_t = lobes.gamma_t(sin_theta_o, cos_theta_o);
+        let dphi = if p < P_MAX {
+            azimuth(p, lobes.gamma_o, gamma_t) + sample_trimmed_logistic(random_double(), lobes.s, -PI, PI)
+        } else {
+            2.0 * PI * random_double()
+        };
+        let phi_i = phi_o + dphi;
+        let local_wi = Vec3::new(sin_theta_i, cos_theta_i * phi_i.cos(), cos_theta_i * phi_i.sin());
+
+        // weight f·cos/pdf, with the pdf from the same lobes weighted by the
+        // chances of picking them
+        let mut value = Vec3::new(0.0, 0.0, 0.0);
+        let mut pdf = 0.0;
+        for (p, attenuation) in attenuation.iter().enumerate() {
+            let (sin_theta_op, cos_theta_op) = lobes.tilt(p, sin_theta_o, cos_theta_o);
+            let longitudinal = longitudinal(cos_theta_i, cos_theta_op.abs(), sin_theta_i, sin_theta_op, lobes.variance[p]);
+            let azimuthal = if p < P_MAX {
+                azimuthal(phi_i - phi_o, p, lobes.s, lobes.gamma_o, gamma_t)
+            } else {
+                1.0 / (2.0 * PI)
+            };
+            value = value.add(&attenuation.multiply(longitudinal * azimuthal));
+            pdf += longitudinal * azimuthal * luminances[p] / total;
+        }
+        let weight = if pdf > 0.0 {
+            value.divide(pdf)
+        } else {
+            Vec3::new(0.0, 0.0, 0.0)
+        };
+        let wi = x_axis
+            .multiply(local_wi.x)
+            .add(&y_axis.multiply(local_wi.y))
+            .add(&z_axis.multiply(local_wi.z));
+        (wi, weight)
+    }
+}
+
+impl Default for Hair {
+    /// Brown hair with pbrt's default roughness and scale tilt.
+    fn default() -> Self {
+        Hair {
+            sigma_a: Vec3::new(0.419, 0.697, 1.37).multiply(1.3),
+            eta: 1.55,
+            beta_m: 0.3,
+            beta_n: 0.3,
+            alpha: 2.0,
+        }
+    }
+}
+
+/// Everything about the lobes that depends only on the fibre and on `h`.
+struct Lobes<'a> {
+    hair: &'a Hair,
+    h: f32,
+    gamma_o: f32,
+    variance: [f32; P_MAX + 1],
+    /// Logistic scale of the azimuthal lobes.
+    s: f32,
+    sin_2k_alpha: [f32; 3],
+    cos_2k_alpha: [f32; 3],
+}
+
+impl<'a> Lobes<'a> {
+    fn new(hair: &'a Hair, h: f32) -> Self {
+        let beta_m = hair.beta_m;
+        let beta_n = hair.beta_n;
+        let v0 = (0.726 * beta_m + 0.812 * beta_m * beta_m + 3.7 * beta_m.powi(20)).powi(2);
+        let mut sin_2k_alpha = [(hair.alpha.to_radians()).sin(), 0.0, 0.0];
+        let mut cos_2k_alpha = [safe_sqrt(1.0 - sin_2k_alpha[0] * sin_2k_alpha[0]), 0.0, 0.0];
+        for i in 1..3 {
+            sin_2k_alpha[i] = 2.0 * cos_2k_alpha[i - 1] * sin_2k_alpha[i - 1];
+            cos_2k_alpha[i] = cos_2k_alpha[i - 1].powi(2) - sin_2k_alpha[i - 1].powi(2);
+        }
+        Lobes {
+            hair,
+            h,
+            gamma_o: h.clamp(-1.0, 1.0).asin(),
+            variance: [v0, 0.25 * v0, 4.0 * v0, 4.0 * v0],
+            s: (PI / 8.0).sqrt() * (0.265 * beta_n + 1.194 * beta_n * beta_n + 5.372 * beta_n.powi(22)),
+            sin_2k_alpha,
+            cos_2k_alpha,
+        }
+    }
+
+    /// Refracted azimuthal offset inside the fibre.
+    fn gamma_t(&self, sin_theta_o: f32, cos_theta_o: f32) -> f32 {
+        let eta = self.hair.eta;
+        let eta_p = safe_sqrt(eta * eta - sin_theta_o * sin_theta_o) / cos_theta_o.max(1e-6);
+        (self.h / eta_p).clamp(-1.0, 1.0).asin()
+    }
+
+    /// How much of the light leaves after each number of internal paths.
+    fn attenuation(&self, cos_theta_o: f32, sin_theta_o: f32) -> [Vec3; P_MAX + 1] {
+        let eta = self.hair.eta;
+        let sin_theta_t = sin_theta_o / eta;
+        let cos_theta_t = safe_sqrt(1.0 - sin_theta_t * sin_theta_t);
+        let gamma_t = self.gamma_t(sin_theta_o, cos_theta_o);
+        let path = 2.0 * gamma_t.cos() / cos_theta_t;
+        let sigma_a = self.hair.sigma_a;
+        let transmittance = Vec3::new(
+            (-sigma_a.x * path).exp(),
+            (-sigma_a.y * path).exp(),
+            (-sigma_a.z * path).exp(),
+        );
+
+        let fresnel = fresnel_dielectric(cos_theta_o * safe_sqrt(1.0 - self.h * self.h), eta);
+        let mut attenuation = [Vec3::new(fresnel, fresnel, fresnel); P_MAX + 1];
+        attenuation[1] = transmittance.multiply((1.0 - fresnel).powi(2));
+        for p in 2..P_MAX {
+            attenuation[p] = attenuation[p - 1].multiply_vec(&transmittance).multiply(fresnel);
+        }
+        // geometric series for all the longer paths
+        let tf = transmittance.multiply(fresnel);
+        attenuation[P_MAX] = attenuation[P_MAX - 1].multiply_vec(&tf).multiply_vec(&Vec3::new(
+            1.0 / (1.0 - tf.x),
+            1.0 / (1.0 - tf.y),
+            1.0 / (1.0 - tf.z),
+        ));
+        attenuation
+    }
+
+    /// The outgoing angle as seen by lobe `p`, shifted by the scale tilt.
+    fn tilt(&self, p: usize, sin_theta_o: f32, cos_theta_o: f32) -> (f32, f32) {
+        let (sin, cos) = (self.sin_2k_alpha, self.cos_2k_alpha);
+        match p {
+            0 => (
+                sin_theta_o * cos[1] - cos_theta_o * sin[1],
+                cos_theta_o * cos[1] + sin_theta_o * sin[1],
+            ),
+            1 => (
+                sin_theta_o * cos[0] + cos_theta_o * sin[0],
+                cos_theta_o * cos[0] - sin_theta_o * sin[0],
+            ),
+            2 => (
+                sin_theta_o * cos[2] + cos_theta_o * sin[2],
+                cos_theta_o * cos[2] - sin_theta_o * sin[2],
+            ),
+            _ => (sin_theta_o, cos_theta_o),
+        }
+    }
+}
+
+fn safe_sqrt(x: f32) -> f32 {
+    x.max(0.0).sqrt()
+}
+
+/// Modified Bessel function of the first kind, order zero.
+fn bessel_i0(x: f32) -> f32 {
+    let mut value = 0.0;
+    let mut x2i = 1.0;
+    let mut factorial: f32 = 1.0;
+    let mut four_i = 1.0;
+    for i in 0..10 {
+        if i > 1 {
+            factorial *= i as f32;
+        }
+        value += x2i / (four_i * factorial * factorial);
+        x2i *= x * x;
+        four_i *= 4.0;
+    }
+    value
+}
+
+fn log_bessel_i0(x: f32) -> f32 {
+    if x > 12.0 {
+        x + 0.5 * (-(2.0 * PI).ln() + (1.0 / x).ln() + 1.0 / (8.0 * x))
+    } else {
+        bessel_i0(x).ln()
+    }
+}
+
+/// Longitudinal scattering M_p.
+fn longitudinal(cos_theta_i: f32, cos_theta_o: f32, sin_theta_i: f32, sin_theta_o: f32, v: f32) -> f32 {
+    let a = cos_theta_i * cos_theta_o / v;
+    let b = sin_theta_i * sin_theta_o / v;
+    if v <= 0.1 {
+        (log_bessel_i0(a) - b - 1.0 / v + std::f32::consts::LN_2 + (1.0 / (2.0 * v)).ln()).exp()
+    } else {
+        (-b).exp() * bessel_i0(a) / ((1.0 / v).sinh() * 2.0 * v)
+    }
+}
+
+/// Azimuthal direction light leaves in after `p` internal paths.
+fn azimuth(p: usize, gamma_o: f32, gamma_t: f32) -> f32 {
+    2.0 * p as f32 * gamma_t - 2.0 * gamma_o + p as f32 * PI
+}
+
+fn logistic(x: f32, s: f32) -> f32 {
+    let x = x.abs();
+    (-x / s).exp() / (s * (1.0 + (-x / s).exp()).powi(2))
+}
+
+fn logistic_cdf(x: f32, s: f32) -> f32 {
+    1.0 / (1.0 + (-x / s).exp())
+}
+
+/// Azimuthal scattering N_p.
+fn azimuthal(phi: f32, p: usize, s: f32, gamma_o: f32, gamma_t: f32) -> f32 {
+    let mut dphi = phi - azimuth(p, gamma_o, gamma_t);
+    while dphi > PI {
+        dphi -= 2.0 * PI;
+    }
+    while dphi < -PI {
+        dphi += 2.0 * PI;
+    }
+    logistic(dphi, s) / (logistic_cdf(PI, s) - logistic_cdf(-PI, s))
+}
+
+fn sample_trimmed_logistic(u: f32, s: f32, a: f32, b: f32) -> f32 {
+    let k = logistic_cdf(b, s) - logistic_cdf(a, s);
+    let x = -s * (1.0 / (u * k + logistic_cdf(a, s)) - 1.0).ln();
+    x.clamp(a, b)
+}
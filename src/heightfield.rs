@@ -0,0 +1,291 @@
+use std::{io, path::Path, sync::Arc};
+
+use noise::NoiseFn;
+
+use crate::{
+    aabb::Aabb,
+    hitable::{HitRecord, Hitable},
+    material::Material,
+    ray::Ray,
+    triangle::moller_trumbore_intersection,
+    vec3::Vec3,
+};
+
+/// Terrain given by a grid of height samples spanning `size_x` by `size_z`
+/// from `corner`, each cell split into two triangles with interpolated
+/// normals. Rays walk a min/max mipmap of the cells front to back, so only
+/// cells near the ray are tested. UVs run over the whole field.
+#[derive(Debug, Clone)]
+pub struct Heightfield {
+    corner: Vec3,
+    size_x: f32,
+    size_z: f32,
+    samples_x: usize,
+    samples_z: usize,
+    heights: Arc<Vec<f32>>,
+    normals: Arc<Vec<Vec3>>,
+    /// Per level (min, max) height of each node, level 0 being single cells.
+    levels: Arc<Vec<MipLevel>>,
+    material: Material,
+}
+
+#[derive(Debug, Clone)]
+struct MipLevel {
+    width: usize,
+    depth: usize,
+    bounds: Vec<(f32, f32)>,
+}
+
+impl Heightfield {
+    /// `heights` holds `samples_x * samples_z` heights above `corner.y` in
+    /// rows of constant z.
+    pub fn new(
+        heights: Vec<f32>,
+        samples_x: usize,
+        samples_z: usize,
+        corner: Vec3,
+        size_x: f32,
+        size_z: f32,
+        material: Material,
+    ) -> Self {
+        assert!(samples_x >= 2 && samples_z >= 2 && heights.len() == samples_x * samples_z);
+        let dx = size_x / (samples_x - 1) as f32;
+        let dz = size_z / (samples_z - 1) as f32;
+        let height = |x: usize, z: usize| heights[z * samples_x + x];
+
+        let mut normals = Vec::with_capacity(heights.len());
+        for z in 0..samples_z {
+            for x in 0..samples_x {
+                let (x0, x1) = (x.saturating_sub(1), (x + 1).min(samples_x - 1));
+                let (z0, z1) = (z.saturating_sub(1), (z + 1).min(samples_z - 1));
+                let slope_x = (height(x1, z) - height(x0, z)) / ((x1 - x0) as f32 * dx);
+                let slope_z = (height(x, z1) - height(x, z0)) / ((z1 - z0) as f32 * dz);
+                normals.push(Vec3::new(-slope_x, 1.0, -slope_z).normalize());
+            }
+        }
+
+        let mut levels = vec![];
+        let mut level = MipLevel {
+            width: samples_x - 1,
+            depth: samples_z - 1,
+            bounds: vec![],
+        };
+        for z in 0..level.depth {
+            for x in 0..level.width {
+                let corners = [height(x, z), height(x + 1, z), height(x, z + 1), height(x + 1, z + 1)];
+                level.bounds.push((
+                    corners.iter().copied().fold(f32::INFINITY, f32::min),
+                    corners.iter().copied().fold(f32::NEG_INFINITY, f32::max),
+                ));
+            }
+        }
+        while level.width > 1 || level.depth > 1 {
+            let mut coarser = MipLevel {
+                width: level.width.div_ceil(2),
+                depth: level.depth.div_ceil(2),
+                bounds: vec![],
+            };
+            for z in 0..coarser.depth {
+                for x in 0..coarser.width {
+                    let mut bounds = (f32::INFINITY, f32::NEG_INFINITY);
+                    for (cx, cz) in [(2 * x, 2 * z), (2 * x + 1, 2 * z), (2 * x, 2 * z + 1), (2 * x + 1, 2 * z + 1)] {
+                        if cx < level.width && cz < level.depth {
+                            let (min, max) = level.bounds[cz * level.width + cx];
+                            bounds = (bounds.0.min(min), bounds.1.max(max));
+                        }
+                    }
+                    coarser.bounds.push(bounds);
+                }
+            }
+            levels.push(level);
+            level = coarser;
+        }
+        levels.push(level);
+
+        Heightfield {
+            corner,
+            size_x,
+            size_z,
+            samples_x,
+            samples_z,
+            heights: Arc::new(heights),
+            normals: Arc::new(normals),
+            levels: Arc::new(levels),
+            material,
+        }
+    }
+
+    /// Heights from the brightness of a grayscale image, black being `corner.y`
+    /// and white `corner.y + height`. Image rows run along +z.
+    pub fn from_image<P: AsRef<Path>>(
+        path: P,
+        corner: Vec3,
+        size_x: f32,
+        size_z: f32,
+        height: f32,
+        material: Material,
+    ) -> io::Result<Self> {
+        let image = image::open(path).map_err(io::Error::other)?.to_luma16();
+        if image.width() < 2 || image.height() < 2 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("heightfield image must be at least 2x2, got {}x{}", image.width(), image.height()),
+            ));
+        }
+        let heights = image.pixels().map(|pixel| pixel.0[0] as f32 / 65535.0 * height).collect();
+        Ok(Heightfield::new(
+            heights,
+            image.width() as usize,
+            image.height() as usize,
+            corner,
+            size_x,
+            size_z,
+            material,
+        ))
+    }
+
+    /// Heights sampled from 2D noise, e.g. `noise::Fbm` or `noise::RidgedMulti`,
+    /// at `frequency` cycles per world unit. Noise values in [-1, 1] map to
+    /// heights from `corner.y` to `corner.y + height`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_noise(
+        noise: &impl NoiseFn<f64, 2>,
+        frequency: f32,
+        samples: usize,
+        corner: Vec3,
+        size_x: f32,
+        size_z: f32,
+        height: f32,
+        material: Material,
+    ) -> Self {
+        let mut heights = Vec::with_capacity(samples * samples);
+        for z in 0..samples {
+            for x in 0..samples {
+                let world_x = (corner.x + size_x * x as f32 / (samples - 1) as f32) * frequency;
+                let world_z = (corner.z + size_z * z as f32 / (samples - 1) as f32) * frequency;
+                let value = noise.get([world_x as f64, world_z as f64]) as f32;
+                heights.push((value * 0.5 + 0.5).clamp(0.0, 1.0) * height);
+            }
+        }
+        Heightfield::new(heights, samples, samples, corner, size_x, size_z, material)
+    }
+
+    fn cell_size(&self) -> (f32, f32) {
+        (
+            self.size_x / (self.samples_x - 1) as f32,
+            self.size_z / (self.samples_z - 1) as f32,
+        )
+    }
+
+    fn vertex(&self, x: usize, z: usize) -> Vec3 {
+        let (dx, dz) = self.cell_size();
+        Vec3::new(
+            self.corner.x + x as f32 * dx,
+            self.corner.y + self.heights[z * self.samples_x + x],
+            self.corner.z + z as f32 * dz,
+        )
+    }
+
+    /// Closest hit below the mipmap node `(level, x, z)` as t, the grid
+    /// vertices of the triangle hit and its barycentric u and v.
+    #[allow(clippy::type_complexity)]
+    fn traverse(
+        &self,
+        ray: &Ray,
+        inverse_direction: &Vec3,
+        (level, x, z): (usize, usize, usize),
+        t_min: f32,
+        t_max: f32,
+    ) -> Option<(f32, [(usize, usize); 3], f32, f32)> {
+        let mip = &self.levels[level];
+        let (min, max) = mip.bounds[z * mip.width + x];
+        let (dx, dz) = self.cell_size();
+        let span = 1 << level;
+        let node = Aabb::new(
+            Vec3::new(
+                self.corner.x + (x * span) as f32 * dx,
+                self.corner.y + min,
+                self.corner.z + (z * span) as f32 * dz,
+            ),
+            Vec3::new(
+                self.corner.x + (((x + 1) * span).min(self.samples_x - 1)) as f32 * dx,
+                self.corner.y + max,
+                self.corner.z + (((z + 1) * span).min(self.samples_z - 1)) as f32 * dz,
+            ),
+        );
+        if !node.hit(ray, inverse_direction, t_min, t_max) {
+            return None;
+        }
+
+        if level == 0 {
+            let mut closest = None;
+            let mut closest_t = t_max;
+            for triangle in [[(x, z), (x + 1, z), (x + 1, z + 1)], [(x, z), (x + 1, z + 1), (x, z + 1)]] {
+                let [a, b, c] = triangle.map(|(vx, vz)| self.vertex(vx, vz));
+                if let Some((t, u, v)) = moller_trumbore_intersection(&ray.origin, &ray.direction, &a, &b, &c)
+                    && t > t_min
+                    && t < closest_t
+                {
+                    closest_t = t;
+                    closest = Some((t, triangle, u, v));
+                }
+            }
+            return closest;
+        }
+
+        // children in the order the ray passes over them; columns don't
+        // overlap, so the first hit found is the closest
+        let child = &self.levels[level - 1];
+        let flip_x = ray.direction.x < 0.0;
+        let flip_z = ray.direction.z < 0.0;
+        let order = if ray.direction.x.abs() > ray.direction.z.abs() {
+            [(0, 0), (0, 1), (1, 0), (1, 1)]
+        } else {
+            [(0, 0), (1, 0), (0, 1), (1, 1)]
+        };
+        for (ox, oz) in order {
+            let cx = 2 * x + if flip_x { 1 - ox } else { ox };
+            let cz = 2 * z + if flip_z { 1 - oz } else { oz };
+            if cx >= child.width || cz >= child.depth {
+                continue;
+            }
+            if let Some(hit) = self.traverse(ray, inverse_direction, (level - 1, cx, cz), t_min, t_max) {
+                return Some(hit);
+            }
+        }
+        None
+    }
+}
+
+impl Hitable for Heightfield {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let inverse_direction = Vec3::new(1.0 / ray.direction.x, 1.0 / ray.direction.y, 1.0 / ray.direction.z);
+        let (t, triangle, u, v) = self.traverse(ray, &inverse_direction, (self.levels.len() - 1, 0, 0), t_min, t_max)?;
+
+        let normal_at = |(x, z): (usize, usize)| self.normals[z * self.samples_x + x];
+        let normal = normal_at(triangle[0])
+            .multiply(1.0 - u - v)
+            .add(&normal_at(triangle[1]).multiply(u))
+            .add(&normal_at(triangle[2]).multiply(v))
+            .normalize();
+        let point = ray.at(t);
+        let mut record = HitRecord::new(
+            ray,
+            t,
+            normal,
+            self.material.clone(),
+            (point.x - self.corner.x) / self.size_x,
+            (point.z - self.corner.z) / self.size_z,
+        );
+        record.tangent = Some(Vec3::new(1.0, 0.0, 0.0));
+        Some(record)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let (min, max) = self.levels.last()?.bounds[0];
+        Some(Aabb::new(
+            Vec3::new(self.corner.x, self.corner.y + min, self.corner.z),
+            Vec3::new(self.corner.x + self.size_x, self.corner.y + max, self.corner.z + self.size_z),
+        ))
+    }
+}
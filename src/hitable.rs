@@ -1,15 +1,96 @@
 use dyn_clone::DynClone;
 
-use crate::{material::Material, ray::Ray, vec3::Vec3};
+use crate::{aabb::Aabb, interval::Interval, material::Material, ray::Ray, vec3::Vec3};
 
+#[derive(Clone)]
 pub struct HitRecord {
     pub t: f32,
     pub point: Vec3,
     pub normal: Vec3,
     pub front_face: bool,
     pub material: Material,
+    pub u: f32,
+    pub v: f32,
+    /// Direction of increasing u on the surface, for shapes that provide one.
+    pub tangent: Option<Vec3>,
+    pub vertex_color: Option<Vec3>,
+}
+
+impl HitRecord {
+    /// Builds a record from the surface's outward normal. The stored normal
+    /// always faces against the ray and `front_face` tells which side was hit.
+    pub fn new(
+        ray: &Ray,
+        t: f32,
+        outward_normal: Vec3,
+        material: Material,
+        u: f32,
+        v: f32,
+    ) -> Self {
+        let front_face = ray.direction.dot(&outward_normal) < 0.0;
+        HitRecord {
+            t,
+            point: ray.at(t),
+            normal: if front_face {
+                outward_normal
+            } else {
+                outward_normal.multiply(-1.0)
+            },
+            front_face,
+            material,
+            u,
+            v,
+            tangent: None,
+            vertex_color: None,
+        }
+    }
+}
+
+/// Stretch of a ray that lies inside a closed object. The records are the
+/// surface hits where the ray enters and leaves; `None` means the span was cut
+/// off by the ray's `t_min` or `t_max` instead.
+#[derive(Clone)]
+pub struct Span {
+    pub interval: Interval,
+    pub entry: Option<HitRecord>,
+    pub exit: Option<HitRecord>,
 }
 
 pub trait Hitable: DynClone + Send + Sync {
     fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord>;
+
+    /// Every span of the ray inside the object, in order. The default walks
+    /// from hit to hit and pairs entries with exits using `front_face`, which
+    /// only makes sense for closed objects that report every crossing.
+    fn hit_intervals(&self, ray: &Ray, t_min: f32, t_max: f32) -> Vec<Span> {
+        let mut spans = vec![];
+        let mut entry: Option<HitRecord> = None;
+        let mut t = t_min;
+        while let Some(record) = self.hit(ray, t, t_max) {
+            t = record.t + 1e-4;
+            if record.front_face {
+                entry = Some(record);
+            } else {
+                let start = entry.as_ref().map_or(t_min, |entry| entry.t);
+                spans.push(Span {
+                    interval: Interval::new(start, record.t),
+                    entry: entry.take(),
+                    exit: Some(record),
+                });
+            }
+        }
+        if let Some(entry) = entry {
+            spans.push(Span {
+                interval: Interval::new(entry.t, t_max),
+                entry: Some(entry),
+                exit: None,
+            });
+        }
+        spans
+    }
+
+    /// Bounds of the object, `None` for unbounded ones.
+    fn bounding_box(&self) -> Option<Aabb> {
+        None
+    }
 }
@@ -1,7 +1,10 @@
-use crate::hitable::Hitable;
+use std::collections::HashMap;
+
+use crate::{aabb::Aabb, hitable::Hitable};
 
 pub struct HitableList {
     list: Vec<Box<dyn Hitable>>,
+    names: HashMap<String, usize>,
 }
 
 impl Clone for HitableList {
@@ -10,18 +13,32 @@ impl Clone for HitableList {
         for hitable in &self.list {
             new_list.add(dyn_clone::clone_box(hitable.as_ref()));
         }
+        new_list.names = self.names.clone();
         new_list
     }
 }
 
 impl HitableList {
     pub fn new() -> Self {
-        HitableList { list: Vec::new() }
+        HitableList {
+            list: Vec::new(),
+            names: HashMap::new(),
+        }
     }
 
     pub fn add(&mut self, hitable: Box<dyn Hitable>) {
         self.list.push(hitable);
     }
+
+    /// Adds an object that can later be looked up by name, e.g. to focus on it.
+    pub fn add_named(&mut self, name: &str, hitable: Box<dyn Hitable>) {
+        self.names.insert(name.to_string(), self.list.len());
+        self.list.push(hitable);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Hitable> {
+        self.names.get(name).map(|&index| self.list[index].as_ref())
+    }
 }
 
 impl Hitable for HitableList {
@@ -43,4 +60,10 @@ impl Hitable for HitableList {
 
         hit_record
     }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let mut boxes = self.list.iter().map(|hitable| hitable.bounding_box());
+        let first = boxes.next()??;
+        boxes.try_fold(first, |aabb, other| Some(aabb.union(&other?)))
+    }
 }
@@ -0,0 +1,143 @@
+use crate::{
+    aabb::Aabb,
+    hitable::{HitRecord, Hitable},
+    material::Material,
+    ray::Ray,
+    roots::{self, solve_quadratic},
+    sphere,
+    vec3::Vec3,
+};
+
+/// One term `coefficient * x^i * y^j * z^k` of a polynomial.
+#[derive(Debug, Clone, Copy)]
+pub struct Term {
+    pub coefficient: f64,
+    pub powers: [u32; 3],
+}
+
+impl Term {
+    pub fn new(coefficient: f64, i: u32, j: u32, k: u32) -> Self {
+        Term {
+            coefficient,
+            powers: [i, j, k],
+        }
+    }
+}
+
+/// Algebraic surface f(x, y, z) = 0 of any degree, evaluated in coordinates
+/// centred at `center` and divided by `scale`. Only the part inside a sphere of
+/// `bounding_radius` (in those coordinates) is rendered, and f > 0 counts as
+/// outside. u and v are the spherical coordinates of the hit around the centre.
+#[derive(Debug, Clone)]
+pub struct ImplicitSurface {
+    center: Vec3,
+    scale: f32,
+    bounding_radius: f32,
+    terms: Vec<Term>,
+    degree: usize,
+    material: Material,
+}
+
+impl ImplicitSurface {
+    pub fn new(center: Vec3, scale: f32, bounding_radius: f32, terms: Vec<Term>, material: Material) -> Self {
+        let degree = terms
+            .iter()
+            .map(|term| term.powers.iter().sum::<u32>() as usize)
+            .max()
+            .unwrap_or(0);
+        ImplicitSurface {
+            center,
+            scale,
+            bounding_radius,
+            terms,
+            degree,
+            material,
+        }
+    }
+
+    /// Tanglecube x⁴ - 5x² + y⁴ - 5y² + z⁴ - 5z² + 11.8 = 0.
+    pub fn tanglecube(center: Vec3, scale: f32, material: Material) -> Self {
+        let mut terms = vec![Term::new(11.8, 0, 0, 0)];
+        for axis in 0..3 {
+            let mut quartic = [0; 3];
+            let mut quadratic = [0; 3];
+            quartic[axis] = 4;
+            quadratic[axis] = 2;
+            terms.push(Term::new(1.0, quartic[0], quartic[1], quartic[2]));
+            terms.push(Term::new(-5.0, quadratic[0], quadratic[1], quadratic[2]));
+        }
+        ImplicitSurface::new(center, scale, 3.6, terms, material)
+    }
+
+    fn gradient(&self, p: [f64; 3]) -> Vec3 {
+        let mut gradient = [0.0; 3];
+        for term in &self.terms {
+            for (axis, value) in gradient.iter_mut().enumerate() {
+                if term.powers[axis] == 0 {
+                    continue;
+                }
+                let mut partial = term.coefficient * term.powers[axis] as f64;
+                for (other, power) in term.powers.iter().enumerate() {
+                    let power = if other == axis { power - 1 } else { *power };
+                    partial *= p[other].powi(power as i32);
+                }
+                *value += partial;
+            }
+        }
+        Vec3::new(gradient[0] as f32, gradient[1] as f32, gradient[2] as f32)
+    }
+}
+
+impl Hitable for ImplicitSurface {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let o = ray.origin.sub(&self.center).divide(self.scale);
+        let d = ray.direction.divide(self.scale);
+        let length = d.length() as f64;
+        let o = [o.x as f64, o.y as f64, o.z as f64];
+        let d = [d.x as f64 / length, d.y as f64 / length, d.z as f64 / length];
+
+        let f = o[0] * d[0] + o[1] * d[1] + o[2] * d[2];
+        let o_squared = o[0] * o[0] + o[1] * o[1] + o[2] * o[2];
+        let bound = self.bounding_radius as f64;
+        let (enter, exit) = solve_quadratic(1.0, 2.0 * f, o_squared - bound * bound)?;
+        let lo = enter.max(t_min as f64 * length);
+        let hi = exit.min(t_max as f64 * length);
+        if lo >= hi {
+            return None;
+        }
+
+        // substitute the ray into every term: each coordinate is linear in t,
+        // so its powers are built up once and multiplied together per term
+        let powers: Vec<Vec<Vec<f64>>> = (0..3)
+            .map(|axis| {
+                let mut axis_powers = vec![vec![1.0]];
+                for n in 1..=self.degree {
+                    axis_powers.push(roots::multiply(&axis_powers[n - 1], &[o[axis], d[axis]]));
+                }
+                axis_powers
+            })
+            .collect();
+        let mut coefficients = vec![0.0; self.degree + 1];
+        for term in &self.terms {
+            let [i, j, k] = term.powers.map(|power| power as usize);
+            let product = roots::multiply(&roots::multiply(&powers[0][i], &powers[1][j]), &powers[2][k]);
+            for (coefficient, value) in coefficients.iter_mut().zip(product) {
+                *coefficient += term.coefficient * value;
+            }
+        }
+
+        let t = *roots::roots_in_interval(&coefficients, lo, hi)
+            .iter()
+            .find(|t| **t > t_min as f64 * length)?;
+        let p: [f64; 3] = std::array::from_fn(|axis| o[axis] + d[axis] * t);
+        let normal = self.gradient(p).normalize();
+        let (u, v) = sphere::sphere_uv(&Vec3::new(p[0] as f32, p[1] as f32, p[2] as f32).normalize());
+        Some(HitRecord::new(ray, (t / length) as f32, normal, self.material.clone(), u, v))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let extent = self.bounding_radius * self.scale;
+        let extent = Vec3::new(extent, extent, extent);
+        Some(Aabb::new(self.center.sub(&extent), self.center.add(&extent)))
+    }
+}
@@ -1,4 +1,5 @@
-struct Interval {
+#[derive(Debug, Clone, Copy)]
+pub struct Interval {
     pub min: f32,
     pub max: f32,
 }
@@ -8,14 +9,6 @@ impl Interval {
         Interval { min, max }
     }
 
-    pub fn size(&self) -> f32 {
-        self.max - self.min
-    }
-
-    pub fn contains(&self, value: f32) -> bool {
-        value >= self.min && value <= self.max
-    }
-
     pub fn surrounds(&self, value: f32) -> bool {
         self.min < value && value < self.max
     }
@@ -29,4 +22,4 @@ impl Interval {
             value
         }
     }
-}
\ No newline at end of file
+}
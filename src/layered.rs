@@ -0,0 +1,180 @@
+use std::io;
+
+use crate::{
+    hitable::HitRecord,
+    material::Material,
+    microfacet::{self, TrowbridgeReitz},
+    util::random_double,
+    vec3::Vec3,
+};
+
+/// Most times light may cross the layer before it is given up as absorbed.
+const MAX_CROSSINGS: usize = 64;
+
+/// A layer over another material, like varnish over wood or dust over
+/// metal. Light is followed on a random walk through the layer: refracted
+/// by its rough top surface, absorbed and scattered inside, reflected by
+/// the base and bounced back down by the top surface from within, until it
+/// gets out again. Nothing is lost or made up between the layers, so only
+/// the materials themselves decide how much is absorbed.
+///
+/// The base must be a material that can be sampled in a shading frame (see
+/// `Material::sample_local`); `varnish` and `dust` fail with `InvalidInput`
+/// for any other.
+#[derive(Debug, Clone)]
+pub struct Layered {
+    base: Material,
+    /// Index of refraction of the layer; 1 for a layer without a surface of
+    /// its own, like dust.
+    pub ior: f32,
+    pub roughness: f32,
+    /// Fraction of each channel that gets straight down through the layer
+    /// without being absorbed, the colour of a varnish.
+    pub tint: Vec3,
+    /// Mean number of particles, like dust or pigment, that light straight
+    /// down through the layer runs into. Each sends it off in a random
+    /// direction.
+    pub density: f32,
+    /// Colour of the particles.
+    pub albedo: Vec3,
+}
+
+impl Layered {
+    /// Clear coat of `ior` over `base`.
+    pub fn varnish(base: Material, ior: f32, roughness: f32, tint: Vec3) -> io::Result<Self> {
+        check_base(&base)?;
+        Ok(Layered {
+            base,
+            ior,
+            roughness,
+            tint,
+            density: 0.0,
+            albedo: Vec3::new(1.0, 1.0, 1.0),
+        })
+    }
+
+    /// Loose layer of particles over `base`.
+    pub fn dust(base: Material, density: f32, albedo: Vec3) -> io::Result<Self> {
+        check_base(&base)?;
+        Ok(Layered {
+            base,
+            ior: 1.0,
+            roughness: 0.0,
+            tint: Vec3::new(1.0, 1.0, 1.0),
+            density,
+            albedo,
+        })
+    }
+
+    pub fn base(&self) -> &Material {
+        &self.base
+    }
+
+    /// Picks an incoming direction for light leaving toward `wo`, in the
+    /// shading frame of `hit_record` with the normal along z. Returns the
+    /// direction and the weight to multiply the light arriving from it by,
+    /// or `None` when the walk ends in absorption.
+    pub fn sample(&self, wo: &Vec3, hit_record: &HitRecord) -> Option<(Vec3, Vec3)> {
+        if wo.z <= 0.0 {
+            return None;
+        }
+        let surface = TrowbridgeReitz::from_roughness(self.roughness, 0.0);
+        let mut weight = Vec3::new(1.0, 1.0, 1.0);
+
+        // into the layer, or reflected off its top
+        let mut direction = if self.ior == 1.0 {
+            wo.multiply(-1.0)
+        } else {
+            let (direction, crossed, surface_weight) = scatter_at_surface(&surface, wo, self.ior)?;
+            weight = weight.multiply(surface_weight);
+            if !crossed {
+                return Some((direction, weight));
+            }
+            direction
+        };
+
+        // depth below the top of the layer, in units of its thickness
+        let mut depth: f32 = 0.0;
+        for _ in 0..MAX_CROSSINGS {
+            let to_boundary = if direction.z < 0.0 {
+                (1.0 - depth) / -direction.z
+            } else {
+                depth / direction.z
+            };
+            let distance = if self.density > 0.0 {
+                -(1.0 - random_double()).ln() / self.density
+            } else {
+                f32::INFINITY
+            };
+            if distance < to_boundary {
+                depth -= distance * direction.z;
+                weight = weight.multiply_vec(&transmittance(&self.tint, distance)).multiply_vec(&self.albedo);
+                direction = Vec3::random_unit();
+                continue;
+            }
+            weight = weight.multiply_vec(&transmittance(&self.tint, to_boundary));
+
+            if direction.z < 0.0 {
+                // off the base and back up
+                depth = 1.0;
+                let (reflected, base_weight) = self.base.sample_local(&direction.multiply(-1.0), hit_record)?;
+                if reflected.z <= 0.0 {
+                    return None;
+                }
+                weight = weight.multiply_vec(&base_weight);
+                direction = reflected;
+            } else {
+                // out through the top, or back down from under it
+                depth = 0.0;
+                if self.ior == 1.0 {
+                    return Some((direction, weight));
+                }
+                let flipped = Vec3::new(-direction.x, -direction.y, direction.z);
+                let (next, crossed, surface_weight) = scatter_at_surface(&surface, &flipped, 1.0 / self.ior)?;
+                weight = weight.multiply(surface_weight);
+                direction = Vec3::new(next.x, next.y, -next.z);
+                if crossed {
+                    return Some((direction, weight));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Fails unless `base` can be sampled in a shading frame, as the walk
+/// through the layer would go dark wherever it reached the base.
+fn check_base(base: &Material) -> io::Result<()> {
+    if base.samples_locally() {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "a layer's base must be diffuse, textured, metallic, a conductor, dielectric, principled, layered or a mix of these",
+        ))
+    }
+}
+
+/// Light travelling against `w`, which is above a rough boundary into a
+/// medium of relative index `eta`, either reflected back up or refracted
+/// down. Returns the new direction, whether it crossed and the sample
+/// weight.
+fn scatter_at_surface(surface: &TrowbridgeReitz, w: &Vec3, eta: f32) -> Option<(Vec3, bool, f32)> {
+    let m = surface.sample_visible_normal(w, random_double(), random_double());
+    let fresnel = microfacet::fresnel_dielectric(w.dot(&m), eta);
+    let (direction, crossed) = if random_double() < fresnel {
+        let direction = microfacet::reflect(w, &m);
+        (direction.z > 0.0).then_some((direction, false))?
+    } else {
+        let direction = w.multiply(-1.0).refract(&m, 1.0 / eta).filter(|direction| direction.z < 0.0)?;
+        (direction, true)
+    };
+    let above = Vec3::new(direction.x, direction.y, direction.z.abs());
+    Some((direction, crossed, surface.g(w, &above) / surface.g1(w)))
+}
+
+/// Share of each channel left after `distance` thicknesses through a layer
+/// that lets `tint` through one straight crossing.
+fn transmittance(tint: &Vec3, distance: f32) -> Vec3 {
+    Vec3::new(tint.x.powf(distance), tint.y.powf(distance), tint.z.powf(distance))
+}
@@ -0,0 +1,289 @@
+use std::{fs, io, path::Path};
+
+use crate::{ray::Ray, vec3::Vec3};
+
+/// One interface of a lens prescription: a spherical surface, or the aperture
+/// stop when `curvature_radius` is zero. `ior` is the medium behind the
+/// interface (towards the film), `thickness` the axial distance to the next one.
+#[derive(Debug, Clone, Copy)]
+pub struct LensElement {
+    pub curvature_radius: f32,
+    pub thickness: f32,
+    pub ior: f32,
+    pub aperture_radius: f32,
+}
+
+/// A stack of lens elements traced with real refraction instead of the thin
+/// lens model. Lens space has the film at z = 0 and the scene towards -z,
+/// which matches the camera's (u, v, w) frame.
+#[derive(Debug, Clone)]
+pub struct LensSystem {
+    elements: Vec<LensElement>,
+    exposure: f32,
+    max_stop_radius: f32,
+}
+
+impl LensSystem {
+    pub fn new(elements: Vec<LensElement>) -> Self {
+        let max_stop_radius = elements
+            .iter()
+            .filter(|element| element.curvature_radius == 0.0)
+            .map(|element| element.aperture_radius)
+            .fold(0.0, f32::max);
+        let mut lens_system = LensSystem {
+            elements,
+            exposure: 1.0,
+            max_stop_radius,
+        };
+        lens_system.update_exposure();
+        lens_system
+    }
+
+    /// Parses a prescription table as printed in lens patents: one element per
+    /// line with radius, thickness, index of refraction and aperture diameter,
+    /// all in millimetres. An IOR of 0 means air and a radius of 0 marks the stop.
+    pub fn parse(prescription: &str) -> io::Result<Self> {
+        let mut elements = vec![];
+        for (line_number, line) in prescription.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let values = line
+                .split_whitespace()
+                .map(|value| value.parse::<f32>())
+                .collect::<Result<Vec<f32>, _>>()
+                .map_err(|err| invalid_data(format!("line {}: {}", line_number + 1, err)))?;
+            if values.len() != 4 {
+                return Err(invalid_data(format!(
+                    "line {}: expected 4 columns, found {}",
+                    line_number + 1,
+                    values.len()
+                )));
+            }
+            elements.push(LensElement {
+                curvature_radius: values[0] * 0.001,
+                thickness: values[1] * 0.001,
+                ior: if values[2] == 0.0 { 1.0 } else { values[2] },
+                aperture_radius: values[3] * 0.001 / 2.0,
+            });
+        }
+        if elements.is_empty() {
+            return Err(invalid_data("lens prescription has no elements".to_string()));
+        }
+        Ok(LensSystem::new(elements))
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        LensSystem::parse(&fs::read_to_string(path)?)
+    }
+
+    /// Opens or closes the aperture stop, in millimetres. The stop can't open
+    /// wider than the prescription allows, so larger diameters are clamped.
+    pub fn set_aperture_diameter(&mut self, diameter: f32) {
+        let mut radius = diameter * 0.001 / 2.0;
+        if radius > self.max_stop_radius {
+            eprintln!(
+                "aperture diameter {diameter}mm is wider than the {}mm stop, clamping",
+                self.max_stop_radius * 2000.0
+            );
+            radius = self.max_stop_radius;
+        }
+        for element in self.elements.iter_mut() {
+            if element.curvature_radius == 0.0 {
+                element.aperture_radius = radius;
+            }
+        }
+        self.update_exposure();
+    }
+
+    pub fn rear_z(&self) -> f32 {
+        -self.elements.last().unwrap().thickness
+    }
+
+    pub fn front_z(&self) -> f32 {
+        -self.elements.iter().map(|element| element.thickness).sum::<f32>()
+    }
+
+    pub fn rear_aperture(&self) -> f32 {
+        self.elements.last().unwrap().aperture_radius
+    }
+
+    /// Scale applied to rays that make it through the lens, so that the
+    /// centre of the film is exposed as with the thin lens camera and only
+    /// off-axis vignetting darkens the image.
+    pub fn exposure(&self) -> f32 {
+        self.exposure
+    }
+
+    /// Moves the film by adjusting the rear element's thickness so that the
+    /// plane at `focus_distance` in front of the film is sharp. Uses the
+    /// thick lens approximation, so focus breathing follows from the stack.
+    pub fn focus(&mut self, focus_distance: f32) {
+        let Some((pz, fz)) = self.thick_lens_approximation() else {
+            return;
+        };
+        let f = fz[0] - pz[0];
+        let z = -focus_distance;
+        let c = (pz[1] - z - pz[0]) * (pz[1] - z - 4.0 * f - pz[0]);
+        if c <= 0.0 {
+            // closer than the minimum focus distance of this lens
+            return;
+        }
+        let delta = 0.5 * (pz[1] - z + pz[0] - c.sqrt());
+        self.elements.last_mut().unwrap().thickness += delta;
+        self.update_exposure();
+    }
+
+    /// Traces a lens space ray leaving the film through every element.
+    /// Returns `None` when the ray is blocked by an aperture or totally reflected.
+    pub fn trace_from_film(&self, ray: &Ray) -> Option<Ray> {
+        let mut element_z = 0.0;
+        let mut lens_ray = Ray::new(ray.origin, ray.direction);
+        for i in (0..self.elements.len()).rev() {
+            let element = &self.elements[i];
+            element_z -= element.thickness;
+            let next_ior = if i > 0 { self.elements[i - 1].ior } else { 1.0 };
+            lens_ray = self.trace_element(element, element_z, &lens_ray, element.ior / next_ior)?;
+        }
+        Some(lens_ray)
+    }
+
+    /// Traces a lens space ray entering the front element towards the film.
+    pub fn trace_from_scene(&self, ray: &Ray) -> Option<Ray> {
+        let mut element_z = self.front_z();
+        let mut lens_ray = Ray::new(ray.origin, ray.direction);
+        for i in 0..self.elements.len() {
+            let element = &self.elements[i];
+            let previous_ior = if i > 0 { self.elements[i - 1].ior } else { 1.0 };
+            lens_ray = self.trace_element(element, element_z, &lens_ray, previous_ior / element.ior)?;
+            element_z += element.thickness;
+        }
+        Some(lens_ray)
+    }
+
+    fn trace_element(
+        &self,
+        element: &LensElement,
+        element_z: f32,
+        ray: &Ray,
+        eta_ratio: f32,
+    ) -> Option<Ray> {
+        let is_stop = element.curvature_radius == 0.0;
+        let (t, normal) = if is_stop {
+            if ray.direction.z == 0.0 {
+                return None;
+            }
+            ((element_z - ray.origin.z) / ray.direction.z, Vec3::new(0.0, 0.0, 0.0))
+        } else {
+            let z_center = element_z + element.curvature_radius;
+            intersect_spherical_element(element.curvature_radius, z_center, ray)?
+        };
+        if t < 0.0 {
+            return None;
+        }
+
+        let hit_point = ray.at(t);
+        let r2 = hit_point.x * hit_point.x + hit_point.y * hit_point.y;
+        if r2 > element.aperture_radius * element.aperture_radius {
+            return None;
+        }
+        if is_stop {
+            return Some(Ray::new(hit_point, ray.direction));
+        }
+        let refracted = ray.direction.normalize().refract(&normal, eta_ratio)?;
+        Some(Ray::new(hit_point, refracted))
+    }
+
+    /// Principal planes and focal points of the stack, found by tracing rays
+    /// parallel to the axis from either side.
+    fn thick_lens_approximation(&self) -> Option<([f32; 2], [f32; 2])> {
+        let x = 0.05
+            * self
+                .elements
+                .iter()
+                .map(|element| element.aperture_radius)
+                .fold(f32::INFINITY, f32::min);
+
+        let scene_ray = Ray::new(Vec3::new(x, 0.0, self.front_z() - 1.0), Vec3::new(0.0, 0.0, 1.0));
+        let film_ray = self.trace_from_scene(&scene_ray)?;
+        let (pz0, fz0) = cardinal_points(&scene_ray, &film_ray);
+
+        let film_ray = Ray::new(Vec3::new(x, 0.0, self.rear_z() + 1.0), Vec3::new(0.0, 0.0, -1.0));
+        let scene_ray = self.trace_from_film(&film_ray)?;
+        let (pz1, fz1) = cardinal_points(&film_ray, &scene_ray);
+
+        Some(([pz0, pz1], [fz0, fz1]))
+    }
+
+    fn update_exposure(&mut self) {
+        // fraction of the rear element that the film centre sees through the stack
+        let grid = 32;
+        let rear_z = self.rear_z();
+        let rear_aperture = self.rear_aperture();
+        let mut total = 0;
+        let mut passed = 0;
+        for a in 0..grid {
+            for b in 0..grid {
+                let x = (a as f32 + 0.5) / grid as f32 * 2.0 - 1.0;
+                let y = (b as f32 + 0.5) / grid as f32 * 2.0 - 1.0;
+                if x * x + y * y > 1.0 {
+                    continue;
+                }
+                total += 1;
+                let target = Vec3::new(x * rear_aperture, y * rear_aperture, rear_z);
+                if self.trace_from_film(&Ray::new(Vec3::new(0.0, 0.0, 0.0), target)).is_some() {
+                    passed += 1;
+                }
+            }
+        }
+        self.exposure = if passed > 0 {
+            total as f32 / passed as f32
+        } else {
+            1.0
+        };
+    }
+}
+
+fn intersect_spherical_element(radius: f32, z_center: f32, ray: &Ray) -> Option<(f32, Vec3)> {
+    let oc = ray.origin.sub(&Vec3::new(0.0, 0.0, z_center));
+    let a = ray.direction.length_squared();
+    let b = 2.0 * ray.direction.dot(&oc);
+    let c = oc.length_squared() - radius * radius;
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    // numerically stable form, the radii are tiny compared to the ray origin offsets
+    let q = if b < 0.0 {
+        -0.5 * (b - discriminant.sqrt())
+    } else {
+        -0.5 * (b + discriminant.sqrt())
+    };
+    let (t0, t1) = (q / a, c / q);
+    let use_closer_t = (ray.direction.z > 0.0) ^ (radius < 0.0);
+    let t = if use_closer_t { t0.min(t1) } else { t0.max(t1) };
+    if t < 0.0 {
+        return None;
+    }
+
+    let normal = oc.add(&ray.direction.multiply(t)).normalize();
+    let normal = if normal.dot(&ray.direction) > 0.0 {
+        normal.multiply(-1.0)
+    } else {
+        normal
+    };
+    Some((t, normal))
+}
+
+fn cardinal_points(ray_in: &Ray, ray_out: &Ray) -> (f32, f32) {
+    let tf = -ray_out.origin.x / ray_out.direction.x;
+    let fz = ray_out.at(tf).z;
+    let tp = (ray_in.origin.x - ray_out.origin.x) / ray_out.direction.x;
+    let pz = ray_out.at(tp).z;
+    (pz, fz)
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -1,24 +1,73 @@
-use std::{
-    fs::File,
-    io::BufWriter,
-    sync::{Arc, Mutex},
-};
+use std::f32::consts::PI;
 
-use image::{DynamicImage, ImageEncoder, codecs::png::PngEncoder};
+use material::Material;
 use vec3::Vec3;
 
+mod aa_box;
+mod aabb;
+mod animation;
+mod bezier;
+mod bvh;
 mod camera;
+mod conductor;
+mod csg;
+mod curve;
+mod dielectric;
+mod disk;
+mod filter;
+mod frame;
+mod gltf;
+mod hair;
+mod heightfield;
 mod hitable;
 mod hitable_list;
+mod implicit;
+mod interval;
+mod layered;
+mod lens;
 mod material;
+mod mesh;
+mod microfacet;
+mod output;
+mod plane;
+mod ply;
+mod principled;
+mod quad;
+mod quadric;
 mod ray;
+mod roots;
 mod scene1;
+mod scene10;
+mod scene11;
+mod scene12;
+mod scene13;
+mod scene14;
+mod scene15;
+mod scene16;
+mod scene17;
+mod scene18;
+mod scene19;
+mod scene20;
+mod scene21;
+mod scene22;
 mod scene2;
 mod scene3;
 mod scene4;
 mod scene5;
 mod scene6;
+mod scene7;
+mod scene8;
+mod scene9;
+mod sdf;
+mod spectrum;
 mod sphere;
+mod stl;
+mod subdivision;
+mod subsurface;
+mod texture;
+mod thin_film;
+mod torus;
+mod transform;
 mod triangle;
 mod util;
 mod vec3;
@@ -27,50 +76,202 @@ fn main() {
     let (world, flen) = scene1::create_scene();
     let camera = camera::Camera::new(0.0, 1.0, 30.0, Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
     let image = camera.render(&world, Vec3::new(0.5, 0.5, 0.7));
-    encode_png(image.clone(), "scene1.png");
+    output::encode_png(&image.lock().unwrap(), "scene1.png").unwrap();
 
     let (world, flen) = scene2::create_scene();
     let camera = camera::Camera::new(6.0, 1.1, 40.0, Vec3::new(-0.9, 0.0, -0.3), Vec3::new(0.0, 0.0, -1.7), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
     let image = camera.render(&world, Vec3::new(0.5, 0.5, 0.7));
-    encode_png(image.clone(), "scene2.png");
+    output::encode_png(&image.lock().unwrap(), "scene2.png").unwrap();
+
+    let (world, _) = scene2::create_scene();
+    let mut lens_system = lens::LensSystem::load("lenses/dgauss.50mm.dat").unwrap();
+    lens_system.set_aperture_diameter(8.5);
+    let camera = camera::Camera::new(0.0, 1.1, 40.0, Vec3::new(-0.9, 0.0, -0.3), Vec3::new(0.0, 0.0, -1.7), Vec3::new(0.0, 1.0, 0.0), 1000, 2000)
+        .with_lens_system(lens_system, 43.3)
+        .autofocus(&world, camera::FocusTarget::Object("lantern".to_string()));
+    let image = camera.render(&world, Vec3::new(0.5, 0.5, 0.7));
+    output::encode_png(&image.lock().unwrap(), "scene2_lens.png").unwrap();
+
+    let (world, _) = scene2::create_scene();
+    let camera = camera::Camera::new(6.0, 1.1, 40.0, Vec3::new(-0.9, 0.0, -0.3), Vec3::new(0.0, 0.0, -1.7), Vec3::new(0.0, 1.0, 0.0), 1000, 2000)
+        .with_sensor_shift(0.0, 0.2)
+        .with_tilt(-20.0, 0.0)
+        .with_distortion(camera::Distortion::new(-0.3, 0.05, 0.0, 0.0, 0.0))
+        .autofocus(&world, camera::FocusTarget::Pixel(500, 900))
+        .with_filter(filter::Filter::new(filter::FilterKind::MitchellNetravali { b: 1.0 / 3.0, c: 1.0 / 3.0 }, 2.0));
+    let image = camera.render(&world, Vec3::new(0.5, 0.5, 0.7));
+    output::encode_png(&image.lock().unwrap(), "scene2_tilt_shift.png").unwrap();
 
     let (world, flen) = scene3::create_scene();
     let camera = camera::Camera::new(0.0, 1.0, 50.0, Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
     let image = camera.render(&world, Vec3::new(0.0, 0.0, 0.0));
-    encode_png(image.clone(), "scene3.png");
+    output::encode_png(&image.lock().unwrap(), "scene3.png").unwrap();
 
     let (world, flen) = scene4::create_scene();
     let camera = camera::Camera::new(0.0, 1.0, 30.0, Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 5000);
     let image = camera.render(&world, Vec3::new(0.0, 0.0, 0.0));
-    encode_png(image.clone(), "scene4.png");
+    output::encode_png(&image.lock().unwrap(), "scene4.png").unwrap();
 
     let (world, flen) = scene5::create_scene();
     let camera = camera::Camera::new(0.0, 1.0, 30.0, Vec3::new(-1.5, 0.3, 0.0), Vec3::new(0.0, 0.0, -2.0), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
     let image = camera.render(&world, Vec3::new(0.5, 0.5, 0.7));
-    encode_png(image.clone(), "scene5.png");
+    output::encode_png(&image.lock().unwrap(), "scene5.png").unwrap();
 
     let (world, flen) = scene5::create_scene();
     let camera = camera::Camera::new(5.0, 2.0, 30.0, Vec3::new(-1.5, 0.3, 0.0), Vec3::new(0.0, 0.0, -2.0), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
     let image = camera.render(&world, Vec3::new(0.5, 0.5, 0.7));
-    encode_png(image.clone(), "scene5_blur.png");
+    output::encode_png(&image.lock().unwrap(), "scene5_blur.png").unwrap();
 
     let (world, flen) = scene6::create_scene();
     let camera = camera::Camera::new(15.0, 1.28, 23.0, Vec3::new(0.7,0.1,-0.3), Vec3::new(0.0, 0.0, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 5000);
     let image = camera.render(&world, Vec3::new(0.0, 0.0, 0.0));
-    encode_png(image.clone(), "scene6.png");
-}
+    output::encode_png(&image.lock().unwrap(), "scene6.png").unwrap();
+
+    let (world, _) = scene7::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.2, 0.0), Vec3::new(0.0, 0.0, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000)
+        .with_filter(filter::Filter::new(filter::FilterKind::Tent, 1.0));
+    let image = camera.render(&world, Vec3::new(0.1, 0.1, 0.15));
+    output::encode_png(&image.lock().unwrap(), "scene7.png").unwrap();
+
+    let (world, _) = scene8::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.2, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000)
+        .with_filter(filter::Filter::new(filter::FilterKind::Gaussian { sigma: 0.5 }, 1.5));
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene8.png").unwrap();
+
+    let (world, _) = scene9::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.4, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000)
+        .with_filter(filter::Filter::new(filter::FilterKind::Lanczos { tau: 2.0 }, 2.0));
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene9.png").unwrap();
+
+    let (world, _) = scene10::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.4, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000)
+        .with_filter(filter::Filter::new(filter::FilterKind::BlackmanHarris, 1.5));
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene10.png").unwrap();
+
+    let (world, _) = scene11::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 25.0, Vec3::new(0.0, 0.5, 1.0), Vec3::new(0.0, -0.1, -3.0), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
+    let image = camera.render(&world, Vec3::new(0.5, 0.6, 0.8));
+    output::encode_png(&image.lock().unwrap(), "scene11.png").unwrap();
+
+    let (world, _) = scene12::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.4, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene12.png").unwrap();
+
+    let (world, _) = scene13::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.4, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene13.png").unwrap();
+
+    let (world, _) = scene14::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.4, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene14.png").unwrap();
+
+    let (world, _) = scene15::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.4, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene15.png").unwrap();
+
+    let (world, _) = scene16::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.4, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene16.png").unwrap();
+
+    let (world, _) = scene17::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.4, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene17.png").unwrap();
+
+    let (world, _) = scene18::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.4, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene18.png").unwrap();
+
+    let (world, _) = scene19::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.4, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene19.png").unwrap();
+
+    let (world, _) = scene20::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.4, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene20.png").unwrap();
+
+    let (world, _) = scene21::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.4, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000);
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene21.png").unwrap();
+
+    let (world, _) = scene22::create_scene();
+    let camera = camera::Camera::new(0.0, 1.5, 30.0, Vec3::new(0.0, 0.4, 0.0), Vec3::new(0.0, -0.1, -1.5), Vec3::new(0.0, 1.0, 0.0), 1000, 2000)
+        .with_spectral();
+    let image = camera.render(&world, Vec3::new(0.3, 0.3, 0.4));
+    output::encode_png(&image.lock().unwrap(), "scene22.png").unwrap();
+
+    let scene = gltf::GltfScene::load("models/gallery.gltf").unwrap();
+    for index in 0..scene.camera_count() {
+        let camera = scene.camera(index, 1000, 2000).unwrap();
+        let image = camera.render(&scene.world, Vec3::new(0.2, 0.2, 0.25));
+        output::encode_png(&image.lock().unwrap(), &format!("gallery_{}.png", index)).unwrap();
+    }
+
+    // turntable around the scene5 spiral
+    let mut path = animation::CameraPath::new(animation::Interpolation::CatmullRom);
+    for (i, angle) in [0.0f32, 90.0, 180.0, 270.0, 360.0].iter().enumerate() {
+        let look_from = Vec3::new(angle.to_radians().sin() * 2.0, 0.3, angle.to_radians().cos() * 2.0 - 2.0);
+        path.add(animation::Keyframe::new(i as f32, look_from, Vec3::new(0.0, 0.0, -2.0), 30.0, 2.0));
+    }
+    animation::render_sequence(
+        &path,
+        12.0,
+        "scene5_turntable",
+        |keyframe| camera::Camera::new(0.0, keyframe.focus_distance, keyframe.fov, keyframe.look_from, keyframe.look_at, Vec3::new(0.0, 1.0, 0.0), 400, 200),
+        |_| scene5::create_scene().0,
+        Vec3::new(0.5, 0.5, 0.7),
+        Some(animation::AnimationFormat::Gif),
+    )
+    .unwrap();
+
+    // crane shot over the scene2 lantern, slowing into the last keyframe
+    let mut path = animation::CameraPath::new(animation::Interpolation::Bezier);
+    path.add(animation::Keyframe::new(0.0, Vec3::new(-1.5, -0.2, 0.5), Vec3::new(0.0, 0.0, -1.7), 40.0, 2.5));
+    path.add(animation::Keyframe::new(1.0, Vec3::new(-0.9, 0.4, -0.1), Vec3::new(0.0, 0.0, -1.7), 40.0, 1.9));
+    path.add(animation::Keyframe::new(3.0, Vec3::new(0.2, 0.8, -0.6), Vec3::new(0.0, -0.2, -1.7), 30.0, 1.4));
+    animation::render_sequence(
+        &path,
+        12.0,
+        "scene2_crane",
+        |keyframe| camera::Camera::new(0.0, keyframe.focus_distance, keyframe.fov, keyframe.look_from, keyframe.look_at, Vec3::new(0.0, 1.0, 0.0), 400, 200),
+        |_| scene2::create_scene().0,
+        Vec3::new(0.5, 0.5, 0.7),
+        Some(animation::AnimationFormat::Apng),
+    )
+    .unwrap();
 
-fn encode_png(img: Arc<Mutex<DynamicImage>>, filename: &str) {
-    let img = img.lock().unwrap();
-    let file = File::create(filename).unwrap();
-    let ref mut buff = BufWriter::new(file);
-    let encoder = PngEncoder::new(buff);
-    encoder
-        .write_image(
-            img.as_rgb8().unwrap(),
-            img.width(),
-            img.height(),
-            image::ExtendedColorType::Rgb8,
-        )
-        .unwrap();
+    // straight zoom into scene1 while a ball bounces in front, frames only
+    let mut path = animation::CameraPath::new(animation::Interpolation::Linear);
+    path.add(animation::Keyframe::new(0.0, Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0), 40.0, 1.0));
+    path.add(animation::Keyframe::new(1.0, Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0), 15.0, 1.0));
+    animation::render_sequence(
+        &path,
+        12.0,
+        "scene1_zoom",
+        |keyframe| camera::Camera::new(0.0, keyframe.focus_distance, keyframe.fov, keyframe.look_from, keyframe.look_at, Vec3::new(0.0, 1.0, 0.0), 400, 200),
+        |time| {
+            let mut world = scene1::create_scene().0;
+            world.add(Box::new(sphere::Sphere::new(
+                Vec3::new(0.0, -0.4 + 0.3 * (time * 2.0 * PI).sin().abs(), -0.9),
+                0.1,
+                Material::Diffuse(Vec3::new(0.2, 0.4, 0.8)),
+            )));
+            world
+        },
+        Vec3::new(0.5, 0.5, 0.7),
+        None,
+    )
+    .unwrap();
 }
@@ -1,17 +1,153 @@
-use crate::vec3::Vec3;
+use crate::{
+    conductor::Conductor,
+    dielectric::Dielectric,
+    hair::Hair,
+    hitable::HitRecord,
+    layered::Layered,
+    microfacet,
+    principled::Principled,
+    subsurface::Subsurface,
+    texture::{NormalMap, ScalarTexture, Texture},
+    util::random_double,
+    vec3::Vec3,
+};
 
 #[derive(Debug, Clone)]
 pub enum Material {
     Diffuse(Vec3),
+    /// Diffuse with the colour looked up from a texture.
+    Textured(Texture),
     Light(Vec3),
+    /// Light of a black body glowing at `temperature` kelvin, as bright as a
+    /// `Light` of luminance `strength`.
+    Blackbody { temperature: f32, strength: f32 },
     Metallic(Metallic),
+    /// Rough metal with measured optical constants.
+    Conductor(Conductor),
+    /// Clear glass or water, optionally frosted or coated with a thin film.
+    Dielectric(Dielectric),
+    /// Disney-style material with all the usual artist parameters.
+    Principled(Box<Principled>),
     CheckerBoard(Box<Material>, Box<Material>, f32),
+    /// `first` or `second` at random, taking `second` as often as `weight`
+    /// says.
+    Mix {
+        first: Box<Material>,
+        second: Box<Material>,
+        weight: MixWeight,
+    },
+    /// A varnish, dust or similar layer over another material.
+    Layered(Box<Layered>),
+    /// Translucent closed objects, lit from inside by light that scatters
+    /// through them.
+    Subsurface(Subsurface),
+    /// Hair fibres, for `Curves`.
+    Hair(Hair),
+    /// `material` with its shading normal taken from a normal map, the tilt
+    /// away from the surface normal scaled by `strength`.
+    NormalMapped {
+        material: Box<Material>,
+        map: NormalMap,
+        strength: f32,
+    },
+    /// `material` with its shading normal tilted by the slope of `bump`,
+    /// which raises the surface by `scale` times its value.
+    BumpMapped {
+        material: Box<Material>,
+        bump: ScalarTexture,
+        scale: f32,
+    },
     Crazy(),
 }
 
+/// Simple metal, rendered as a GGX `Conductor` that reflects `albedo` head
+/// on with `fuzz` as its roughness. `diffuse` metals are an even mix of that
+/// and a diffuse bounce of the same colour.
 #[derive(Debug, Clone, Copy)]
 pub struct Metallic {
     pub albedo: Vec3,
     pub fuzz: f32,
     pub diffuse: bool
 }
+
+impl Metallic {
+    pub fn to_material(self) -> Material {
+        let conductor = Material::Conductor(Conductor::from_reflectance(self.albedo, self.fuzz, 0.0));
+        if self.diffuse {
+            Material::Mix {
+                first: Box::new(Material::Diffuse(self.albedo)),
+                second: Box::new(conductor),
+                weight: MixWeight::Constant(0.5),
+            }
+        } else {
+            conductor
+        }
+    }
+}
+
+/// How much of its second material a `Material::Mix` takes, from 0 to 1.
+#[derive(Debug, Clone)]
+pub enum MixWeight {
+    Constant(f32),
+    /// Value of a texture at the hit, clamped to [0, 1].
+    Texture(ScalarTexture),
+    /// Fresnel reflectance of a dielectric with this index of refraction, so
+    /// the second material shows more toward grazing angles.
+    Fresnel(f32),
+}
+
+impl MixWeight {
+    /// Weight at the hit, seen at `cos_theta` to the normal.
+    pub fn value(&self, hit_record: &HitRecord, cos_theta: f32) -> f32 {
+        match self {
+            MixWeight::Constant(weight) => weight.clamp(0.0, 1.0),
+            MixWeight::Texture(texture) => texture
+                .value(&hit_record.point, (hit_record.u, hit_record.v))
+                .clamp(0.0, 1.0),
+            MixWeight::Fresnel(ior) => microfacet::fresnel_dielectric(cos_theta, *ior),
+        }
+    }
+}
+
+impl Material {
+    /// Picks an incoming direction for light leaving toward `wo`, in the
+    /// shading frame of `hit_record` with the normal along z, for materials
+    /// that can be sampled without tracing further rays: diffuse, conductor,
+    /// dielectric, principled, layered and mixes of these. Returns the direction and the
+    /// weight to multiply the light arriving from it by, or `None` when the
+    /// light is absorbed or the material can't be sampled this way.
+    pub fn sample_local(&self, wo: &Vec3, hit_record: &HitRecord) -> Option<(Vec3, Vec3)> {
+        match self {
+            Material::Diffuse(color) => Some((Vec3::random_cosine_direction(), *color)),
+            Material::Textured(texture) => Some((Vec3::random_cosine_direction(), texture.value(hit_record))),
+            Material::Metallic(metal) => metal.to_material().sample_local(wo, hit_record),
+            Material::Conductor(conductor) => conductor.sample(wo, hit_record),
+            Material::Dielectric(dielectric) => dielectric.sample(wo, hit_record),
+            Material::Principled(principled) => principled.sample(wo, hit_record),
+            Material::Layered(layered) => layered.sample(wo, hit_record),
+            Material::Mix { first, second, weight } => {
+                if random_double() < weight.value(hit_record, wo.z) {
+                    second.sample_local(wo, hit_record)
+                } else {
+                    first.sample_local(wo, hit_record)
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether `sample_local` can sample the material.
+    pub fn samples_locally(&self) -> bool {
+        match self {
+            Material::Diffuse(_)
+            | Material::Textured(_)
+            | Material::Metallic(_)
+            | Material::Conductor(_)
+            | Material::Dielectric(_)
+            | Material::Principled(_) => true,
+            Material::Layered(layered) => layered.base().samples_locally(),
+            Material::Mix { first, second, .. } => first.samples_locally() && second.samples_locally(),
+            _ => false,
+        }
+    }
+}
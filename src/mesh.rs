@@ -0,0 +1,298 @@
+use std::{io, sync::Arc};
+
+use crate::{
+    aabb::Aabb,
+    bvh::Bvh,
+    hitable::{HitRecord, Hitable},
+    material::Material,
+    ray::Ray,
+    subdivision,
+    texture::ScalarTexture,
+    transform::Transform,
+    triangle::moller_trumbore_intersection,
+    vec3::Vec3,
+};
+
+/// Indexed triangle mesh. Vertex attributes live in shared buffers, so clones
+/// are cheap, and the whole mesh uses a single material. Normals, UVs,
+/// tangents and colours are optional; when present there is one per position.
+#[derive(Debug, Clone)]
+pub struct TriangleMesh {
+    positions: Arc<Vec<Vec3>>,
+    normals: Arc<Vec<Vec3>>,
+    uvs: Arc<Vec<(f32, f32)>>,
+    tangents: Arc<Vec<Vec3>>,
+    colors: Arc<Vec<Vec3>>,
+    indices: Arc<Vec<[usize; 3]>>,
+    bvh: Arc<Bvh>,
+    material: Material,
+    alpha: Option<ScalarTexture>,
+}
+
+impl TriangleMesh {
+    pub fn new(positions: Vec<Vec3>, indices: Vec<[usize; 3]>, material: Material) -> Self {
+        let bounds: Vec<Aabb> = indices
+            .iter()
+            .map(|triangle| Aabb::from_points(&triangle.map(|index| positions[index])))
+            .collect();
+        TriangleMesh {
+            positions: Arc::new(positions),
+            normals: Arc::new(vec![]),
+            uvs: Arc::new(vec![]),
+            tangents: Arc::new(vec![]),
+            colors: Arc::new(vec![]),
+            indices: Arc::new(indices),
+            bvh: Arc::new(Bvh::new(&bounds)),
+            material,
+            alpha: None,
+        }
+    }
+
+    /// Panics unless there is one normal per position, as for the other
+    /// vertex attributes.
+    pub fn with_normals(mut self, normals: Vec<Vec3>) -> Self {
+        self.check_attribute("normals", normals.len());
+        self.normals = Arc::new(normals);
+        self
+    }
+
+    /// Vertex normals averaged from the faces around each vertex, weighted by
+    /// face area.
+    pub fn with_smooth_normals(self) -> Self {
+        let mut normals = vec![Vec3::new(0.0, 0.0, 0.0); self.positions.len()];
+        for [a, b, c] in self.indices.iter().copied() {
+            let face_normal = self.positions[b]
+                .sub(&self.positions[a])
+                .cross(&self.positions[c].sub(&self.positions[a]));
+            for index in [a, b, c] {
+                normals[index] = normals[index].add(&face_normal);
+            }
+        }
+        let normals = normals.iter().map(|normal| normal.normalize()).collect();
+        self.with_normals(normals)
+    }
+
+    pub fn with_uvs(mut self, uvs: Vec<(f32, f32)>) -> Self {
+        self.check_attribute("UVs", uvs.len());
+        self.uvs = Arc::new(uvs);
+        self
+    }
+
+    pub fn with_tangents(mut self, tangents: Vec<Vec3>) -> Self {
+        self.check_attribute("tangents", tangents.len());
+        self.tangents = Arc::new(tangents);
+        self
+    }
+
+    /// Per-vertex colours, available to materials through `Texture::VertexColor`.
+    pub fn with_colors(mut self, colors: Vec<Vec3>) -> Self {
+        self.check_attribute("colours", colors.len());
+        self.colors = Arc::new(colors);
+        self
+    }
+
+    fn check_attribute(&self, name: &str, len: usize) {
+        assert!(
+            len == self.positions.len(),
+            "mesh has {} positions but {} {}",
+            self.positions.len(),
+            len,
+            name
+        );
+    }
+
+    /// Opacity looked up by UV, for cut-outs like foliage cards. Rays pass
+    /// through where it is 0.
+    pub fn with_alpha(mut self, alpha: ScalarTexture) -> Self {
+        self.alpha = Some(alpha);
+        self
+    }
+
+    /// Copy of the mesh with all vertex data moved by `transform`.
+    pub fn transformed(&self, transform: &Transform) -> Self {
+        let positions = self.positions.iter().map(|p| transform.transform_point(p)).collect();
+        // mirroring transforms would turn the faces inside out
+        let indices = if transform.determinant() < 0.0 {
+            self.indices.iter().map(|&[a, b, c]| [a, c, b]).collect()
+        } else {
+            self.indices.to_vec()
+        };
+        TriangleMesh {
+            normals: Arc::new(self.normals.iter().map(|n| transform.transform_normal(n)).collect()),
+            uvs: self.uvs.clone(),
+            tangents: Arc::new(
+                self.tangents
+                    .iter()
+                    .map(|t| transform.transform_vector(t).normalize())
+                    .collect(),
+            ),
+            colors: self.colors.clone(),
+            alpha: self.alpha.clone(),
+            ..TriangleMesh::new(positions, indices, self.material.clone())
+        }
+    }
+
+    /// Loop subdivision surface of the mesh after `levels` steps, with the
+    /// vertices moved onto the limit surface and smooth normals. Colours and
+    /// UVs are interpolated along the split edges; normals and tangents of
+    /// the control mesh are dropped. Vertices duplicated along UV seams
+    /// subdivide as separate boundaries.
+    pub fn subdivided(&self, levels: usize) -> Self {
+        let mut positions = self.positions.to_vec();
+        let mut indices = self.indices.to_vec();
+        let mut uvs = self.uvs.to_vec();
+        let mut colors = self.colors.to_vec();
+        for _ in 0..levels {
+            let (new_positions, new_indices, parents) = subdivision::loop_step(&positions, &indices);
+            uvs = with_midpoints(&uvs, &parents, |a, b| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0));
+            colors = with_midpoints(&colors, &parents, |a, b| a.add(b).multiply(0.5));
+            positions = new_positions;
+            indices = new_indices;
+        }
+        let positions = subdivision::limit_positions(&positions, &indices);
+        TriangleMesh {
+            uvs: Arc::new(uvs),
+            colors: Arc::new(colors),
+            alpha: self.alpha.clone(),
+            ..TriangleMesh::new(positions, indices, self.material.clone())
+        }
+        .with_smooth_normals()
+    }
+
+    /// Copy of the mesh with each vertex pushed along its normal by `scale`
+    /// times the texture, after splitting edges longer than `max_edge` so
+    /// the detail has vertices to land on. Meshes without normals get smooth
+    /// ones first, and normals are recomputed from the displaced surface.
+    /// Vertices duplicated to give hard edges move apart and open cracks.
+    /// Fails with `InvalidInput` unless `max_edge` is positive.
+    pub fn displaced(&self, texture: &ScalarTexture, scale: f32, max_edge: f32) -> io::Result<Self> {
+        if max_edge <= 0.0 || max_edge.is_nan() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("maximum edge length must be positive, got {}", max_edge),
+            ));
+        }
+        let base = if self.normals.is_empty() {
+            self.clone().with_smooth_normals()
+        } else {
+            self.clone()
+        };
+        let (mut positions, indices, parents) = subdivision::split_long_edges(&base.positions, &base.indices, max_edge);
+        let normals = with_midpoints(&base.normals, &parents, |a, b| a.add(b).normalize());
+        let uvs = with_midpoints(&base.uvs, &parents, |a, b| ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0));
+        let colors = with_midpoints(&base.colors, &parents, |a, b| a.add(b).multiply(0.5));
+        for (vertex, position) in positions.iter_mut().enumerate() {
+            let uv = uvs.get(vertex).copied().unwrap_or((0.0, 0.0));
+            let height = texture.value(position, uv);
+            *position = position.add(&normals[vertex].multiply(scale * height));
+        }
+        Ok(TriangleMesh {
+            uvs: Arc::new(uvs),
+            colors: Arc::new(colors),
+            alpha: self.alpha.clone(),
+            ..TriangleMesh::new(positions, indices, self.material.clone())
+        }
+        .with_smooth_normals())
+    }
+
+    /// Texture coordinates at barycentric (u, v) in a face, or the barycentric
+    /// weights themselves when the mesh has no UVs.
+    fn texture_uv(&self, vertices: [usize; 3], u: f32, v: f32) -> (f32, f32) {
+        if self.uvs.is_empty() {
+            return (u, v);
+        }
+        let w = 1.0 - u - v;
+        let [uv_a, uv_b, uv_c] = vertices.map(|index| self.uvs[index]);
+        (
+            w * uv_a.0 + u * uv_b.0 + v * uv_c.0,
+            w * uv_a.1 + u * uv_b.1 + v * uv_c.1,
+        )
+    }
+
+    /// Tangent of a face from its UV parametrization.
+    fn face_tangent(&self, [a, b, c]: [usize; 3]) -> Option<Vec3> {
+        let (uv_a, uv_b, uv_c) = (self.uvs[a], self.uvs[b], self.uvs[c]);
+        let (du1, dv1) = (uv_b.0 - uv_a.0, uv_b.1 - uv_a.1);
+        let (du2, dv2) = (uv_c.0 - uv_a.0, uv_c.1 - uv_a.1);
+        let determinant = du1 * dv2 - du2 * dv1;
+        if determinant.abs() < 1e-12 {
+            return None;
+        }
+        let e1 = self.positions[b].sub(&self.positions[a]);
+        let e2 = self.positions[c].sub(&self.positions[a]);
+        Some(e1.multiply(dv2).sub(&e2.multiply(dv1)).divide(determinant).normalize())
+    }
+}
+
+/// Vertex attributes extended to the vertices added on edges by subdivision,
+/// given the end points of each edge. Missing attributes stay missing.
+fn with_midpoints<T: Copy>(values: &[T], parents: &[[usize; 2]], midpoint: impl Fn(&T, &T) -> T) -> Vec<T> {
+    if values.is_empty() {
+        return vec![];
+    }
+    let mut values = values.to_vec();
+    for &[a, b] in parents {
+        values.push(midpoint(&values[a], &values[b]));
+    }
+    values
+}
+
+fn interpolate(values: &[Vec3], [a, b, c]: [usize; 3], u: f32, v: f32) -> Vec3 {
+    values[a]
+        .multiply(1.0 - u - v)
+        .add(&values[b].multiply(u))
+        .add(&values[c].multiply(v))
+}
+
+impl Hitable for TriangleMesh {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        // closest hit as (t, triangle, barycentric u, barycentric v)
+        let mut closest: Option<(f32, usize, f32, f32)> = None;
+        self.bvh.traverse(ray, t_min, t_max, |triangle, closest_t| {
+            let [a, b, c] = self.indices[triangle].map(|index| self.positions[index]);
+            let (t, u, v) = moller_trumbore_intersection(&ray.origin, &ray.direction, &a, &b, &c)?;
+            if t <= t_min || t >= closest_t {
+                return None;
+            }
+            if let Some(alpha) = &self.alpha
+                && !alpha.is_opaque(&ray.at(t), self.texture_uv(self.indices[triangle], u, v))
+            {
+                return None;
+            }
+            closest = Some((t, triangle, u, v));
+            Some(t)
+        });
+        let (t, triangle, u, v) = closest?;
+        let vertices = self.indices[triangle];
+        let [a, b, c] = vertices.map(|index| self.positions[index]);
+
+        // front_face comes from the geometric normal, the shading normal is
+        // then flipped to the same side
+        let geometric_normal = b.sub(&a).cross(&c.sub(&a)).normalize();
+        let (tex_u, tex_v) = self.texture_uv(vertices, u, v);
+        let mut record = HitRecord::new(ray, t, geometric_normal, self.material.clone(), tex_u, tex_v);
+        if !self.normals.is_empty() {
+            let shading_normal = interpolate(&self.normals, vertices, u, v).normalize();
+            record.normal = if record.front_face {
+                shading_normal
+            } else {
+                shading_normal.multiply(-1.0)
+            };
+        }
+        record.tangent = if !self.tangents.is_empty() {
+            Some(interpolate(&self.tangents, vertices, u, v).normalize())
+        } else if !self.uvs.is_empty() {
+            self.face_tangent(vertices)
+        } else {
+            None
+        };
+        if !self.colors.is_empty() {
+            record.vertex_color = Some(interpolate(&self.colors, vertices, u, v));
+        }
+        Some(record)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        self.bvh.bounds()
+    }
+}
@@ -0,0 +1,175 @@
+use std::{f32::consts::PI, sync::OnceLock};
+
+use crate::vec3::Vec3;
+
+/// Resolution of the single-scattering albedo table along each axis.
+const ALBEDO_TABLE_SIZE: usize = 32;
+
+/// Trowbridge–Reitz (GGX) distribution of microfacet normals, in a shading
+/// frame with the surface normal along z and the tangent along x.
+#[derive(Debug, Clone, Copy)]
+pub struct TrowbridgeReitz {
+    pub alpha_x: f32,
+    pub alpha_y: f32,
+}
+
+impl TrowbridgeReitz {
+    /// Distribution for a perceptual `roughness` in [0, 1], stretched along
+    /// the tangent for positive `anisotropy` up to 1 as in Disney's model.
+    pub fn from_roughness(roughness: f32, anisotropy: f32) -> Self {
+        let alpha = roughness * roughness;
+        let aspect = (1.0 - 0.9 * anisotropy.clamp(0.0, 1.0)).sqrt();
+        TrowbridgeReitz {
+            alpha_x: (alpha / aspect).max(1e-3),
+            alpha_y: (alpha * aspect).max(1e-3),
+        }
+    }
+
+    /// Smith's auxiliary function: microfacet area hidden from `w`, per unit
+    /// of visible area.
+    pub fn lambda(&self, w: &Vec3) -> f32 {
+        if w.z == 0.0 {
+            return f32::INFINITY;
+        }
+        let tan2 = ((self.alpha_x * w.x).powi(2) + (self.alpha_y * w.y).powi(2)) / (w.z * w.z);
+        ((1.0 + tan2).sqrt() - 1.0) / 2.0
+    }
+
+    /// Fraction of microfacets visible from `w`.
+    pub fn g1(&self, w: &Vec3) -> f32 {
+        1.0 / (1.0 + self.lambda(w))
+    }
+
+    /// Fraction of microfacets visible from both `wo` and `wi`.
+    pub fn g(&self, wo: &Vec3, wi: &Vec3) -> f32 {
+        1.0 / (1.0 + self.lambda(wo) + self.lambda(wi))
+    }
+
+    /// Microfacet normal drawn in proportion to how much of it `wo` sees,
+    /// from two uniform numbers in [0, 1) (Heitz 2018). `wo` must be above
+    /// the surface.
+    pub fn sample_visible_normal(&self, wo: &Vec3, u1: f32, u2: f32) -> Vec3 {
+        // stretch to the hemisphere configuration
+        let wh = Vec3::new(self.alpha_x * wo.x, self.alpha_y * wo.y, wo.z).normalize();
+        let length_squared = wh.x * wh.x + wh.y * wh.y;
+        let t1 = if length_squared > 0.0 {
+            Vec3::new(-wh.y, wh.x, 0.0).divide(length_squared.sqrt())
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let t2 = wh.cross(&t1);
+
+        // point on the projected disk, squeezed onto the visible half
+        let r = u1.sqrt();
+        let phi = 2.0 * PI * u2;
+        let p1 = r * phi.cos();
+        let s = (1.0 + wh.z) / 2.0;
+        let p2 = (1.0 - s) * (1.0 - p1 * p1).max(0.0).sqrt() + s * r * phi.sin();
+        let nh = t1
+            .multiply(p1)
+            .add(&t2.multiply(p2))
+            .add(&wh.multiply((1.0 - p1 * p1 - p2 * p2).max(0.0).sqrt()));
+
+        // and back to the ellipsoid
+        Vec3::new(self.alpha_x * nh.x, self.alpha_y * nh.y, nh.z.max(1e-6)).normalize()
+    }
+
+    /// Share of light arriving at `cos_theta` to the normal that a perfectly
+    /// reflective surface with this distribution sends back out after a
+    /// single bounce. The rest is what multiple scattering between the
+    /// microfacets would return, which the Smith model leaves out.
+    pub fn albedo(&self, cos_theta: f32) -> f32 {
+        let table = ALBEDO_TABLE.get_or_init(albedo_table);
+        let alpha = (self.alpha_x * self.alpha_y).sqrt();
+        let last = (ALBEDO_TABLE_SIZE - 1) as f32;
+        let x = cos_theta.clamp(0.0, 1.0) * last;
+        let y = alpha.clamp(0.0, 1.0) * last;
+        let (x0, y0) = (x.floor().min(last - 1.0), y.floor().min(last - 1.0));
+        let (fx, fy) = (x - x0, y - y0);
+        let at = |x: f32, y: f32| table[y as usize * ALBEDO_TABLE_SIZE + x as usize];
+        at(x0, y0) * (1.0 - fx) * (1.0 - fy)
+            + at(x0 + 1.0, y0) * fx * (1.0 - fy)
+            + at(x0, y0 + 1.0) * (1.0 - fx) * fy
+            + at(x0 + 1.0, y0 + 1.0) * fx * fy
+    }
+}
+
+static ALBEDO_TABLE: OnceLock<Vec<f32>> = OnceLock::new();
+
+/// Single-scattering albedo by roughness (rows) and cosine of the incoming
+/// direction (columns), integrated over a stratified grid of visible normals.
+fn albedo_table() -> Vec<f32> {
+    let strata = 16;
+    let mut table = Vec::with_capacity(ALBEDO_TABLE_SIZE * ALBEDO_TABLE_SIZE);
+    for row in 0..ALBEDO_TABLE_SIZE {
+        let alpha = (row as f32 / (ALBEDO_TABLE_SIZE - 1) as f32).max(1e-3);
+        let distribution = TrowbridgeReitz {
+            alpha_x: alpha,
+            alpha_y: alpha,
+        };
+        for column in 0..ALBEDO_TABLE_SIZE {
+            let cos_theta = (column as f32 / (ALBEDO_TABLE_SIZE - 1) as f32).max(1e-3);
+            let wo = Vec3::new((1.0 - cos_theta * cos_theta).sqrt(), 0.0, cos_theta);
+            let mut sum = 0.0;
+            for i in 0..strata * strata {
+                let u1 = ((i / strata) as f32 + 0.5) / strata as f32;
+                let u2 = ((i % strata) as f32 + 0.5) / strata as f32;
+                let m = distribution.sample_visible_normal(&wo, u1, u2);
+                let wi = reflect(&wo, &m);
+                if wi.z > 0.0 {
+                    sum += distribution.g(&wo, &wi) / distribution.g1(&wo);
+                }
+            }
+            table.push(sum / (strata * strata) as f32);
+        }
+    }
+    table
+}
+
+/// Mirror image of `w` about the normal `m`, both pointing away from the
+/// surface.
+pub fn reflect(w: &Vec3, m: &Vec3) -> Vec3 {
+    m.multiply(2.0 * w.dot(m)).sub(w)
+}
+
+/// Unpolarized Fresnel reflectance of light arriving at `cos_theta_i` to the
+/// normal of a dielectric boundary, where `eta` is the index on the far side
+/// over the index on the near side. 1 under total internal reflection.
+pub fn fresnel_dielectric(cos_theta_i: f32, eta: f32) -> f32 {
+    let cos_theta_i = cos_theta_i.clamp(0.0, 1.0);
+    let sin_theta_t = (1.0 - cos_theta_i * cos_theta_i).max(0.0).sqrt() / eta;
+    if sin_theta_t >= 1.0 {
+        return 1.0;
+    }
+    let cos_theta_t = (1.0 - sin_theta_t * sin_theta_t).max(0.0).sqrt();
+    let parallel = (eta * cos_theta_i - cos_theta_t) / (eta * cos_theta_i + cos_theta_t);
+    let perpendicular = (cos_theta_i - eta * cos_theta_t) / (cos_theta_i + eta * cos_theta_t);
+    (parallel * parallel + perpendicular * perpendicular) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glass_reflects_four_percent_head_on() {
+        assert!((fresnel_dielectric(1.0, 1.5) - 0.04).abs() < 1e-4);
+    }
+
+    #[test]
+    fn fresnel_reflects_everything_at_grazing_angles() {
+        assert!(fresnel_dielectric(0.0, 1.5) > 0.999);
+    }
+
+    #[test]
+    fn total_internal_reflection() {
+        // past the critical angle of about 42 degrees from glass into air
+        let cos_theta = 45f32.to_radians().cos();
+        assert_eq!(fresnel_dielectric(cos_theta, 1.0 / 1.5), 1.0);
+    }
+
+    #[test]
+    fn matched_index_reflects_nothing() {
+        assert!(fresnel_dielectric(0.7, 1.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,20 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter},
+};
+
+use image::{DynamicImage, ImageEncoder, codecs::png::PngEncoder};
+
+/// Writes `img` to `filename` as an 8-bit RGB PNG.
+pub fn encode_png(img: &DynamicImage, filename: &str) -> io::Result<()> {
+    let file = File::create(filename)?;
+    let encoder = PngEncoder::new(BufWriter::new(file));
+    encoder
+        .write_image(
+            img.to_rgb8().as_raw(),
+            img.width(),
+            img.height(),
+            image::ExtendedColorType::Rgb8,
+        )
+        .map_err(io::Error::other)
+}
@@ -0,0 +1,55 @@
+use crate::{
+    hitable::{HitRecord, Hitable},
+    material::Material,
+    ray::Ray,
+    vec3::Vec3,
+};
+
+/// Infinite plane through `point`. UVs are world space distances along two
+/// axes in the plane, so textures tile instead of stretching.
+#[derive(Debug, Clone)]
+pub struct Plane {
+    point: Vec3,
+    normal: Vec3,
+    tangent: Vec3,
+    bitangent: Vec3,
+    material: Material,
+}
+
+impl Plane {
+    pub fn new(point: Vec3, normal: Vec3, material: Material) -> Self {
+        let normal = normal.normalize();
+        let (tangent, bitangent) = normal.orthonormal_basis();
+        Plane {
+            point,
+            normal,
+            tangent,
+            bitangent,
+            material,
+        }
+    }
+}
+
+impl Hitable for Plane {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let denominator = self.normal.dot(&ray.direction);
+        if denominator.abs() < 1e-8 {
+            return None;
+        }
+        let t = self.point.sub(&ray.origin).dot(&self.normal) / denominator;
+        if t <= t_min || t >= t_max {
+            return None;
+        }
+        let offset = ray.at(t).sub(&self.point);
+        let mut record = HitRecord::new(
+            ray,
+            t,
+            self.normal,
+            self.material.clone(),
+            offset.dot(&self.tangent),
+            offset.dot(&self.bitangent),
+        );
+        record.tangent = Some(self.tangent);
+        Some(record)
+    }
+}
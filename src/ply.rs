@@ -0,0 +1,293 @@
+use std::{fs, io, path::Path, str::SplitAsciiWhitespace};
+
+use crate::{material::Material, mesh::TriangleMesh, vec3::Vec3};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ScalarType {
+    Int8,
+    Uint8,
+    Int16,
+    Uint16,
+    Int32,
+    Uint32,
+    Float32,
+    Float64,
+}
+
+impl ScalarType {
+    fn parse(name: &str) -> io::Result<Self> {
+        Ok(match name {
+            "char" | "int8" => ScalarType::Int8,
+            "uchar" | "uint8" => ScalarType::Uint8,
+            "short" | "int16" => ScalarType::Int16,
+            "ushort" | "uint16" => ScalarType::Uint16,
+            "int" | "int32" => ScalarType::Int32,
+            "uint" | "uint32" => ScalarType::Uint32,
+            "float" | "float32" => ScalarType::Float32,
+            "double" | "float64" => ScalarType::Float64,
+            _ => return Err(invalid_data(format!("unknown PLY property type '{}'", name))),
+        })
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            ScalarType::Int8 | ScalarType::Uint8 => 1,
+            ScalarType::Int16 | ScalarType::Uint16 => 2,
+            ScalarType::Int32 | ScalarType::Uint32 | ScalarType::Float32 => 4,
+            ScalarType::Float64 => 8,
+        }
+    }
+
+    /// Scale that maps integer colour channels to [0, 1].
+    fn color_scale(&self) -> f64 {
+        match self {
+            ScalarType::Uint8 => 1.0 / 255.0,
+            ScalarType::Uint16 => 1.0 / 65535.0,
+            _ => 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Property {
+    Scalar(String, ScalarType),
+    List(String, ScalarType, ScalarType),
+}
+
+#[derive(Debug, Clone)]
+struct Element {
+    name: String,
+    count: usize,
+    properties: Vec<Property>,
+}
+
+/// Element data after the header, read one value at a time.
+enum Body<'a> {
+    Ascii(SplitAsciiWhitespace<'a>),
+    Binary {
+        data: &'a [u8],
+        position: usize,
+        big_endian: bool,
+    },
+}
+
+impl Body<'_> {
+    fn read(&mut self, scalar_type: ScalarType) -> io::Result<f64> {
+        match self {
+            Body::Ascii(tokens) => {
+                let token = tokens
+                    .next()
+                    .ok_or_else(|| invalid_data("PLY data ends early".to_string()))?;
+                token
+                    .parse()
+                    .map_err(|_| invalid_data(format!("invalid PLY value '{}'", token)))
+            }
+            Body::Binary {
+                data,
+                position,
+                big_endian,
+            } => {
+                let size = scalar_type.size();
+                let bytes = data
+                    .get(*position..*position + size)
+                    .ok_or_else(|| invalid_data("PLY data ends early".to_string()))?;
+                *position += size;
+                let mut buffer = [0u8; 8];
+                buffer[..size].copy_from_slice(bytes);
+                if *big_endian {
+                    buffer[..size].reverse();
+                }
+                Ok(match scalar_type {
+                    ScalarType::Int8 => buffer[0] as i8 as f64,
+                    ScalarType::Uint8 => buffer[0] as f64,
+                    ScalarType::Int16 => i16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+                    ScalarType::Uint16 => u16::from_le_bytes([buffer[0], buffer[1]]) as f64,
+                    ScalarType::Int32 => i32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64,
+                    ScalarType::Uint32 => u32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64,
+                    ScalarType::Float32 => f32::from_le_bytes(buffer[..4].try_into().unwrap()) as f64,
+                    ScalarType::Float64 => f64::from_le_bytes(buffer),
+                })
+            }
+        }
+    }
+}
+
+/// Parses an ASCII or binary PLY file into a mesh. Vertex normals, colours
+/// (`red`, `green`, `blue`) and texture coordinates (`u`/`v`, `s`/`t` or
+/// `texture_u`/`texture_v`) are picked up when present, polygons are split
+/// into triangle fans and all other elements are skipped.
+pub fn parse(data: &[u8], material: Material) -> io::Result<TriangleMesh> {
+    let header_end = data
+        .windows(10)
+        .position(|window| window == b"end_header")
+        .ok_or_else(|| invalid_data("PLY header has no end_header".to_string()))?;
+    let header = std::str::from_utf8(&data[..header_end])
+        .map_err(|_| invalid_data("PLY header is not valid text".to_string()))?;
+    // the body starts after the line break that ends the header
+    let mut body_start = header_end + 10;
+    while body_start < data.len() && data[body_start] != b'\n' {
+        body_start += 1;
+    }
+    body_start += 1;
+
+    let mut lines = header.lines();
+    if lines.next().map(str::trim) != Some("ply") {
+        return Err(invalid_data("not a PLY file".to_string()));
+    }
+    let mut format = None;
+    let mut elements: Vec<Element> = vec![];
+    for line in lines {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            ["format", name, _] => {
+                format = Some(match *name {
+                    "ascii" => Format::Ascii,
+                    "binary_little_endian" => Format::BinaryLittleEndian,
+                    "binary_big_endian" => Format::BinaryBigEndian,
+                    _ => return Err(invalid_data(format!("unknown PLY format '{}'", name))),
+                })
+            }
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: count
+                    .parse()
+                    .map_err(|_| invalid_data(format!("invalid PLY element count '{}'", count)))?,
+                properties: vec![],
+            }),
+            ["property", "list", count_type, item_type, name] => elements
+                .last_mut()
+                .ok_or_else(|| invalid_data("PLY property outside of an element".to_string()))?
+                .properties
+                .push(Property::List(
+                    name.to_string(),
+                    ScalarType::parse(count_type)?,
+                    ScalarType::parse(item_type)?,
+                )),
+            ["property", scalar_type, name] => elements
+                .last_mut()
+                .ok_or_else(|| invalid_data("PLY property outside of an element".to_string()))?
+                .properties
+                .push(Property::Scalar(name.to_string(), ScalarType::parse(scalar_type)?)),
+            _ => {}
+        }
+    }
+    let format = format.ok_or_else(|| invalid_data("PLY header has no format".to_string()))?;
+
+    let body_data = data.get(body_start..).unwrap_or(&[]);
+    let mut body = match format {
+        Format::Ascii => Body::Ascii(
+            std::str::from_utf8(body_data)
+                .map_err(|_| invalid_data("PLY data is not valid text".to_string()))?
+                .split_ascii_whitespace(),
+        ),
+        _ => Body::Binary {
+            data: body_data,
+            position: 0,
+            big_endian: format == Format::BinaryBigEndian,
+        },
+    };
+
+    let mut positions = vec![];
+    let mut normals = vec![];
+    let mut colors = vec![];
+    let mut uvs = vec![];
+    let mut indices = vec![];
+    for element in &elements {
+        for _ in 0..element.count {
+            // scalar values of this element by property name
+            let mut values: Vec<(&str, f64, ScalarType)> = vec![];
+            let mut list: Vec<usize> = vec![];
+            for property in &element.properties {
+                match property {
+                    Property::Scalar(name, scalar_type) => {
+                        values.push((name.as_str(), body.read(*scalar_type)?, *scalar_type));
+                    }
+                    Property::List(name, count_type, item_type) => {
+                        let count = index(body.read(*count_type)?, "list length")?;
+                        // each item takes at least a byte, so a bogus count
+                        // can't reserve more than the file could hold
+                        let mut items = Vec::with_capacity(count.min(body_data.len()));
+                        for _ in 0..count {
+                            items.push(index(body.read(*item_type)?, "list item")?);
+                        }
+                        if name == "vertex_indices" || name == "vertex_index" {
+                            list = items;
+                        }
+                    }
+                }
+            }
+
+            let value = |names: &[&str]| {
+                values
+                    .iter()
+                    .find(|(name, _, _)| names.contains(name))
+                    .map(|(_, value, scalar_type)| (*value, *scalar_type))
+            };
+            match element.name.as_str() {
+                "vertex" => {
+                    let coordinate = |name: &str| value(&[name]).map_or(0.0, |(value, _)| value as f32);
+                    positions.push(Vec3::new(coordinate("x"), coordinate("y"), coordinate("z")));
+                    if value(&["nx"]).is_some() {
+                        normals.push(Vec3::new(coordinate("nx"), coordinate("ny"), coordinate("nz")));
+                    }
+                    if value(&["red"]).is_some() {
+                        let channel = |name: &str| {
+                            value(&[name]).map_or(0.0, |(value, scalar_type)| (value * scalar_type.color_scale()) as f32)
+                        };
+                        colors.push(Vec3::new(channel("red"), channel("green"), channel("blue")));
+                    }
+                    if let (Some((u, _)), Some((v, _))) =
+                        (value(&["u", "s", "texture_u"]), value(&["v", "t", "texture_v"]))
+                    {
+                        uvs.push((u as f32, v as f32));
+                    }
+                }
+                "face" => {
+                    for i in 1..list.len().saturating_sub(1) {
+                        indices.push([list[0], list[i], list[i + 1]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if let Some(index) = indices.iter().flatten().find(|&&index| index >= positions.len()) {
+        return Err(invalid_data(format!("PLY face refers to missing vertex {}", index)));
+    }
+    let mut mesh = TriangleMesh::new(positions, indices, material);
+    if !normals.is_empty() {
+        mesh = mesh.with_normals(normals);
+    }
+    if !colors.is_empty() {
+        mesh = mesh.with_colors(colors);
+    }
+    if !uvs.is_empty() {
+        mesh = mesh.with_uvs(uvs);
+    }
+    Ok(mesh)
+}
+
+pub fn load<P: AsRef<Path>>(path: P, material: Material) -> io::Result<TriangleMesh> {
+    parse(&fs::read(path)?, material)
+}
+
+/// List lengths and vertex indices must be whole, non-negative numbers.
+fn index(value: f64, what: &str) -> io::Result<usize> {
+    if value >= 0.0 && value.fract() == 0.0 {
+        Ok(value as usize)
+    } else {
+        Err(invalid_data(format!("PLY {} must be a non-negative integer, got {}", what, value)))
+    }
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
@@ -0,0 +1,235 @@
+use std::f32::consts::PI;
+
+use crate::{
+    conductor::Conductor,
+    hitable::HitRecord,
+    microfacet::{self, TrowbridgeReitz},
+    texture::Texture,
+    util::{luminance, random_double},
+    vec3::Vec3,
+};
+
+/// Index of refraction of the clear coat, which reflects 4% head on.
+const CLEARCOAT_IOR: f32 = 1.5;
+
+/// Disney-style uber material with the parameters of the Disney 2015 BSDF
+/// and glTF's metallic-roughness model and extensions: a diffuse base with
+/// sheen and a subsurface look, GGX specular, metal, rough glass and a
+/// clear coat on top. All factors are in [0, 1].
+///
+/// Each bounce picks one lobe at random, in proportion to roughly how much
+/// it reflects toward the viewer, and samples it on its own.
+#[derive(Debug, Clone)]
+pub struct Principled {
+    pub base_color: Vec3,
+    /// Multiplies `base_color` where present, as glTF's base colour texture.
+    pub base_texture: Option<Texture>,
+    pub metallic: f32,
+    pub roughness: f32,
+    /// Stretch of the highlights along the surface tangent.
+    pub anisotropy: f32,
+    /// Strength of the dielectric reflection; 0.5 gives the 4% of `ior` 1.5.
+    pub specular: f32,
+    /// How much the dielectric reflection takes on the base colour.
+    pub specular_tint: f32,
+    pub ior: f32,
+    /// Soft grazing-angle highlight of cloth.
+    pub sheen: f32,
+    pub sheen_tint: f32,
+    /// Extra colourless GGX layer on top, as car paint.
+    pub clearcoat: f32,
+    pub clearcoat_roughness: f32,
+    /// Share of the dielectric base that is glass instead of diffuse, with
+    /// the refracted light tinted by the base colour.
+    pub transmission: f32,
+    /// Blends the diffuse lobe toward the flatter look of light scattered
+    /// under the surface (Hanrahan–Krueger, as in Disney 2012).
+    pub subsurface: f32,
+}
+
+impl Principled {
+    pub fn new(base_color: Vec3, metallic: f32, roughness: f32) -> Self {
+        Principled {
+            base_color,
+            metallic,
+            roughness,
+            ..Principled::default()
+        }
+    }
+
+    /// Picks an incoming direction for light leaving toward `wo`, in the
+    /// shading frame of `hit_record` with the normal along z and the tangent
+    /// along x. Returns the direction and the weight to multiply the light
+    /// arriving from it by, or `None` when the sample is absorbed.
+    pub fn sample(&self, wo: &Vec3, hit_record: &HitRecord) -> Option<(Vec3, Vec3)> {
+        if wo.z <= 0.0 {
+            return None;
+        }
+        let base = match &self.base_texture {
+            Some(texture) => texture.value(hit_record).multiply_vec(&self.base_color),
+            None => self.base_color,
+        };
+        let dielectric = (1.0 - self.metallic) * (1.0 - self.transmission);
+        let glass = (1.0 - self.metallic) * self.transmission;
+        let specular_color = self.specular_color(&base);
+
+        // light reflected by the coat never reaches the layers below
+        let coat_reflectance = self.clearcoat * schlick_scalar(coat_f0(), wo.z);
+        let below = 1.0 - coat_reflectance;
+        let dielectric_reflectance = luminance(&schlick(&specular_color, wo.z));
+        let chances = [
+            coat_reflectance,
+            below * self.metallic,
+            below * dielectric * dielectric_reflectance,
+            below * dielectric * (1.0 - dielectric_reflectance) * (luminance(&base) + self.sheen),
+            below * glass,
+        ];
+        let total: f32 = chances.iter().sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut choice = random_double() * total;
+        let mut lobe = chances.iter().rposition(|&chance| chance > 0.0).unwrap();
+        for (index, &chance) in chances.iter().enumerate() {
+            if choice < chance {
+                lobe = index;
+                break;
+            }
+            choice -= chance;
+        }
+        let probability = chances[lobe] / total;
+
+        let (wi, weight) = match lobe {
+            0 => {
+                let coat = TrowbridgeReitz::from_roughness(self.clearcoat_roughness, 0.0);
+                let (wi, m) = sample_reflection(&coat, wo)?;
+                let fresnel = self.clearcoat * schlick_scalar(coat_f0(), wo.dot(&m));
+                (wi, Vec3::new(fresnel, fresnel, fresnel).multiply(coat.g(wo, &wi) / coat.g1(wo)))
+            }
+            1 => {
+                let metal = Conductor::from_reflectance(base, self.roughness, self.anisotropy);
+                let (wi, weight) = metal.sample(wo, hit_record)?;
+                (wi, weight.multiply(below * self.metallic))
+            }
+            2 => {
+                let distribution = self.distribution();
+                let (wi, m) = sample_reflection(&distribution, wo)?;
+                let weight = schlick(&specular_color, wo.dot(&m))
+                    .multiply(below * dielectric * distribution.g(wo, &wi) / distribution.g1(wo));
+                (wi, weight)
+            }
+            3 => {
+                let wi = Vec3::random_cosine_direction();
+                // cos / pdf is π for cosine-weighted directions
+                let weight = self
+                    .diffuse(wo, &wi, &base)
+                    .multiply(below * dielectric * (1.0 - dielectric_reflectance) * PI);
+                (wi, weight)
+            }
+            _ => {
+                let distribution = self.distribution();
+                let m = distribution.sample_visible_normal(wo, random_double(), random_double());
+                let eta = if hit_record.front_face { self.ior } else { 1.0 / self.ior };
+                let fresnel = microfacet::fresnel_dielectric(wo.dot(&m), eta);
+                let (wi, tint) = if random_double() < fresnel {
+                    let wi = microfacet::reflect(wo, &m);
+                    (wi.z > 0.0).then_some((wi, Vec3::new(1.0, 1.0, 1.0)))?
+                } else {
+                    let wi = wo.multiply(-1.0).refract(&m, 1.0 / eta).filter(|wi| wi.z < 0.0)?;
+                    // tinted once, on the way in
+                    let tint = if hit_record.front_face { base } else { Vec3::new(1.0, 1.0, 1.0) };
+                    (wi, tint)
+                };
+                let wi_above = Vec3::new(wi.x, wi.y, wi.z.abs());
+                (wi, tint.multiply(below * glass * distribution.g(wo, &wi_above) / distribution.g1(wo)))
+            }
+        };
+        Some((wi, weight.divide(probability)))
+    }
+
+    fn distribution(&self) -> TrowbridgeReitz {
+        TrowbridgeReitz::from_roughness(self.roughness, self.anisotropy)
+    }
+
+    /// Head-on reflectance of the dielectric base.
+    fn specular_color(&self, base: &Vec3) -> Vec3 {
+        let f0 = ((self.ior - 1.0) / (self.ior + 1.0)).powi(2) * 2.0 * self.specular;
+        lerp(&Vec3::new(1.0, 1.0, 1.0), &hue(base), self.specular_tint).multiply(f0)
+    }
+
+    /// Disney diffuse with retro-reflection at grazing angles, blended with
+    /// the subsurface approximation, plus sheen.
+    fn diffuse(&self, wo: &Vec3, wi: &Vec3, base: &Vec3) -> Vec3 {
+        let half = wo.add(wi).normalize();
+        let cos_d = wi.dot(&half);
+        let (weight_o, weight_i) = (schlick_weight(wo.z), schlick_weight(wi.z));
+        let retro = 0.5 + 2.0 * self.roughness * cos_d * cos_d;
+        let lambert = (1.0 + (retro - 1.0) * weight_i) * (1.0 + (retro - 1.0) * weight_o);
+        let flat = self.roughness * cos_d * cos_d;
+        let subsurface = 1.25
+            * ((1.0 + (flat - 1.0) * weight_i) * (1.0 + (flat - 1.0) * weight_o) * (1.0 / (wo.z + wi.z) - 0.5) + 0.5);
+        let sheen = lerp(&Vec3::new(1.0, 1.0, 1.0), &hue(base), self.sheen_tint).multiply(self.sheen * schlick_weight(cos_d));
+        base.multiply((lambert + (subsurface - lambert) * self.subsurface) / PI)
+            .add(&sheen)
+    }
+}
+
+impl Default for Principled {
+    /// Grey plastic, with Disney's defaults.
+    fn default() -> Self {
+        Principled {
+            base_color: Vec3::new(0.8, 0.8, 0.8),
+            base_texture: None,
+            metallic: 0.0,
+            roughness: 0.5,
+            anisotropy: 0.0,
+            specular: 0.5,
+            specular_tint: 0.0,
+            ior: 1.5,
+            sheen: 0.0,
+            sheen_tint: 0.5,
+            clearcoat: 0.0,
+            clearcoat_roughness: 0.03,
+            transmission: 0.0,
+            subsurface: 0.0,
+        }
+    }
+}
+
+/// Visible normal and the mirror direction off it, or `None` when that
+/// leaves below the surface.
+fn sample_reflection(distribution: &TrowbridgeReitz, wo: &Vec3) -> Option<(Vec3, Vec3)> {
+    let m = distribution.sample_visible_normal(wo, random_double(), random_double());
+    let wi = microfacet::reflect(wo, &m);
+    (wi.z > 0.0).then_some((wi, m))
+}
+
+fn coat_f0() -> f32 {
+    ((CLEARCOAT_IOR - 1.0) / (CLEARCOAT_IOR + 1.0)).powi(2)
+}
+
+fn schlick_weight(cos_theta: f32) -> f32 {
+    (1.0 - cos_theta.clamp(0.0, 1.0)).powi(5)
+}
+
+fn schlick_scalar(f0: f32, cos_theta: f32) -> f32 {
+    f0 + (1.0 - f0) * schlick_weight(cos_theta)
+}
+
+fn schlick(f0: &Vec3, cos_theta: f32) -> Vec3 {
+    lerp(f0, &Vec3::new(1.0, 1.0, 1.0), schlick_weight(cos_theta))
+}
+
+/// Colour normalized to unit luminance, white for black.
+fn hue(color: &Vec3) -> Vec3 {
+    let brightness = luminance(color);
+    if brightness > 0.0 {
+        color.divide(brightness)
+    } else {
+        Vec3::new(1.0, 1.0, 1.0)
+    }
+}
+
+fn lerp(a: &Vec3, b: &Vec3, t: f32) -> Vec3 {
+    a.add(&b.sub(a).multiply(t))
+}
@@ -0,0 +1,86 @@
+use crate::{
+    aabb::Aabb,
+    hitable::{HitRecord, Hitable},
+    material::Material,
+    ray::Ray,
+    texture::ScalarTexture,
+    vec3::Vec3,
+};
+
+/// Parallelogram spanned by `u` and `v` from `corner`, with UVs running from
+/// 0 to 1 along each edge.
+#[derive(Debug, Clone)]
+pub struct Quad {
+    corner: Vec3,
+    u: Vec3,
+    v: Vec3,
+    normal: Vec3,
+    w: Vec3,
+    material: Material,
+    alpha: Option<ScalarTexture>,
+}
+
+impl Quad {
+    pub fn new(corner: Vec3, u: Vec3, v: Vec3, material: Material) -> Self {
+        let n = u.cross(&v);
+        Quad {
+            corner,
+            u,
+            v,
+            normal: n.normalize(),
+            w: n.divide(n.length_squared()),
+            material,
+            alpha: None,
+        }
+    }
+
+    /// Parallelogram through three consecutive corners, `b` being the one
+    /// between the other two.
+    pub fn from_points(a: Vec3, b: Vec3, c: Vec3, material: Material) -> Self {
+        Quad::new(b, a.sub(&b), c.sub(&b), material)
+    }
+
+    /// Opacity looked up by UV, for cut-outs like leaves or fences. Rays
+    /// pass through where it is 0.
+    pub fn with_alpha(mut self, alpha: ScalarTexture) -> Self {
+        self.alpha = Some(alpha);
+        self
+    }
+}
+
+impl Hitable for Quad {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let denominator = self.normal.dot(&ray.direction);
+        if denominator.abs() < 1e-8 {
+            return None;
+        }
+        let t = self.corner.sub(&ray.origin).dot(&self.normal) / denominator;
+        if t <= t_min || t >= t_max {
+            return None;
+        }
+
+        let planar_hit = ray.at(t).sub(&self.corner);
+        let alpha = self.w.dot(&planar_hit.cross(&self.v));
+        let beta = self.w.dot(&self.u.cross(&planar_hit));
+        if !(0.0..=1.0).contains(&alpha) || !(0.0..=1.0).contains(&beta) {
+            return None;
+        }
+        if let Some(mask) = &self.alpha
+            && !mask.is_opaque(&ray.at(t), (alpha, beta))
+        {
+            return None;
+        }
+        let mut record = HitRecord::new(ray, t, self.normal, self.material.clone(), alpha, beta);
+        record.tangent = Some(self.u.normalize());
+        Some(record)
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(Aabb::from_points(&[
+            self.corner,
+            self.corner.add(&self.u),
+            self.corner.add(&self.v),
+            self.corner.add(&self.u).add(&self.v),
+        ]))
+    }
+}
@@ -0,0 +1,239 @@
+use std::f32::consts::PI;
+
+use crate::{
+    aabb::Aabb,
+    frame::Frame,
+    hitable::{HitRecord, Hitable},
+    material::Material,
+    ray::Ray,
+    roots::solve_quadratic,
+    vec3::Vec3,
+};
+
+/// Surfaces of revolution around the local z axis, as in pbrt.
+#[derive(Debug, Clone, Copy)]
+pub enum QuadricShape {
+    Cylinder { radius: f32 },
+    /// Base of `radius` at z = 0, apex at z = `height`.
+    Cone { radius: f32, height: f32 },
+    /// Vertex at z = 0, `radius` wide at z = `height`.
+    Paraboloid { radius: f32, height: f32 },
+    /// One sheet hyperboloid x² + y² - (r z / c)² = r², narrowest at z = 0.
+    Hyperboloid { waist_radius: f32, c: f32 },
+}
+
+impl QuadricShape {
+    /// Coefficients of the intersection quadratic for a ray in object space.
+    fn coefficients(&self, o: &Vec3, d: &Vec3) -> (f32, f32, f32) {
+        match *self {
+            QuadricShape::Cylinder { radius } => (
+                d.x * d.x + d.y * d.y,
+                2.0 * (d.x * o.x + d.y * o.y),
+                o.x * o.x + o.y * o.y - radius * radius,
+            ),
+            QuadricShape::Cone { radius, height } => {
+                let k = (radius / height) * (radius / height);
+                let oz = o.z - height;
+                (
+                    d.x * d.x + d.y * d.y - k * d.z * d.z,
+                    2.0 * (d.x * o.x + d.y * o.y - k * d.z * oz),
+                    o.x * o.x + o.y * o.y - k * oz * oz,
+                )
+            }
+            QuadricShape::Paraboloid { radius, height } => {
+                let k = height / (radius * radius);
+                (
+                    k * (d.x * d.x + d.y * d.y),
+                    2.0 * k * (d.x * o.x + d.y * o.y) - d.z,
+                    k * (o.x * o.x + o.y * o.y) - o.z,
+                )
+            }
+            QuadricShape::Hyperboloid { waist_radius, c } => {
+                let k = (waist_radius / c) * (waist_radius / c);
+                (
+                    d.x * d.x + d.y * d.y - k * d.z * d.z,
+                    2.0 * (d.x * o.x + d.y * o.y - k * d.z * o.z),
+                    o.x * o.x + o.y * o.y - k * o.z * o.z - waist_radius * waist_radius,
+                )
+            }
+        }
+    }
+
+    fn outward_normal(&self, p: &Vec3) -> Vec3 {
+        match *self {
+            QuadricShape::Cylinder { .. } => Vec3::new(p.x, p.y, 0.0),
+            QuadricShape::Cone { radius, height } => {
+                let k = (radius / height) * (radius / height);
+                Vec3::new(p.x, p.y, -k * (p.z - height))
+            }
+            QuadricShape::Paraboloid { radius, height } => {
+                let k = height / (radius * radius);
+                Vec3::new(2.0 * k * p.x, 2.0 * k * p.y, -1.0)
+            }
+            QuadricShape::Hyperboloid { waist_radius, c } => {
+                let k = (waist_radius / c) * (waist_radius / c);
+                Vec3::new(p.x, p.y, -k * p.z)
+            }
+        }
+        .normalize()
+    }
+
+    fn radius_at(&self, z: f32) -> f32 {
+        match *self {
+            QuadricShape::Cylinder { radius } => radius,
+            QuadricShape::Cone { radius, height } => (radius * (1.0 - z / height)).max(0.0),
+            QuadricShape::Paraboloid { radius, height } => radius * (z / height).max(0.0).sqrt(),
+            QuadricShape::Hyperboloid { waist_radius, c } => waist_radius * (1.0 + z * z / (c * c)).sqrt(),
+        }
+    }
+
+    fn default_height(&self) -> f32 {
+        match *self {
+            QuadricShape::Cylinder { radius } | QuadricShape::Hyperboloid { waist_radius: radius, .. } => radius,
+            QuadricShape::Cone { height, .. } | QuadricShape::Paraboloid { height, .. } => height,
+        }
+    }
+}
+
+/// Quadric primitive placed at `base` with its z axis along `axis`. The surface
+/// is clipped to a height range and a sweep angle, and can be closed with caps.
+#[derive(Debug, Clone)]
+pub struct Quadric {
+    shape: QuadricShape,
+    frame: Frame,
+    z_min: f32,
+    z_max: f32,
+    phi_max: f32,
+    capped: bool,
+    material: Material,
+}
+
+impl Quadric {
+    pub fn new(shape: QuadricShape, base: Vec3, axis: Vec3, material: Material) -> Self {
+        let z_max = shape.default_height();
+        let z_min = match shape {
+            QuadricShape::Hyperboloid { .. } => -z_max,
+            _ => 0.0,
+        };
+        Quadric {
+            shape,
+            frame: Frame::from_axis(base, axis),
+            z_min,
+            z_max,
+            phi_max: 2.0 * PI,
+            capped: false,
+            material,
+        }
+    }
+
+    pub fn cylinder(base: Vec3, axis: Vec3, radius: f32, height: f32, material: Material) -> Self {
+        Quadric::new(QuadricShape::Cylinder { radius }, base, axis, material).with_height_range(0.0, height)
+    }
+
+    pub fn cone(base: Vec3, axis: Vec3, radius: f32, height: f32, material: Material) -> Self {
+        Quadric::new(QuadricShape::Cone { radius, height }, base, axis, material)
+    }
+
+    pub fn paraboloid(base: Vec3, axis: Vec3, radius: f32, height: f32, material: Material) -> Self {
+        Quadric::new(QuadricShape::Paraboloid { radius, height }, base, axis, material)
+    }
+
+    pub fn hyperboloid(center: Vec3, axis: Vec3, waist_radius: f32, c: f32, half_height: f32, material: Material) -> Self {
+        Quadric::new(QuadricShape::Hyperboloid { waist_radius, c }, center, axis, material)
+            .with_height_range(-half_height, half_height)
+    }
+
+    /// Clips the surface to `z_min..=z_max` along its axis.
+    pub fn with_height_range(mut self, z_min: f32, z_max: f32) -> Self {
+        self.z_min = z_min;
+        self.z_max = z_max;
+        self
+    }
+
+    /// Only keeps the part of the surface up to `degrees` around the axis.
+    pub fn with_sweep(mut self, degrees: f32) -> Self {
+        self.phi_max = degrees.clamp(0.0, 360.0).to_radians();
+        self
+    }
+
+    /// Closes the ends of the height range with (partial) disks.
+    pub fn with_caps(mut self) -> Self {
+        self.capped = true;
+        self
+    }
+
+    fn phi(&self, p: &Vec3) -> f32 {
+        let phi = p.y.atan2(p.x);
+        if phi < 0.0 { phi + 2.0 * PI } else { phi }
+    }
+}
+
+impl Hitable for Quadric {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let o = self.frame.point_to_local(&ray.origin);
+        let d = self.frame.to_local(&ray.direction);
+
+        // closest hit so far as (t, local outward normal, u, v)
+        let mut closest: Option<(f32, Vec3, f32, f32)> = None;
+        let mut closest_t = t_max;
+
+        let (a, b, c) = self.shape.coefficients(&o, &d);
+        if let Some((t0, t1)) = solve_quadratic(a as f64, b as f64, c as f64) {
+            for t in [t0 as f32, t1 as f32] {
+                if t <= t_min || t >= closest_t {
+                    continue;
+                }
+                let p = o.add(&d.multiply(t));
+                let phi = self.phi(&p);
+                if p.z < self.z_min || p.z > self.z_max || phi > self.phi_max {
+                    continue;
+                }
+                let v = (p.z - self.z_min) / (self.z_max - self.z_min);
+                closest = Some((t, self.shape.outward_normal(&p), phi / self.phi_max, v));
+                closest_t = t;
+                break;
+            }
+        }
+
+        if self.capped && d.z != 0.0 {
+            for (z, normal_z) in [(self.z_min, -1.0), (self.z_max, 1.0)] {
+                let radius = self.shape.radius_at(z);
+                let t = (z - o.z) / d.z;
+                if radius <= 0.0 || t <= t_min || t >= closest_t {
+                    continue;
+                }
+                let p = o.add(&d.multiply(t));
+                let distance = (p.x * p.x + p.y * p.y).sqrt();
+                let phi = self.phi(&p);
+                if distance > radius || phi > self.phi_max {
+                    continue;
+                }
+                closest = Some((t, Vec3::new(0.0, 0.0, normal_z), phi / self.phi_max, distance / radius));
+                closest_t = t;
+            }
+        }
+
+        let (t, normal, u, v) = closest?;
+        Some(HitRecord::new(
+            ray,
+            t,
+            self.frame.to_world(&normal),
+            self.material.clone(),
+            u,
+            v,
+        ))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let radius = self.shape.radius_at(self.z_min).max(self.shape.radius_at(self.z_max));
+        let mut corners = vec![];
+        for x in [-radius, radius] {
+            for y in [-radius, radius] {
+                for z in [self.z_min, self.z_max] {
+                    corners.push(self.frame.point_to_world(&Vec3::new(x, y, z)));
+                }
+            }
+        }
+        Some(Aabb::from_points(&corners))
+    }
+}
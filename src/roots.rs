@@ -0,0 +1,129 @@
+/// Real roots of a x² + b x + c in ascending order, computed in f64 with the
+/// cancellation-free form of the quadratic formula.
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Option<(f64, f64)> {
+    if a == 0.0 {
+        if b == 0.0 {
+            return None;
+        }
+        return Some((-c / b, -c / b));
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let q = if b < 0.0 {
+        -0.5 * (b - discriminant.sqrt())
+    } else {
+        -0.5 * (b + discriminant.sqrt())
+    };
+    let (t0, t1) = if q == 0.0 { (0.0, 0.0) } else { (q / a, c / q) };
+    Some(if t0 < t1 { (t0, t1) } else { (t1, t0) })
+}
+
+/// Evaluates a polynomial given by its coefficients, lowest degree first.
+pub fn evaluate(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |result, c| result * x + c)
+}
+
+pub fn derivative(coefficients: &[f64]) -> Vec<f64> {
+    coefficients
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(power, c)| c * power as f64)
+        .collect()
+}
+
+/// Multiplies two polynomials, lowest degree first.
+pub fn multiply(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut result = vec![0.0; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            result[i + j] += x * y;
+        }
+    }
+    result
+}
+
+/// All real roots of a polynomial inside [lo, hi], in ascending order.
+///
+/// The roots of the derivative split the interval into pieces on which the
+/// polynomial is monotonic, so each piece holds at most one root and can be
+/// solved with safeguarded Newton iterations. Recursing on the derivative keeps
+/// this robust for any degree, unlike closed-form cubic and quartic solutions
+/// that lose precision near multiple roots.
+pub fn roots_in_interval(coefficients: &[f64], lo: f64, hi: f64) -> Vec<f64> {
+    let scale = coefficients.iter().fold(0.0f64, |max, c| max.max(c.abs()));
+    let mut degree = coefficients.len();
+    while degree > 0 && coefficients[degree - 1].abs() <= scale * 1e-12 {
+        degree -= 1;
+    }
+    let coefficients = &coefficients[..degree];
+    if degree < 2 || lo > hi {
+        return vec![];
+    }
+    if degree == 2 {
+        let root = -coefficients[0] / coefficients[1];
+        return if root >= lo && root <= hi { vec![root] } else { vec![] };
+    }
+    if degree == 3 {
+        let mut roots = vec![];
+        if let Some((r0, r1)) = solve_quadratic(coefficients[2], coefficients[1], coefficients[0]) {
+            roots.extend([r0, r1].iter().filter(|r| **r >= lo && **r <= hi));
+            roots.dedup();
+        }
+        return roots;
+    }
+
+    let mut bounds = vec![lo];
+    bounds.extend(roots_in_interval(&derivative(coefficients), lo, hi));
+    bounds.push(hi);
+
+    // exact zeros at the bounds are taken as they are, so a root shared by
+    // two neighbouring pieces is only reported once
+    let mut roots = vec![];
+    for window in bounds.windows(2) {
+        let (a, b) = (window[0], window[1]);
+        let (fa, fb) = (evaluate(coefficients, a), evaluate(coefficients, b));
+        if fa == 0.0 {
+            roots.push(a);
+        } else if fb != 0.0 && (fa < 0.0) != (fb < 0.0) {
+            roots.push(refine_root(coefficients, a, b, fa));
+        }
+    }
+    if evaluate(coefficients, hi) == 0.0 {
+        roots.push(hi);
+    }
+    roots.dedup();
+    roots
+}
+
+/// Newton iterations that fall back to bisection whenever a step leaves the
+/// bracket [a, b], which is known to hold exactly one root.
+fn refine_root(coefficients: &[f64], mut a: f64, mut b: f64, fa: f64) -> f64 {
+    let derivative = derivative(coefficients);
+    let mut x = 0.5 * (a + b);
+    for _ in 0..64 {
+        let fx = evaluate(coefficients, x);
+        if fx == 0.0 {
+            return x;
+        }
+        if (fx < 0.0) == (fa < 0.0) {
+            a = x;
+        } else {
+            b = x;
+        }
+        let slope = evaluate(&derivative, x);
+        let newton = x - fx / slope;
+        let next = if slope != 0.0 && newton > a && newton < b {
+            newton
+        } else {
+            0.5 * (a + b)
+        };
+        if (next - x).abs() <= 1e-12 * (1.0 + x.abs()) {
+            return next;
+        }
+        x = next;
+    }
+    x
+}
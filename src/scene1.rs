@@ -1,7 +1,7 @@
 use crate::{
     hitable_list::{self, HitableList},
     material::{self, Material},
-    sphere, triangle,
+    quad, sphere,
     vec3::Vec3,
 };
 
@@ -27,32 +27,14 @@ pub fn create_scene() -> (HitableList, f32) {
         }),
     )));
 
-    fn add_parallelogram(
-        hitables: &mut HitableList,
-        a: Vec3,
-        b: Vec3,
-        c: Vec3,
-        material: Material,
-    ) {
-        hitables.add(Box::new(triangle::Triangle::new(a, b, c, material.clone())));
-        hitables.add(Box::new(triangle::Triangle::new(
-            a,
-            c,
-            c.add(&a.sub(&b)),
-            material,
-        )));
-    }
-
-    add_parallelogram(
-        &mut world,
+    world.add(Box::new(quad::Quad::from_points(
         Vec3::new(-1.0, -0.5, -2.0),
         Vec3::new(-1.0, -0.5, 2.0),
         Vec3::new(1.0, -0.5, 2.0),
         Material::Diffuse(Vec3::new(0.8, 0.8, 0.8)),
-    );
+    )));
 
-    add_parallelogram(
-        &mut world,
+    world.add(Box::new(quad::Quad::from_points(
         Vec3::new(0.5, -0.5, -1.25),
         Vec3::new(-0.5, -0.5, -1.25),
         Vec3::new(-0.5, 0.5, -1.25),
@@ -61,17 +43,15 @@ pub fn create_scene() -> (HitableList, f32) {
             Box::new(Material::Diffuse(Vec3::new(0.0, 0.0, 0.0))),
             15.0,
         ),
-    );
-    add_parallelogram(
-        &mut world,
+    )));
+    world.add(Box::new(quad::Quad::from_points(
         Vec3::new(0.5, 0.5, -1.25),
         Vec3::new(-0.5, 0.5, -1.25),
         Vec3::new(-0.5, 0.5, -0.75),
         Material::Diffuse(Vec3::new(0.8, 0.8, 0.8)),
-    );
+    )));
     // right wall
-    add_parallelogram(
-        &mut world,
+    world.add(Box::new(quad::Quad::from_points(
         Vec3::new(0.5, -0.5, -1.25),
         Vec3::new(0.5, -0.5, -0.75),
         Vec3::new(0.5, 0.5, -0.75),
@@ -80,10 +60,9 @@ pub fn create_scene() -> (HitableList, f32) {
             fuzz: 0.2,
             diffuse: false,
         }),
-    );
+    )));
     // left wall
-    add_parallelogram(
-        &mut world,
+    world.add(Box::new(quad::Quad::from_points(
         Vec3::new(-0.5, -0.5, -1.25),
         Vec3::new(-0.5, -0.5, -0.75),
         Vec3::new(-0.5, 0.5, -0.75),
@@ -92,7 +71,7 @@ pub fn create_scene() -> (HitableList, f32) {
             fuzz: 0.5,
             diffuse: false,
         }),
-    );
+    )));
 
     (world, 1.6)
 }
@@ -0,0 +1,117 @@
+use std::f32::consts::PI;
+
+use crate::{
+    hitable_list::{self, HitableList},
+    material::{self, Material},
+    mesh::TriangleMesh,
+    plane, ply, sphere, stl,
+    texture::Texture,
+    transform::Transform,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-1.0, 1.5, -0.5),
+        0.5,
+        Material::Light(Vec3::new(1.0, 0.95, 0.9).multiply(4.0)),
+    )));
+
+    // the same knot smooth shaded and with flat facets
+    world.add(Box::new(
+        torus_knot(
+            Vec3::new(-0.4, -0.36, -1.6),
+            0.1,
+            160,
+            16,
+            Material::Metallic(material::Metallic {
+                albedo: Vec3::new(0.95, 0.75, 0.35),
+                fuzz: 0.1,
+                diffuse: false,
+            }),
+        )
+        .with_smooth_normals(),
+    ));
+
+    world.add(Box::new(torus_knot(
+        Vec3::new(0.4, -0.36, -1.6),
+        0.1,
+        48,
+        6,
+        Material::Diffuse(Vec3::new(0.3, 0.5, 0.8)),
+    )));
+
+    // scanned-style PLY with vertex colours and a CAD part from STL
+    let ball = ply::load("models/icosphere.ply", Material::Textured(Texture::VertexColor)).unwrap();
+    world.add(Box::new(ball.transformed(
+        &Transform::translate(Vec3::new(0.0, -0.35, -1.2)).multiply(&Transform::scale(Vec3::new(0.15, 0.15, 0.15))),
+    )));
+
+    let bracket = stl::load(
+        "models/bracket.stl",
+        Material::Metallic(material::Metallic {
+            albedo: Vec3::new(0.8, 0.8, 0.85),
+            fuzz: 0.2,
+            diffuse: false,
+        }),
+    )
+    .unwrap();
+    world.add(Box::new(
+        bracket.transformed(
+            &Transform::translate(Vec3::new(0.35, -0.5, -1.1))
+                .multiply(&Transform::rotate(Vec3::new(0.0, 1.0, 0.0), -30.0))
+                .multiply(&Transform::scale(Vec3::new(0.2, 0.2, 0.2))),
+        ),
+    ));
+
+    (world, 1.5)
+}
+
+/// Tube around a (2, 3) torus knot.
+fn torus_knot(center: Vec3, scale: f32, segments: usize, sides: usize, material: Material) -> TriangleMesh {
+    let curve = |phi: f32| {
+        let r = (3.0 * phi).cos() + 2.0;
+        Vec3::new(r * (2.0 * phi).cos(), -(3.0 * phi).sin(), r * (2.0 * phi).sin())
+    };
+
+    let mut positions = vec![];
+    let mut uvs = vec![];
+    for i in 0..segments {
+        let phi = 2.0 * PI * i as f32 / segments as f32;
+        let point = curve(phi);
+        let tangent = curve(phi + 1e-3).sub(&curve(phi - 1e-3)).normalize();
+        let normal = tangent.cross(&Vec3::new(0.0, 1.0, 0.0)).normalize();
+        let binormal = tangent.cross(&normal);
+        for j in 0..sides {
+            let theta = 2.0 * PI * j as f32 / sides as f32;
+            let offset = normal.multiply(theta.cos()).add(&binormal.multiply(theta.sin())).multiply(0.4);
+            positions.push(center.add(&point.add(&offset).multiply(scale)));
+            uvs.push((i as f32 / segments as f32, j as f32 / sides as f32));
+        }
+    }
+
+    let mut indices = vec![];
+    for i in 0..segments {
+        for j in 0..sides {
+            let a = i * sides + j;
+            let b = (i + 1) % segments * sides + j;
+            let c = (i + 1) % segments * sides + (j + 1) % sides;
+            let d = i * sides + (j + 1) % sides;
+            indices.push([a, b, c]);
+            indices.push([a, c, d]);
+        }
+    }
+    TriangleMesh::new(positions, indices, material).with_uvs(uvs)
+}
@@ -0,0 +1,80 @@
+use noise::{Fbm, MultiFractal, Perlin, RidgedMulti};
+
+use crate::{
+    heightfield::Heightfield,
+    hitable_list::{self, HitableList},
+    material::{self, Material},
+    plane, sphere,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    // rolling hills around a lake, mountains behind and an island from a heightmap
+    let hills = Fbm::<Perlin>::new(7).set_octaves(6).set_persistence(0.45);
+    world.add_named(
+        "hills",
+        Box::new(Heightfield::from_noise(
+            &hills,
+            0.6,
+            513,
+            Vec3::new(-4.0, -0.75, -6.0),
+            8.0,
+            6.5,
+            0.7,
+            Material::Diffuse(Vec3::new(0.35, 0.5, 0.25)),
+        )),
+    );
+
+    let mountains = RidgedMulti::<Perlin>::new(3).set_octaves(7).set_frequency(1.0);
+    world.add_named(
+        "mountains",
+        Box::new(Heightfield::from_noise(
+            &mountains,
+            0.35,
+            513,
+            Vec3::new(-12.0, -0.8, -20.0),
+            24.0,
+            14.0,
+            2.5,
+            Material::Diffuse(Vec3::new(0.55, 0.5, 0.48)),
+        )),
+    );
+
+    world.add_named(
+        "island",
+        Box::new(
+            Heightfield::from_image(
+                "models/island.png",
+                Vec3::new(-0.6, -0.45, -3.2),
+                1.2,
+                1.2,
+                0.4,
+                Material::Diffuse(Vec3::new(0.6, 0.5, 0.35)),
+            )
+            .unwrap(),
+        ),
+    );
+
+    world.add_named(
+        "lake",
+        Box::new(plane::Plane::new(
+            Vec3::new(0.0, -0.35, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Material::Metallic(material::Metallic {
+                albedo: Vec3::new(0.5, 0.6, 0.7),
+                fuzz: 0.03,
+                diffuse: false,
+            }),
+        )),
+    );
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-6.0, 6.0, -12.0),
+        2.0,
+        Material::Light(Vec3::new(1.0, 0.9, 0.75).multiply(6.0)),
+    )));
+
+    (world, 1.5)
+}
@@ -0,0 +1,106 @@
+use crate::{
+    bezier,
+    hitable_list::{self, HitableList},
+    material::{self, Material},
+    mesh::TriangleMesh,
+    plane, sphere,
+    transform::Transform,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-1.0, 1.5, -0.5),
+        0.5,
+        Material::Light(Vec3::new(1.0, 0.95, 0.9).multiply(4.0)),
+    )));
+
+    let teapot = bezier::tessellate(
+        &bezier::teapot(),
+        0.002,
+        Material::Metallic(material::Metallic {
+            albedo: Vec3::new(0.9, 0.9, 0.95),
+            fuzz: 0.05,
+            diffuse: false,
+        }),
+    );
+    world.add_named(
+        "teapot",
+        Box::new(
+            teapot.transformed(
+                &Transform::translate(Vec3::new(0.0, -0.5, -1.7))
+                    .multiply(&Transform::rotate(Vec3::new(0.0, 1.0, 0.0), -20.0))
+                    .multiply(&Transform::scale(Vec3::new(0.16, 0.16, 0.16))),
+            ),
+        ),
+    );
+
+    // a box cage as it is and as its subdivision surface
+    let cage = cage(Material::Diffuse(Vec3::new(0.8, 0.35, 0.3)));
+    world.add(Box::new(cage.transformed(
+        &Transform::translate(Vec3::new(-0.6, -0.35, -1.3))
+            .multiply(&Transform::rotate(Vec3::new(0.0, 1.0, 0.0), 70.0))
+            .multiply(&Transform::scale(Vec3::new(0.15, 0.15, 0.15))),
+    )));
+    world.add(Box::new(cage.subdivided(4).transformed(
+        &Transform::translate(Vec3::new(0.6, -0.35, -1.3))
+            .multiply(&Transform::rotate(Vec3::new(0.0, 1.0, 0.0), -70.0))
+            .multiply(&Transform::scale(Vec3::new(0.15, 0.15, 0.15))),
+    )));
+
+    (world, 1.5)
+}
+
+/// Cube from -1 to 1 with one face pulled out into a snout.
+fn cage(material: Material) -> TriangleMesh {
+    let positions = vec![
+        Vec3::new(-1.0, -1.0, -1.0),
+        Vec3::new(1.0, -1.0, -1.0),
+        Vec3::new(1.0, 1.0, -1.0),
+        Vec3::new(-1.0, 1.0, -1.0),
+        Vec3::new(-1.0, -1.0, 1.0),
+        Vec3::new(1.0, -1.0, 1.0),
+        Vec3::new(1.0, 1.0, 1.0),
+        Vec3::new(-1.0, 1.0, 1.0),
+        Vec3::new(-0.4, -0.4, 2.0),
+        Vec3::new(0.4, -0.4, 2.0),
+        Vec3::new(0.4, 0.4, 2.0),
+        Vec3::new(-0.4, 0.4, 2.0),
+    ];
+    let indices = vec![
+        [0, 2, 1],
+        [0, 3, 2],
+        [0, 1, 5],
+        [0, 5, 4],
+        [1, 2, 6],
+        [1, 6, 5],
+        [2, 3, 7],
+        [2, 7, 6],
+        [3, 0, 4],
+        [3, 4, 7],
+        // snout around the front face
+        [4, 5, 9],
+        [4, 9, 8],
+        [5, 6, 10],
+        [5, 10, 9],
+        [6, 7, 11],
+        [6, 11, 10],
+        [7, 4, 8],
+        [7, 8, 11],
+        [8, 9, 10],
+        [8, 10, 11],
+    ];
+    TriangleMesh::new(positions, indices, material)
+}
@@ -0,0 +1,105 @@
+use rand::{Rng, SeedableRng};
+
+use crate::{
+    curve::{CurveBasis, CurveSegment, CurveShape, Curves},
+    hair::Hair,
+    hitable_list::{self, HitableList},
+    material::{self, Material},
+    plane, sphere,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-1.0, 1.5, -0.5),
+        0.5,
+        Material::Light(Vec3::new(1.0, 0.95, 0.9).multiply(4.0)),
+    )));
+
+    let mut seed = [0u8; 32];
+    seed[0] = 0x41;
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed(seed);
+
+    // two furry balls, blond and dyed red
+    for (center, hair, skin) in [
+        (Vec3::new(-0.4, -0.28, -1.5), Hair::from_melanin(0.3, 0.1), Vec3::new(0.6, 0.45, 0.3)),
+        (Vec3::new(0.4, -0.28, -1.5), Hair::from_color(Vec3::new(0.6, 0.12, 0.08), 0.3), Vec3::new(0.3, 0.05, 0.03)),
+    ] {
+        let radius = 0.14;
+        world.add(Box::new(sphere::Sphere::new(
+            center,
+            radius,
+            Material::Diffuse(skin),
+        )));
+        let mut segments = vec![];
+        for _ in 0..20000 {
+            let z: f32 = rng.random_range(-1.0..1.0);
+            let phi = rng.random_range(0.0..std::f32::consts::TAU);
+            let ring = (1.0 - z * z).sqrt();
+            let normal = Vec3::new(ring * phi.cos(), ring * phi.sin(), z);
+            let length = rng.random_range(0.06..0.08);
+            // strands comb downward under their own weight
+            let mut points = vec![];
+            let mut point = center.add(&normal.multiply(radius * 0.98));
+            let mut direction = normal;
+            for _ in 0..5 {
+                points.push(point);
+                direction = direction.add(&Vec3::new(0.0, -0.35, 0.0)).normalize();
+                point = point.add(&direction.multiply(length / 4.0));
+            }
+            segments.extend(CurveSegment::strand(&points, CurveBasis::BSpline, 0.0025, 0.0008));
+        }
+        world.add(Box::new(Curves::new(segments, CurveShape::Cylinder, Material::Hair(hair))));
+    }
+
+    // a lawn of grass blades in front
+    let mut blades = vec![];
+    for _ in 0..6000 {
+        let root = Vec3::new(rng.random_range(-1.0..1.0), -0.5, rng.random_range(-1.3..-0.9));
+        let height = rng.random_range(0.05..0.12);
+        let lean = Vec3::new(rng.random_range(-0.04..0.04), 0.0, rng.random_range(-0.04..0.04));
+        let control = [
+            root,
+            root.add(&Vec3::new(0.0, height * 0.5, 0.0)),
+            root.add(&lean.multiply(0.5)).add(&Vec3::new(0.0, height, 0.0)),
+            root.add(&lean).add(&Vec3::new(0.0, height * 0.9, 0.0)),
+        ];
+        blades.extend(CurveSegment::strand(&control, CurveBasis::Bezier, 0.006, 0.0005));
+    }
+    world.add(Box::new(Curves::new(
+        blades,
+        CurveShape::Ribbon,
+        Material::Diffuse(Vec3::new(0.25, 0.55, 0.15)),
+    )));
+
+    // scene5's spiral as a single strand instead of a row of spheres
+    let spiral: Vec<Vec3> = (0..150)
+        .map(|i| {
+            let angle = i as f32 * 0.1;
+            Vec3::new(angle.sin() * 0.6, i as f32 * 0.005 - 0.3, angle.cos() * 0.6 - 2.4)
+        })
+        .collect();
+    world.add(Box::new(Curves::new(
+        CurveSegment::strand(&spiral, CurveBasis::BSpline, 0.03, 0.03),
+        CurveShape::Cylinder,
+        Material::Metallic(material::Metallic {
+            albedo: Vec3::new(0.9, 0.7, 0.4),
+            fuzz: 0.1,
+            diffuse: false,
+        }),
+    )));
+
+    (world, 1.5)
+}
@@ -0,0 +1,75 @@
+use noise::{Fbm, MultiFractal, Perlin};
+
+use crate::{
+    hitable_list::{self, HitableList},
+    material::Material,
+    mesh::TriangleMesh,
+    plane, ply, sphere,
+    texture::ScalarTexture,
+    transform::Transform,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-1.0, 1.5, -0.5),
+        0.5,
+        Material::Light(Vec3::new(1.0, 0.95, 0.9).multiply(4.0)),
+    )));
+
+    // a rock from the icosphere and noise
+    let rock_noise = ScalarTexture::Noise {
+        noise: Box::new(Fbm::<Perlin>::new(5).set_octaves(6)),
+        frequency: 1.5,
+    };
+    let rock = ply::load("models/icosphere.ply", Material::Diffuse(Vec3::new(0.45, 0.42, 0.4)))
+        .unwrap()
+        .displaced(&rock_noise, 0.25, 0.03)
+        .unwrap();
+    world.add_named(
+        "rock",
+        Box::new(rock.transformed(
+            &Transform::translate(Vec3::new(-0.35, -0.32, -1.5))
+                .multiply(&Transform::scale(Vec3::new(0.22, 0.16, 0.22))),
+        )),
+    );
+
+    // a two triangle tile, stood up and raised into relief by a heightmap
+    let tile = TriangleMesh::new(
+        vec![
+            Vec3::new(-1.0, 0.0, -1.0),
+            Vec3::new(1.0, 0.0, -1.0),
+            Vec3::new(1.0, 0.0, 1.0),
+            Vec3::new(-1.0, 0.0, 1.0),
+        ],
+        vec![[0, 2, 1], [0, 3, 2]],
+        Material::Diffuse(Vec3::new(0.7, 0.55, 0.4)),
+    )
+    .with_uvs(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]);
+    let relief = tile
+        .displaced(&ScalarTexture::load("models/island.png").unwrap(), 1.5, 0.02)
+        .unwrap();
+    world.add_named(
+        "relief",
+        Box::new(relief.transformed(
+            &Transform::translate(Vec3::new(0.4, -0.26, -1.7))
+                .multiply(&Transform::rotate(Vec3::new(0.0, 1.0, 0.0), -40.0))
+                .multiply(&Transform::rotate(Vec3::new(1.0, 0.0, 0.0), 60.0))
+                .multiply(&Transform::scale(Vec3::new(0.25, 0.25, 0.25))),
+        )),
+    );
+
+    (world, 1.5)
+}
@@ -0,0 +1,88 @@
+use noise::{Fbm, MultiFractal, Perlin};
+
+use crate::{
+    hitable_list::{self, HitableList},
+    material::{self, Material},
+    plane, quad, sphere,
+    texture::{NormalMap, ScalarTexture},
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-1.0, 1.5, -0.5),
+        0.5,
+        Material::Light(Vec3::new(1.0, 0.95, 0.9).multiply(4.0)),
+    )));
+
+    let rivets = NormalMap::load("models/rivets_normal.png").unwrap();
+
+    // riveted panel behind, all detail from the normal map
+    world.add(Box::new(quad::Quad::new(
+        Vec3::new(-1.0, -0.5, -2.4),
+        Vec3::new(2.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::NormalMapped {
+            material: Box::new(Material::Metallic(material::Metallic {
+                albedo: Vec3::new(0.7, 0.7, 0.75),
+                fuzz: 0.3,
+                diffuse: true,
+            })),
+            map: rivets.clone(),
+            strength: 1.0,
+        },
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-0.6, -0.25, -1.5),
+        0.25,
+        Material::NormalMapped {
+            material: Box::new(Material::Diffuse(Vec3::new(0.7, 0.3, 0.2))),
+            map: rivets,
+            strength: 1.0,
+        },
+    )));
+
+    // hammered metal from bumpy noise
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(0.0, -0.25, -1.5),
+        0.25,
+        Material::BumpMapped {
+            material: Box::new(Material::Metallic(material::Metallic {
+                albedo: Vec3::new(0.95, 0.75, 0.4),
+                fuzz: 0.02,
+                diffuse: false,
+            })),
+            bump: ScalarTexture::Noise {
+                noise: Box::new(Fbm::<Perlin>::new(11).set_octaves(3)),
+                frequency: 25.0,
+            },
+            scale: 0.004,
+        },
+    )));
+
+    // the island heightmap pressed into a plain sphere
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(0.6, -0.25, -1.5),
+        0.25,
+        Material::BumpMapped {
+            material: Box::new(Material::Diffuse(Vec3::new(0.3, 0.5, 0.7))),
+            bump: ScalarTexture::load("models/island.png").unwrap(),
+            scale: 0.01,
+        },
+    )));
+
+    (world, 1.5)
+}
@@ -0,0 +1,113 @@
+use std::sync::Arc;
+
+use rand::{Rng, SeedableRng};
+
+use crate::{
+    hitable_list::{self, HitableList},
+    material::{self, Material},
+    mesh::TriangleMesh,
+    plane, quad, sphere,
+    texture::{self, ScalarTexture, Texture},
+    triangle::Triangle,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-1.0, 1.5, -0.5),
+        0.5,
+        Material::Light(Vec3::new(1.0, 0.95, 0.9).multiply(4.0)),
+    )));
+
+    // chain-link fence, a single quad with the wire cut out of it
+    let fence = Material::Metallic(material::Metallic {
+        albedo: Vec3::new(0.6, 0.62, 0.65),
+        fuzz: 0.4,
+        diffuse: false,
+    });
+    let chain_link = ScalarTexture::load("models/chain_link.png").unwrap();
+    world.add(Box::new(
+        quad::Quad::new(
+            Vec3::new(-1.2, -0.5, -2.0),
+            Vec3::new(2.4, 0.0, 0.0),
+            Vec3::new(0.0, 0.9, 0.0),
+            fence.clone(),
+        )
+        .with_alpha(chain_link.clone()),
+    ));
+
+    // a ball behind the fence to look at through it
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(0.5, -0.25, -2.6),
+        0.25,
+        Material::Diffuse(Vec3::new(0.7, 0.3, 0.2)),
+    )));
+
+    // a triangular offcut of the fencing propped up behind the bushes
+    world.add(Box::new(
+        Triangle::new(
+            Vec3::new(0.3, -0.5, -1.9),
+            Vec3::new(0.95, -0.5, -1.8),
+            Vec3::new(0.62, 0.15, -1.85),
+            fence,
+        )
+        .with_alpha(chain_link),
+    ));
+
+    // bushes of leaf sprig cards
+    let leaf = Arc::new(texture::srgb_to_linear(&image::open("models/leaf.png").unwrap()));
+    let leaf_alpha = ScalarTexture::load_alpha("models/leaf.png").unwrap();
+    let mut seed = [0u8; 32];
+    seed[0] = 0x44;
+    let mut rng = rand_chacha::ChaCha8Rng::from_seed(seed);
+    for (center, cards) in [(Vec3::new(-0.5, -0.5, -1.5), 400), (Vec3::new(0.25, -0.5, -1.3), 150)] {
+        let mut positions = vec![];
+        let mut uvs = vec![];
+        let mut indices = vec![];
+        for _ in 0..cards {
+            let up = Vec3::new(
+                rng.random_range(-1.0..1.0),
+                rng.random_range(0.3..1.0),
+                rng.random_range(-1.0..1.0),
+            )
+            .normalize();
+            let (b1, b2) = up.orthonormal_basis();
+            let turn = rng.random_range(0.0..std::f32::consts::TAU);
+            let side = b1.multiply(turn.cos()).add(&b2.multiply(turn.sin()));
+            let root = center.add(&up.multiply(rng.random_range(0.0..0.12)));
+            let length = rng.random_range(0.2..0.3);
+            let half_width = side.multiply(length / 2.0);
+            let tip = root.add(&up.multiply(length));
+            let first = positions.len();
+            positions.extend([root.sub(&half_width), root.add(&half_width), tip.add(&half_width), tip.sub(&half_width)]);
+            uvs.extend([(0.0, 1.0), (1.0, 1.0), (1.0, 0.0), (0.0, 0.0)]);
+            indices.extend([[first, first + 1, first + 2], [first, first + 2, first + 3]]);
+        }
+        world.add(Box::new(
+            TriangleMesh::new(
+                positions,
+                indices,
+                Material::Textured(Texture::Image {
+                    image: leaf.clone(),
+                    tint: Vec3::new(1.0, 1.0, 1.0),
+                }),
+            )
+            .with_uvs(uvs)
+            .with_alpha(leaf_alpha.clone()),
+        ));
+    }
+
+    (world, 1.5)
+}
@@ -0,0 +1,54 @@
+use crate::{
+    conductor::{self, Conductor},
+    hitable_list::{self, HitableList},
+    material::Material,
+    plane, sphere,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-1.0, 1.5, -0.5),
+        0.5,
+        Material::Light(Vec3::new(1.0, 0.95, 0.9).multiply(4.0)),
+    )));
+
+    // measured metals in front, the aluminium brushed around the poles
+    let metals = [
+        Conductor::measured(conductor::GOLD, 0.2, 0.0),
+        Conductor::measured(conductor::COPPER, 0.3, 0.0),
+        Conductor::measured(conductor::SILVER, 0.05, 0.0),
+        Conductor::measured(conductor::ALUMINIUM, 0.4, 0.9),
+    ];
+    for (i, metal) in metals.into_iter().enumerate() {
+        world.add(Box::new(sphere::Sphere::new(
+            Vec3::new(-0.75 + i as f32 * 0.5, -0.3, -1.4),
+            0.2,
+            Material::Conductor(metal),
+        )));
+    }
+
+    // gold from polished to fully rough, which stays as bright thanks to
+    // the multiple scattering compensation
+    for i in 0..5 {
+        world.add(Box::new(sphere::Sphere::new(
+            Vec3::new(-1.0 + i as f32 * 0.5, -0.3, -2.4),
+            0.2,
+            Material::Conductor(Conductor::measured(conductor::GOLD, i as f32 * 0.25, 0.0)),
+        )));
+    }
+
+    (world, 1.5)
+}
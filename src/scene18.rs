@@ -0,0 +1,66 @@
+use crate::{
+    hitable_list::{self, HitableList},
+    material::Material,
+    plane,
+    principled::Principled,
+    sphere,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-1.0, 1.5, -0.5),
+        0.5,
+        Material::Light(Vec3::new(1.0, 0.95, 0.9).multiply(4.0)),
+    )));
+
+    let materials = [
+        // plastic, brushed steel and car paint in the back row
+        Principled::new(Vec3::new(0.1, 0.3, 0.7), 0.0, 0.2),
+        Principled {
+            anisotropy: 0.8,
+            ..Principled::new(Vec3::new(0.75, 0.75, 0.78), 1.0, 0.4)
+        },
+        Principled {
+            clearcoat: 1.0,
+            clearcoat_roughness: 0.02,
+            ..Principled::new(Vec3::new(0.5, 0.02, 0.03), 0.6, 0.5)
+        },
+        // velvet, frosted glass and wax in front
+        Principled {
+            sheen: 1.0,
+            sheen_tint: 0.5,
+            ..Principled::new(Vec3::new(0.25, 0.05, 0.3), 0.0, 1.0)
+        },
+        Principled {
+            transmission: 1.0,
+            ..Principled::new(Vec3::new(0.85, 0.95, 0.9), 0.0, 0.15)
+        },
+        Principled {
+            subsurface: 1.0,
+            ..Principled::new(Vec3::new(0.9, 0.8, 0.6), 0.0, 0.6)
+        },
+    ];
+    for (i, material) in materials.into_iter().enumerate() {
+        let (row, column) = (i / 3, i % 3);
+        world.add(Box::new(sphere::Sphere::new(
+            Vec3::new(-0.6 + column as f32 * 0.6, -0.28, -2.2 + row as f32 * 0.8),
+            0.22,
+            Material::Principled(Box::new(material)),
+        )));
+    }
+
+    (world, 1.5)
+}
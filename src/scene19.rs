@@ -0,0 +1,75 @@
+use noise::{Fbm, MultiFractal, Perlin};
+
+use crate::{
+    conductor::{self, Conductor},
+    hitable_list::{self, HitableList},
+    layered::Layered,
+    material::{Material, MixWeight},
+    plane, sphere,
+    texture::ScalarTexture,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-1.0, 1.5, -0.5),
+        0.5,
+        Material::Light(Vec3::new(1.0, 0.95, 0.9).multiply(4.0)),
+    )));
+
+    // two browns blotched together by noise under amber varnish
+    let wood = Material::Mix {
+        first: Box::new(Material::Diffuse(Vec3::new(0.55, 0.35, 0.18))),
+        second: Box::new(Material::Diffuse(Vec3::new(0.3, 0.16, 0.07))),
+        weight: MixWeight::Texture(ScalarTexture::Noise {
+            noise: Box::new(Fbm::<Perlin>::new(3).set_octaves(3)),
+            frequency: 12.0,
+        }),
+    };
+    let materials = [
+        Layered::varnish(wood, 1.5, 0.05, Vec3::new(0.9, 0.75, 0.5)),
+        // dust on slightly tarnished gold
+        Layered::dust(
+            Material::Mix {
+                first: Box::new(Material::Conductor(Conductor::measured(conductor::GOLD, 0.1, 0.0))),
+                second: Box::new(Material::Diffuse(Vec3::new(0.2, 0.15, 0.05))),
+                weight: MixWeight::Constant(0.2),
+            },
+            0.7,
+            Vec3::new(0.55, 0.5, 0.45),
+        ),
+        Layered::varnish(Material::Diffuse(Vec3::new(0.7, 0.08, 0.05)), 1.5, 0.0, Vec3::new(1.0, 1.0, 1.0)),
+    ];
+    for (i, material) in materials.into_iter().enumerate() {
+        world.add(Box::new(sphere::Sphere::new(
+            Vec3::new(-0.75 + i as f32 * 0.5, -0.3, -1.4),
+            0.2,
+            Material::Layered(Box::new(material.unwrap())),
+        )));
+    }
+
+    // glazed pottery: blue clay that turns into a mirror at grazing angles
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(0.75, -0.3, -1.4),
+        0.2,
+        Material::Mix {
+            first: Box::new(Material::Diffuse(Vec3::new(0.05, 0.15, 0.45))),
+            second: Box::new(Material::Conductor(Conductor::measured(conductor::SILVER, 0.05, 0.0))),
+            weight: MixWeight::Fresnel(1.5),
+        },
+    )));
+
+    (world, 1.5)
+}
@@ -3,13 +3,13 @@ use rand::{Rng, SeedableRng};
 use crate::{
     hitable_list::{self, HitableList},
     material::{self, Material},
-    sphere, triangle,
+    plane, sphere,
     vec3::Vec3,
 };
 
 pub fn create_scene() -> (HitableList, f32) {
     let mut world = hitable_list::HitableList::new();
-    world.add(Box::new(sphere::Sphere::new(
+    world.add_named("lantern", Box::new(sphere::Sphere::new(
         Vec3::new(0.3, -0.3, -1.0),
         0.2,
         Material::CheckerBoard(
@@ -90,33 +90,9 @@ pub fn create_scene() -> (HitableList, f32) {
         )));
     }
 
-    fn add_parallelogram(
-        hitables: &mut HitableList,
-        a: Vec3,
-        b: Vec3,
-        c: Vec3,
-        material: Material,
-    ) {
-        hitables.add(Box::new(triangle::Triangle::new(a, b, c, material.clone())));
-        hitables.add(Box::new(triangle::Triangle::new(
-            a,
-            c,
-            c.add(&a.sub(&b)),
-            material.clone(),
-        )));
-    }
-
-    add_parallelogram(
-        &mut world,
-        Vec3::new(-1.0, 0.0, -2.0)
-            .multiply(500.0)
-            .sub(&Vec3::new(0.0, 0.5, 0.0)),
-        Vec3::new(-1.0, 0.0, 2.0)
-            .multiply(500.0)
-            .sub(&Vec3::new(0.0, 0.5, 0.0)),
-        Vec3::new(1.0, 0.0, 2.0)
-            .multiply(500.0)
-            .sub(&Vec3::new(0.0, 0.5, 0.0)),
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
         Material::CheckerBoard(
             Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
             Box::new(Material::Metallic(material::Metallic {
@@ -126,7 +102,7 @@ pub fn create_scene() -> (HitableList, f32) {
             })),
             5.0,
         ),
-    );
+    )));
 
     (world, 0.9)
 }
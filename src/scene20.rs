@@ -0,0 +1,91 @@
+use crate::{
+    hitable_list::{self, HitableList},
+    material::Material,
+    plane, ply,
+    quadric::Quadric,
+    sphere,
+    subsurface::Subsurface,
+    transform::Transform,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-1.0, 1.5, -0.5),
+        0.5,
+        Material::Light(Vec3::new(1.0, 0.95, 0.9).multiply(4.0)),
+    )));
+
+    // a wax candle glowing around its flame
+    world.add(Box::new(
+        Quadric::cylinder(
+            Vec3::new(-0.6, -0.5, -1.5),
+            Vec3::new(0.0, 1.0, 0.0),
+            0.12,
+            0.45,
+            Material::Subsurface(Subsurface::new(
+                Vec3::new(0.95, 0.85, 0.7),
+                Vec3::new(0.08, 0.05, 0.03),
+                1.45,
+            )
+            .unwrap()),
+        )
+        .with_caps(),
+    ));
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-0.6, 0.0, -1.5),
+        0.03,
+        Material::Light(Vec3::new(1.0, 0.6, 0.2).multiply(20.0)),
+    )));
+
+    // marble and skin
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(0.0, -0.3, -1.4),
+        0.2,
+        Material::Subsurface(Subsurface::new(
+            Vec3::new(0.9, 0.9, 0.88),
+            Vec3::new(0.02, 0.02, 0.02),
+            1.5,
+        )
+        .unwrap()),
+    )));
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(0.6, -0.3, -1.6),
+        0.2,
+        Material::Subsurface(Subsurface::new(
+            Vec3::new(0.85, 0.6, 0.5),
+            Vec3::new(0.04, 0.015, 0.008),
+            1.4,
+        )
+        .unwrap()),
+    )));
+
+    // a jade ball from the icosphere scan
+    let jade = ply::load(
+        "models/icosphere.ply",
+        Material::Subsurface(Subsurface::new(
+            Vec3::new(0.3, 0.7, 0.4),
+            Vec3::new(0.05, 0.1, 0.06),
+            1.6,
+        )
+        .unwrap()),
+    )
+    .unwrap();
+    world.add(Box::new(jade.transformed(
+        &Transform::translate(Vec3::new(0.3, -0.4, -1.0)).multiply(&Transform::scale(Vec3::new(0.1, 0.1, 0.1))),
+    )));
+
+    (world, 1.5)
+}
@@ -0,0 +1,76 @@
+use noise::{Fbm, MultiFractal, Perlin};
+
+use crate::{
+    conductor::Conductor,
+    dielectric::Dielectric,
+    disk,
+    hitable_list::{self, HitableList},
+    material::Material,
+    plane, sphere,
+    texture::ScalarTexture,
+    thin_film::ThinFilm,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-1.0, 1.5, -0.5),
+        0.5,
+        Material::Light(Vec3::new(1.0, 0.95, 0.9).multiply(4.0)),
+    )));
+
+    let swirl = |seed, frequency| ScalarTexture::Noise {
+        noise: Box::new(Fbm::<Perlin>::new(seed).set_octaves(3)),
+        frequency,
+    };
+
+    // soap bubble: nothing inside but air, with the film swirling over it
+    let soap = ThinFilm::new(450.0, 1.33).with_thickness_texture(swirl(5, 4.0), 300.0);
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-0.45, -0.1, -1.5),
+        0.3,
+        Material::Dielectric(Dielectric::new(1.0, 0.0).with_film(soap)),
+    )));
+
+    // oil slick on a puddle over dark tarmac
+    world.add(Box::new(disk::Disk::new(
+        Vec3::new(0.3, -0.499, -1.1),
+        Vec3::new(0.0, 1.0, 0.0),
+        0.35,
+        Material::Diffuse(Vec3::new(0.03, 0.03, 0.03)),
+    )));
+    let oil = ThinFilm::new(350.0, 1.45).with_thickness_texture(swirl(6, 6.0), 250.0);
+    world.add(Box::new(disk::Disk::new(
+        Vec3::new(0.3, -0.49, -1.1),
+        Vec3::new(0.0, 1.0, 0.0),
+        0.33,
+        Material::Dielectric(Dielectric::new(1.33, 0.0).with_film(oil)),
+    )));
+
+    // anodised titanium: bronze, blue and purple as the oxide grows thicker
+    for (i, thickness) in [30.0, 50.0, 140.0].into_iter().enumerate() {
+        world.add(Box::new(sphere::Sphere::new(
+            Vec3::new(0.15 + i as f32 * 0.3, -0.35, -1.8),
+            0.15,
+            Material::Conductor(
+                // roughly titanium's optical constants
+                Conductor::new(Vec3::new(2.9, 2.6, 2.2), Vec3::new(3.9, 3.4, 3.0), 0.15, 0.0)
+                    .with_film(ThinFilm::new(thickness, 2.4)),
+            ),
+        )));
+    }
+
+    (world, 1.5)
+}
@@ -0,0 +1,72 @@
+use crate::{
+    dielectric::Dielectric,
+    hitable_list::{self, HitableList},
+    material::Material,
+    mesh::TriangleMesh,
+    plane, sphere,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-1.0, 1.5, -0.5),
+        0.5,
+        Material::Blackbody {
+            temperature: 5000.0,
+            strength: 4.0,
+        },
+    )));
+
+    // candle flame to blue sky, left to right
+    for (i, temperature) in [1900.0, 2700.0, 4000.0, 6500.0, 10000.0].into_iter().enumerate() {
+        world.add(Box::new(sphere::Sphere::new(
+            Vec3::new(-0.6 + i as f32 * 0.3, 0.0, -2.2),
+            0.08,
+            Material::Blackbody {
+                temperature,
+                strength: 1.0,
+            },
+        )));
+    }
+
+    // dense flint prism lying on its side, splitting the lights behind it
+    let flint = Material::Dielectric(Dielectric::new(1.7, 0.0).with_dispersion(20.0));
+    let (bottom, top, front, back, middle) = (-0.499, -0.24, -1.15, -1.45, -1.3);
+    let positions = [-0.5, 0.5]
+        .into_iter()
+        .flat_map(|x| {
+            [
+                Vec3::new(x, bottom, front),
+                Vec3::new(x, bottom, back),
+                Vec3::new(x, top, middle),
+            ]
+        })
+        .collect();
+    let indices = vec![
+        [0, 2, 1],
+        [3, 4, 5],
+        [0, 1, 4],
+        [0, 4, 3],
+        [0, 3, 5],
+        [0, 5, 2],
+        [1, 2, 5],
+        [1, 5, 4],
+    ];
+    world.add(Box::new(TriangleMesh::new(positions, indices, flint.clone())));
+
+    world.add(Box::new(sphere::Sphere::new(Vec3::new(0.75, -0.3, -1.5), 0.2, flint)));
+
+    (world, 1.5)
+}
@@ -3,7 +3,7 @@ use rand::{Rng, SeedableRng};
 use crate::{
     hitable_list::{self, HitableList},
     material::{self, Material},
-    sphere, triangle,
+    plane, sphere,
     vec3::Vec3,
 };
 
@@ -55,33 +55,9 @@ pub fn create_scene() -> (HitableList, f32) {
         )));
     }
 
-    fn add_parallelogram(
-        hitables: &mut HitableList,
-        a: Vec3,
-        b: Vec3,
-        c: Vec3,
-        material: Material,
-    ) {
-        hitables.add(Box::new(triangle::Triangle::new(a, b, c, material.clone())));
-        hitables.add(Box::new(triangle::Triangle::new(
-            a,
-            c,
-            c.add(&a.sub(&b)),
-            material.clone(),
-        )));
-    }
-
-    add_parallelogram(
-        &mut world,
-        Vec3::new(-1.0, 0.0, -2.0)
-            .multiply(500.0)
-            .sub(&Vec3::new(0.0, 0.5, 0.0)),
-        Vec3::new(-1.0, 0.0, 2.0)
-            .multiply(500.0)
-            .sub(&Vec3::new(0.0, 0.5, 0.0)),
-        Vec3::new(1.0, 0.0, 2.0)
-            .multiply(500.0)
-            .sub(&Vec3::new(0.0, 0.5, 0.0)),
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
         Material::CheckerBoard(
             Box::new(Material::Metallic(material::Metallic {
                 albedo: Vec3::new(1.0, 1.0, 1.0),
@@ -95,7 +71,7 @@ pub fn create_scene() -> (HitableList, f32) {
             })),
             5.0,
         ),
-    );
+    )));
 
     (world, 1.3)
 }
@@ -3,7 +3,7 @@ use rand::{Rng, SeedableRng};
 use crate::{
     hitable_list::{self, HitableList},
     material::{self, Material},
-    sphere, triangle,
+    plane, quad, sphere,
     vec3::Vec3,
 };
 
@@ -67,43 +67,18 @@ pub fn create_scene() -> (HitableList, f32) {
         }),
     )));
 
-    fn add_parallelogram(
-        hitables: &mut HitableList,
-        a: Vec3,
-        b: Vec3,
-        c: Vec3,
-        material: Material,
-    ) {
-        hitables.add(Box::new(triangle::Triangle::new(a, b, c, material.clone())));
-        hitables.add(Box::new(triangle::Triangle::new(
-            a,
-            c,
-            c.add(&a.sub(&b)),
-            material.clone(),
-        )));
-    }
-
-    add_parallelogram(
-        &mut world,
-        Vec3::new(-1.0, 0.0, -2.0)
-            .multiply(500.0)
-            .sub(&Vec3::new(0.0, 0.5, 0.0)),
-        Vec3::new(-1.0, 0.0, 2.0)
-            .multiply(500.0)
-            .sub(&Vec3::new(0.0, 0.5, 0.0)),
-        Vec3::new(1.0, 0.0, 2.0)
-            .multiply(500.0)
-            .sub(&Vec3::new(0.0, 0.5, 0.0)),
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
         Material::CheckerBoard(
             Box::new(Material::Diffuse(Vec3::new(1.0, 1.0, 1.0))),
             Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
             5.0,
         ),
-    );
+    )));
 
     // mirror left wall
-    add_parallelogram(
-        &mut world,
+    world.add(Box::new(quad::Quad::from_points(
         Vec3::new(-1.0, -0.5, -2.0),
         Vec3::new(-1.0, -0.5, 2.0),
         Vec3::new(-1.0, 3.0, 2.0),
@@ -112,11 +87,10 @@ pub fn create_scene() -> (HitableList, f32) {
             fuzz: 0.0,
             diffuse: false,
         }),
-    );
+    )));
 
     // left wall
-    add_parallelogram(
-        &mut world,
+    world.add(Box::new(quad::Quad::from_points(
         Vec3::new(1.0, -0.5, -2.0),
         Vec3::new(1.0, -0.5, 2.0),
         Vec3::new(1.0, 3.0, 2.0),
@@ -125,11 +99,10 @@ pub fn create_scene() -> (HitableList, f32) {
             fuzz: 0.0,
             diffuse: false,
         }),
-    );
+    )));
 
     // back wall
-    add_parallelogram(
-        &mut world,
+    world.add(Box::new(quad::Quad::from_points(
         Vec3::new(-1.0, -0.5, -2.0),
         Vec3::new(1.0, -0.5, -2.0),
         Vec3::new(1.0, 3.0, -2.0),
@@ -138,10 +111,9 @@ pub fn create_scene() -> (HitableList, f32) {
             fuzz: 0.0,
             diffuse: false,
         }),
-    );
+    )));
 
-    add_parallelogram(
-        &mut world,
+    world.add(Box::new(quad::Quad::from_points(
         Vec3::new(-1.0, -0.5, 0.0),
         Vec3::new(1.0, -0.5, 0.0),
         Vec3::new(1.0, 3.0, 0.0),
@@ -150,7 +122,7 @@ pub fn create_scene() -> (HitableList, f32) {
             fuzz: 0.0,
             diffuse: false,
         }),
-    );
+    )));
 
             
 
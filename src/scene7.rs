@@ -0,0 +1,145 @@
+use crate::{
+    aa_box, disk,
+    hitable_list::{self, HitableList},
+    implicit,
+    material::{self, Material},
+    plane, quad, quadric, sphere, torus, triangle,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    // back wall
+    world.add(Box::new(quad::Quad::new(
+        Vec3::new(-1.5, -0.5, -2.5),
+        Vec3::new(3.0, 0.0, 0.0),
+        Vec3::new(0.0, 2.0, 0.0),
+        Material::Diffuse(Vec3::new(0.7, 0.7, 0.7)),
+    )));
+
+    // round ceiling light
+    world.add(Box::new(disk::Disk::new(
+        Vec3::new(0.0, 1.2, -1.5),
+        Vec3::new(0.0, -1.0, 0.0),
+        0.4,
+        Material::Light(Vec3::new(1.0, 0.9, 0.8).multiply(8.0)),
+    )));
+
+    world.add(Box::new(aa_box::AaBox::new(
+        Vec3::new(-0.9, -0.5, -2.0),
+        Vec3::new(-0.4, 0.2, -1.5),
+        Material::Diffuse(Vec3::new(0.8, 0.3, 0.2)),
+    )));
+
+    world.add(Box::new(aa_box::OrientedBox::new(
+        Vec3::new(0.5, -0.3, -1.6),
+        Vec3::new(0.2, 0.2, 0.2),
+        Vec3::new(1.0, 0.0, 0.6),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::Metallic(material::Metallic {
+            albedo: Vec3::new(0.9, 0.9, 0.9),
+            fuzz: 0.05,
+            diffuse: false,
+        }),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(-0.65, 0.4, -1.75),
+        0.2,
+        Material::Metallic(material::Metallic {
+            albedo: Vec3::new(0.9, 0.8, 0.4),
+            fuzz: 0.2,
+            diffuse: false,
+        }),
+    )));
+
+    world.add(Box::new(triangle::Triangle::new(
+        Vec3::new(-0.1, -0.5, -2.2),
+        Vec3::new(0.3, -0.5, -2.2),
+        Vec3::new(0.1, 0.1, -2.3),
+        Material::Diffuse(Vec3::new(0.2, 0.6, 0.3)),
+    )));
+
+    // cut-open tube
+    world.add(Box::new(
+        quadric::Quadric::cylinder(
+            Vec3::new(1.0, -0.5, -2.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            0.15,
+            0.6,
+            Material::Metallic(material::Metallic {
+                albedo: Vec3::new(0.9, 0.7, 0.3),
+                fuzz: 0.1,
+                diffuse: false,
+            }),
+        )
+        .with_sweep(270.0)
+        .with_caps(),
+    ));
+
+    world.add(Box::new(
+        quadric::Quadric::cone(
+            Vec3::new(-0.3, -0.5, -1.25),
+            Vec3::new(0.0, 1.0, 0.0),
+            0.15,
+            0.4,
+            Material::Diffuse(Vec3::new(0.3, 0.4, 0.8)),
+        )
+        .with_caps(),
+    ));
+
+    // bowl
+    world.add(Box::new(quadric::Quadric::paraboloid(
+        Vec3::new(0.05, -0.5, -1.2),
+        Vec3::new(0.0, 1.0, 0.0),
+        0.15,
+        0.15,
+        Material::Diffuse(Vec3::new(0.8, 0.8, 0.2)),
+    )));
+
+    // cooling tower
+    world.add(Box::new(quadric::Quadric::hyperboloid(
+        Vec3::new(-0.2, 0.45, -2.1),
+        Vec3::new(0.0, 1.0, 0.0),
+        0.08,
+        0.1,
+        0.2,
+        Material::Metallic(material::Metallic {
+            albedo: Vec3::new(0.8, 0.8, 0.9),
+            fuzz: 0.3,
+            diffuse: true,
+        }),
+    )));
+
+    // ring lying on the floor
+    world.add(Box::new(torus::Torus::new(
+        Vec3::new(0.45, -0.465, -1.15),
+        Vec3::new(0.0, 1.0, 0.0),
+        0.1,
+        0.035,
+        Material::Metallic(material::Metallic {
+            albedo: Vec3::new(0.95, 0.75, 0.35),
+            fuzz: 0.05,
+            diffuse: false,
+        }),
+    )));
+
+    world.add(Box::new(implicit::ImplicitSurface::tanglecube(
+        Vec3::new(0.35, 0.45, -2.0),
+        0.07,
+        Material::Diffuse(Vec3::new(0.6, 0.3, 0.7)),
+    )));
+
+    (world, 1.5)
+}
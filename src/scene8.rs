@@ -0,0 +1,116 @@
+use crate::{
+    hitable_list::{self, HitableList},
+    material::{self, Material},
+    plane,
+    sdf::{Sdf, SdfObject},
+    sphere, triangle,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(SdfObject::new(
+        Sdf::mandelbulb(8.0, 10).scale(0.3),
+        Vec3::new(0.0, 0.0, -1.7),
+        0.4,
+        Material::Diffuse(Vec3::new(0.8, 0.5, 0.3)),
+    )));
+
+    // organic blob
+    let blob = Sdf::sphere(0.15)
+        .smooth_union(Sdf::sphere(0.1).translate(Vec3::new(0.15, 0.1, 0.0)), 0.1)
+        .smooth_union(Sdf::sphere(0.08).translate(Vec3::new(-0.1, 0.15, 0.05)), 0.1)
+        .displace(0.02, 12.0, 3);
+    world.add(Box::new(
+        SdfObject::new(
+            blob,
+            Vec3::new(-0.6, -0.35, -1.4),
+            0.35,
+            Material::Diffuse(Vec3::new(0.3, 0.7, 0.4)),
+        )
+        .with_step_scale(0.7),
+    ));
+
+    world.add(Box::new(
+        SdfObject::new(
+            Sdf::cube(Vec3::new(0.08, 0.2, 0.08)).twist(4.0),
+            Vec3::new(0.55, -0.3, -1.5),
+            0.3,
+            Material::Metallic(material::Metallic {
+                albedo: Vec3::new(0.8, 0.8, 0.9),
+                fuzz: 0.1,
+                diffuse: false,
+            }),
+        )
+        .with_step_scale(0.5),
+    ));
+
+    // die: a rounded cube with dimples and a handle
+    let die = Sdf::cube(Vec3::new(0.08, 0.08, 0.08))
+        .smooth_intersection(Sdf::sphere(0.12), 0.02)
+        .smooth_subtraction(Sdf::sphere(0.03).translate(Vec3::new(0.0, 0.0, 0.1)), 0.01)
+        .smooth_union(
+            Sdf::capsule(Vec3::new(0.0, 0.08, 0.0), Vec3::new(0.0, 0.2, 0.0), 0.02),
+            0.03,
+        );
+    world.add(Box::new(SdfObject::new(
+        die,
+        Vec3::new(-0.25, -0.42, -1.1),
+        0.3,
+        Material::Diffuse(Vec3::new(0.8, 0.2, 0.2)),
+    )));
+
+    // row of rings, repeated along x and cut to length
+    let rings = Sdf::torus(0.05, 0.012)
+        .repeat(Vec3::new(0.13, 0.0, 0.0))
+        .smooth_intersection(Sdf::cube(Vec3::new(0.32, 0.1, 0.1)), 0.0);
+    world.add(Box::new(
+        SdfObject::new(
+            rings,
+            Vec3::new(0.3, -0.488, -1.0),
+            0.4,
+            Material::Metallic(material::Metallic {
+                albedo: Vec3::new(0.95, 0.75, 0.35),
+                fuzz: 0.05,
+                diffuse: false,
+            }),
+        )
+        .with_step_scale(0.8),
+    ));
+
+    world.add(Box::new(
+        SdfObject::new(
+            Sdf::cube(Vec3::new(0.25, 0.015, 0.04)).bend(2.5),
+            Vec3::new(-0.5, 0.4, -1.9),
+            0.3,
+            Material::Diffuse(Vec3::new(0.9, 0.9, 0.3)),
+        )
+        .with_step_scale(0.6),
+    ));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(0.55, 0.25, -2.0),
+        0.15,
+        Material::Light(Vec3::new(1.0, 0.9, 0.8).multiply(6.0)),
+    )));
+
+    world.add(Box::new(triangle::Triangle::new(
+        Vec3::new(-0.9, -0.5, -2.2),
+        Vec3::new(-0.4, -0.5, -2.2),
+        Vec3::new(-0.65, 0.2, -2.3),
+        Material::Diffuse(Vec3::new(0.2, 0.3, 0.7)),
+    )));
+
+    (world, 1.5)
+}
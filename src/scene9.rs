@@ -0,0 +1,100 @@
+use crate::{
+    aa_box,
+    csg::Csg,
+    hitable::Hitable,
+    hitable_list::{self, HitableList},
+    material::{self, Material},
+    plane, quadric, sphere,
+    vec3::Vec3,
+};
+
+pub fn create_scene() -> (HitableList, f32) {
+    let mut world = hitable_list::HitableList::new();
+
+    world.add(Box::new(plane::Plane::new(
+        Vec3::new(0.0, -0.5, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        Material::CheckerBoard(
+            Box::new(Material::Diffuse(Vec3::new(0.8, 0.8, 0.8))),
+            Box::new(Material::Diffuse(Vec3::new(0.2, 0.2, 0.3))),
+            5.0,
+        ),
+    )));
+
+    world.add(Box::new(sphere::Sphere::new(
+        Vec3::new(0.0, 1.5, -1.0),
+        0.5,
+        Material::Light(Vec3::new(1.0, 0.95, 0.9).multiply(4.0)),
+    )));
+
+    // the classic: a cube intersected with a sphere, drilled along every axis
+    let center = Vec3::new(0.0, -0.15, -1.6);
+    let half = Vec3::new(0.3, 0.3, 0.3);
+    let rounded_cube = Csg::intersection(
+        Box::new(aa_box::AaBox::new(
+            center.sub(&half),
+            center.add(&half),
+            Material::Diffuse(Vec3::new(0.8, 0.2, 0.2)),
+        )),
+        Box::new(sphere::Sphere::new(
+            center,
+            0.4,
+            Material::Diffuse(Vec3::new(0.2, 0.3, 0.8)),
+        )),
+    );
+    let mut drilled: Box<dyn Hitable> = Box::new(rounded_cube);
+    for axis in [Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0)] {
+        let cylinder = quadric::Quadric::cylinder(
+            center.sub(&axis.multiply(0.5)),
+            axis,
+            0.18,
+            1.0,
+            Material::Diffuse(Vec3::new(0.2, 0.7, 0.3)),
+        )
+        .with_caps();
+        drilled = Box::new(Csg::difference(drilled, Box::new(cylinder)));
+    }
+    world.add(drilled);
+
+    // sphere with a corner cut out
+    let center = Vec3::new(-0.65, -0.25, -1.4);
+    world.add(Box::new(Csg::difference(
+        Box::new(sphere::Sphere::new(
+            center,
+            0.25,
+            Material::Metallic(material::Metallic {
+                albedo: Vec3::new(0.9, 0.8, 0.4),
+                fuzz: 0.1,
+                diffuse: false,
+            }),
+        )),
+        Box::new(aa_box::AaBox::new(
+            center,
+            center.add(&Vec3::new(0.5, 0.5, 0.5)),
+            Material::Diffuse(Vec3::new(0.9, 0.9, 0.9)),
+        )),
+    )));
+
+    // lens made of two overlapping spheres, fused with a base
+    world.add(Box::new(Csg::union(
+        Box::new(Csg::intersection(
+            Box::new(sphere::Sphere::new(
+                Vec3::new(0.45, -0.2, -1.2),
+                0.3,
+                Material::Diffuse(Vec3::new(0.3, 0.6, 0.8)),
+            )),
+            Box::new(sphere::Sphere::new(
+                Vec3::new(0.75, -0.2, -1.2),
+                0.3,
+                Material::Diffuse(Vec3::new(0.3, 0.6, 0.8)),
+            )),
+        )),
+        Box::new(aa_box::AaBox::new(
+            Vec3::new(0.5, -0.5, -1.3),
+            Vec3::new(0.7, -0.4, -1.1),
+            Material::Diffuse(Vec3::new(0.7, 0.7, 0.7)),
+        )),
+    )));
+
+    (world, 1.5)
+}
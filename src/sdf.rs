@@ -0,0 +1,301 @@
+use noise::{NoiseFn, Perlin};
+
+use crate::{
+    aabb::Aabb,
+    hitable::{HitRecord, Hitable},
+    material::Material,
+    ray::Ray,
+    roots::solve_quadratic,
+    sphere,
+    vec3::Vec3,
+};
+
+const MAX_STEPS: u32 = 512;
+const EPSILON: f32 = 1e-4;
+
+/// Signed distance field expression. Leaves are centred at the origin and are
+/// combined or deformed by the other nodes, usually through the builder
+/// methods: `Sdf::sphere(0.3).smooth_union(Sdf::cube(..).translate(..), 0.1)`.
+#[derive(Debug, Clone)]
+pub enum Sdf {
+    Sphere { radius: f32 },
+    Box { half_extents: Vec3 },
+    /// Ring in the xz plane around the y axis.
+    Torus { major_radius: f32, minor_radius: f32 },
+    Capsule { a: Vec3, b: Vec3, radius: f32 },
+    /// Distance estimate of the power-n Mandelbulb, roughly unit sized.
+    Mandelbulb { power: f32, iterations: u32 },
+    Translate(Box<Sdf>, Vec3),
+    Scale(Box<Sdf>, f32),
+    SmoothUnion(Box<Sdf>, Box<Sdf>, f32),
+    /// The second shape carved out of the first.
+    SmoothSubtraction(Box<Sdf>, Box<Sdf>, f32),
+    SmoothIntersection(Box<Sdf>, Box<Sdf>, f32),
+    /// Infinite repetition with the given period along each axis, 0 leaves an
+    /// axis alone.
+    Repeat(Box<Sdf>, Vec3),
+    /// Rotation around y by `amount` radians per unit of height.
+    Twist(Box<Sdf>, f32),
+    /// Bends the x axis upwards by `amount` radians per unit of length.
+    Bend(Box<Sdf>, f32),
+    Displace {
+        sdf: Box<Sdf>,
+        noise: Box<Perlin>,
+        amplitude: f32,
+        frequency: f32,
+    },
+}
+
+impl Sdf {
+    pub fn sphere(radius: f32) -> Self {
+        Sdf::Sphere { radius }
+    }
+
+    pub fn cube(half_extents: Vec3) -> Self {
+        Sdf::Box { half_extents }
+    }
+
+    pub fn torus(major_radius: f32, minor_radius: f32) -> Self {
+        Sdf::Torus {
+            major_radius,
+            minor_radius,
+        }
+    }
+
+    pub fn capsule(a: Vec3, b: Vec3, radius: f32) -> Self {
+        Sdf::Capsule { a, b, radius }
+    }
+
+    pub fn mandelbulb(power: f32, iterations: u32) -> Self {
+        Sdf::Mandelbulb { power, iterations }
+    }
+
+    pub fn translate(self, offset: Vec3) -> Self {
+        Sdf::Translate(Box::new(self), offset)
+    }
+
+    pub fn scale(self, factor: f32) -> Self {
+        Sdf::Scale(Box::new(self), factor)
+    }
+
+    /// Union blended over a distance of `k`; 0 gives a sharp union.
+    pub fn smooth_union(self, other: Sdf, k: f32) -> Self {
+        Sdf::SmoothUnion(Box::new(self), Box::new(other), k)
+    }
+
+    pub fn smooth_subtraction(self, other: Sdf, k: f32) -> Self {
+        Sdf::SmoothSubtraction(Box::new(self), Box::new(other), k)
+    }
+
+    pub fn smooth_intersection(self, other: Sdf, k: f32) -> Self {
+        Sdf::SmoothIntersection(Box::new(self), Box::new(other), k)
+    }
+
+    pub fn repeat(self, period: Vec3) -> Self {
+        Sdf::Repeat(Box::new(self), period)
+    }
+
+    pub fn twist(self, amount: f32) -> Self {
+        Sdf::Twist(Box::new(self), amount)
+    }
+
+    pub fn bend(self, amount: f32) -> Self {
+        Sdf::Bend(Box::new(self), amount)
+    }
+
+    /// Offsets the surface by Perlin noise of the given amplitude and frequency.
+    pub fn displace(self, amplitude: f32, frequency: f32, seed: u32) -> Self {
+        Sdf::Displace {
+            sdf: Box::new(self),
+            noise: Box::new(Perlin::new(seed)),
+            amplitude,
+            frequency,
+        }
+    }
+
+    pub fn distance(&self, p: &Vec3) -> f32 {
+        match self {
+            Sdf::Sphere { radius } => p.length() - radius,
+            Sdf::Box { half_extents } => {
+                let q = Vec3::new(
+                    p.x.abs() - half_extents.x,
+                    p.y.abs() - half_extents.y,
+                    p.z.abs() - half_extents.z,
+                );
+                let outside = Vec3::new(q.x.max(0.0), q.y.max(0.0), q.z.max(0.0)).length();
+                outside + q.x.max(q.y).max(q.z).min(0.0)
+            }
+            Sdf::Torus {
+                major_radius,
+                minor_radius,
+            } => {
+                let ring = (p.x * p.x + p.z * p.z).sqrt() - major_radius;
+                (ring * ring + p.y * p.y).sqrt() - minor_radius
+            }
+            Sdf::Capsule { a, b, radius } => {
+                let pa = p.sub(a);
+                let ba = b.sub(a);
+                let h = (pa.dot(&ba) / ba.length_squared()).clamp(0.0, 1.0);
+                pa.sub(&ba.multiply(h)).length() - radius
+            }
+            Sdf::Mandelbulb { power, iterations } => mandelbulb(p, *power, *iterations),
+            Sdf::Translate(sdf, offset) => sdf.distance(&p.sub(offset)),
+            Sdf::Scale(sdf, factor) => sdf.distance(&p.divide(*factor)) * factor,
+            Sdf::SmoothUnion(a, b, k) => smooth_min(a.distance(p), b.distance(p), *k),
+            Sdf::SmoothSubtraction(a, b, k) => -smooth_min(-a.distance(p), b.distance(p), *k),
+            Sdf::SmoothIntersection(a, b, k) => -smooth_min(-a.distance(p), -b.distance(p), *k),
+            Sdf::Repeat(sdf, period) => {
+                let wrap = |x: f32, period: f32| {
+                    if period > 0.0 {
+                        x - period * (x / period).round()
+                    } else {
+                        x
+                    }
+                };
+                sdf.distance(&Vec3::new(wrap(p.x, period.x), wrap(p.y, period.y), wrap(p.z, period.z)))
+            }
+            Sdf::Twist(sdf, amount) => {
+                let (sin, cos) = (amount * p.y).sin_cos();
+                sdf.distance(&Vec3::new(cos * p.x - sin * p.z, p.y, sin * p.x + cos * p.z))
+            }
+            Sdf::Bend(sdf, amount) => {
+                let (sin, cos) = (amount * p.x).sin_cos();
+                sdf.distance(&Vec3::new(cos * p.x - sin * p.y, sin * p.x + cos * p.y, p.z))
+            }
+            Sdf::Displace {
+                sdf,
+                noise,
+                amplitude,
+                frequency,
+            } => {
+                let q = p.multiply(*frequency);
+                let offset = noise.get([q.x as f64, q.y as f64, q.z as f64]) as f32;
+                sdf.distance(p) + amplitude * offset
+            }
+        }
+    }
+
+    /// Gradient direction by central differences on a tetrahedron.
+    fn normal(&self, p: &Vec3) -> Vec3 {
+        let h = EPSILON * 0.5;
+        [(1.0, -1.0, -1.0), (-1.0, -1.0, 1.0), (-1.0, 1.0, -1.0), (1.0, 1.0, 1.0)]
+            .iter()
+            .fold(Vec3::new(0.0, 0.0, 0.0), |normal, (x, y, z)| {
+                let k = Vec3::new(*x, *y, *z);
+                normal.add(&k.multiply(self.distance(&p.add(&k.multiply(h)))))
+            })
+            .normalize()
+    }
+}
+
+/// Polynomial smooth minimum from Inigo Quilez.
+fn smooth_min(a: f32, b: f32, k: f32) -> f32 {
+    if k <= 0.0 {
+        return a.min(b);
+    }
+    let h = (0.5 + 0.5 * (b - a) / k).clamp(0.0, 1.0);
+    b + (a - b) * h - k * h * (1.0 - h)
+}
+
+fn mandelbulb(p: &Vec3, power: f32, iterations: u32) -> f32 {
+    let mut z = *p;
+    let mut dr = 1.0;
+    let mut r = 0.0;
+    for _ in 0..iterations {
+        r = z.length();
+        if r > 2.0 || r == 0.0 {
+            break;
+        }
+        let theta = (z.y / r).acos() * power;
+        let phi = z.z.atan2(z.x) * power;
+        dr = r.powf(power - 1.0) * power * dr + 1.0;
+        let zr = r.powf(power);
+        z = Vec3::new(theta.sin() * phi.cos(), theta.cos(), theta.sin() * phi.sin())
+            .multiply(zr)
+            .add(p);
+    }
+    if r == 0.0 {
+        return 0.0;
+    }
+    0.5 * r.ln() * r / dr
+}
+
+/// Sphere traces an `Sdf` placed at `center`. Marching is limited to a sphere
+/// of `bounding_radius` around it. Deformations like twist, bend and
+/// displacement can make the field overestimate distances, which
+/// `with_step_scale` compensates for by taking shorter steps.
+#[derive(Debug, Clone)]
+pub struct SdfObject {
+    sdf: Sdf,
+    center: Vec3,
+    bounding_radius: f32,
+    step_scale: f32,
+    material: Material,
+}
+
+impl SdfObject {
+    pub fn new(sdf: Sdf, center: Vec3, bounding_radius: f32, material: Material) -> Self {
+        SdfObject {
+            sdf,
+            center,
+            bounding_radius,
+            step_scale: 1.0,
+            material,
+        }
+    }
+
+    pub fn with_step_scale(mut self, step_scale: f32) -> Self {
+        self.step_scale = step_scale;
+        self
+    }
+}
+
+impl Hitable for SdfObject {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let length = ray.direction.length();
+        let direction = ray.direction.divide(length);
+        let origin = ray.origin.sub(&self.center);
+
+        let (enter, exit) = solve_quadratic(
+            1.0,
+            2.0 * origin.dot(&direction) as f64,
+            (origin.length_squared() - self.bounding_radius * self.bounding_radius) as f64,
+        )?;
+        let mut t = (enter as f32).max(t_min * length);
+        let end = (exit as f32).min(t_max * length);
+
+        // rays starting inside, e.g. refracted ones, march on |d| until they leave
+        let mut inside = self.sdf.distance(&origin.add(&direction.multiply(t))) < 0.0;
+        // rays setting off from the surface first step along until they are
+        // clear of it, then march on from whichever side they are on
+        let mut clear = t > t_min * length;
+        for _ in 0..MAX_STEPS {
+            if t > end {
+                return None;
+            }
+            let p = origin.add(&direction.multiply(t));
+            let distance = self.sdf.distance(&p);
+            if !clear {
+                if distance.abs() < EPSILON {
+                    t += EPSILON;
+                    continue;
+                }
+                clear = true;
+                inside = distance < 0.0;
+            }
+            let distance = if inside { -distance } else { distance };
+            if distance < EPSILON {
+                let normal = self.sdf.normal(&p);
+                let (u, v) = sphere::sphere_uv(&p.normalize());
+                return Some(HitRecord::new(ray, t / length, normal, self.material.clone(), u, v));
+            }
+            t += distance * self.step_scale;
+        }
+        None
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let extent = Vec3::new(self.bounding_radius, self.bounding_radius, self.bounding_radius);
+        Some(Aabb::new(self.center.sub(&extent), self.center.add(&extent)))
+    }
+}
@@ -0,0 +1,241 @@
+use std::sync::OnceLock;
+
+use crate::{util::luminance, vec3::Vec3};
+
+/// Shortest and longest visible wavelengths in nm.
+pub const VISIBLE: (f32, f32) = (380.0, 780.0);
+
+/// Wavelengths followed together along each path in spectral mode.
+pub const SAMPLES: usize = 4;
+
+/// Wavelengths a spectrum is sampled at when it is reduced to RGB.
+const RGB_SAMPLES: usize = 32;
+
+/// Where the RGB basis spectra hand over from blue to green and from green
+/// to red, in nm, and how soft the handover is.
+const BLUE_GREEN: f32 = 490.0;
+const GREEN_RED: f32 = 585.0;
+const EDGE_WIDTH: f32 = 8.0;
+
+/// Wavelengths carried along one path in spectral mode: a hero wavelength
+/// drawn uniformly over the visible range and the others spaced evenly from
+/// it, wrapping around (Wilkie et al. 2014). All are equally likely.
+#[derive(Debug, Clone, Copy)]
+pub struct Wavelengths {
+    pub values: [f32; SAMPLES],
+    /// Whether the path has already parted from all but the hero.
+    pub hero_only: bool,
+}
+
+impl Wavelengths {
+    /// Wavelengths for a uniform number `u` in [0, 1).
+    pub fn sample(u: f32) -> Self {
+        let (shortest, longest) = VISIBLE;
+        Wavelengths {
+            values: std::array::from_fn(|i| shortest + (u + i as f32 / SAMPLES as f32).fract() * (longest - shortest)),
+            hero_only: false,
+        }
+    }
+
+    /// The wavelength that decides directions where light of different
+    /// wavelengths would part ways.
+    pub fn hero(&self) -> f32 {
+        self.values[0]
+    }
+
+    /// The same wavelengths, for the rest of a path only the hero follows.
+    pub fn drop_secondary(&self) -> Self {
+        Wavelengths {
+            hero_only: true,
+            ..*self
+        }
+    }
+
+    /// Linear RGB colour of `radiance` carried at these wavelengths, on the
+    /// same scale as the RGB renderer.
+    pub fn to_rgb(self, radiance: &SampledSpectrum) -> Vec3 {
+        let (shortest, longest) = VISIBLE;
+        let mut xyz = Vec3::new(0.0, 0.0, 0.0);
+        for (wavelength, value) in self.values.iter().zip(radiance.values) {
+            xyz = xyz.add(&xyz_matching(*wavelength).multiply(value));
+        }
+        // divided by the chance of each wavelength, averaged over all of them
+        balance(&xyz_to_rgb(&xyz.multiply((longest - shortest) / SAMPLES as f32)))
+    }
+}
+
+/// A spectrum, or a weight that depends on wavelength, at the `Wavelengths`
+/// of one path.
+#[derive(Debug, Clone, Copy)]
+pub struct SampledSpectrum {
+    pub values: [f32; SAMPLES],
+}
+
+impl SampledSpectrum {
+    pub fn new(values: [f32; SAMPLES]) -> Self {
+        SampledSpectrum { values }
+    }
+
+    pub fn zero() -> Self {
+        SampledSpectrum::new([0.0; SAMPLES])
+    }
+
+    /// Smooth spectrum for an RGB colour, at `wavelengths`.
+    pub fn from_rgb(rgb: &Vec3, wavelengths: &Wavelengths) -> Self {
+        SampledSpectrum::new(wavelengths.values.map(|wavelength| rgb_at(rgb, wavelength)))
+    }
+
+    /// Light of a black body at `temperature` kelvin with unit luminance.
+    pub fn blackbody(temperature: f32, wavelengths: &Wavelengths) -> Self {
+        let scale = 1.0 / luminance(&reflectance_to_rgb(|wavelength| blackbody(wavelength, temperature)));
+        SampledSpectrum::new(wavelengths.values.map(|wavelength| blackbody(wavelength, temperature) * scale))
+    }
+
+    pub fn multiply(&self, factor: f32) -> SampledSpectrum {
+        SampledSpectrum::new(self.values.map(|value| value * factor))
+    }
+
+    pub fn multiply_spectrum(&self, other: &SampledSpectrum) -> SampledSpectrum {
+        SampledSpectrum::new(std::array::from_fn(|i| self.values[i] * other.values[i]))
+    }
+
+    /// Keeps only the hero wavelength, standing in for all of them, for
+    /// paths the others couldn't have followed.
+    pub fn hero_only(&self) -> SampledSpectrum {
+        let mut values = [0.0; SAMPLES];
+        values[0] = self.values[0] * SAMPLES as f32;
+        SampledSpectrum::new(values)
+    }
+}
+
+/// CIE 1931 colour matching functions x̄, ȳ and z̄ at `wavelength` in nm,
+/// from the multi-lobe Gaussian fit of Wyman, Sloan and Shirley 2013.
+pub fn xyz_matching(wavelength: f32) -> Vec3 {
+    let lobe = |mean: f32, below: f32, above: f32| {
+        let t = (wavelength - mean) / if wavelength < mean { below } else { above };
+        (-0.5 * t * t).exp()
+    };
+    Vec3::new(
+        1.056 * lobe(599.8, 37.9, 31.0) + 0.362 * lobe(442.0, 16.0, 26.7) - 0.065 * lobe(501.1, 20.4, 26.2),
+        0.821 * lobe(568.8, 46.9, 40.5) + 0.286 * lobe(530.9, 16.3, 31.1),
+        1.217 * lobe(437.0, 11.8, 36.0) + 0.681 * lobe(459.0, 26.0, 13.8),
+    )
+}
+
+/// CIE XYZ to linear sRGB with the D65 white point.
+pub fn xyz_to_rgb(xyz: &Vec3) -> Vec3 {
+    Vec3::new(
+        3.2406 * xyz.x - 1.5372 * xyz.y - 0.4986 * xyz.z,
+        -0.9689 * xyz.x + 1.8758 * xyz.y + 0.0415 * xyz.z,
+        0.0557 * xyz.x - 0.2040 * xyz.y + 1.0570 * xyz.z,
+    )
+}
+
+/// Linear RGB colour of a reflectance spectrum given by `reflectance` at a
+/// wavelength in nm, balanced so that a flat spectrum of 1 gives white.
+/// Colours outside the sRGB gamut are clipped.
+pub fn reflectance_to_rgb(reflectance: impl Fn(f32) -> f32) -> Vec3 {
+    let rgb = balance(&integrate(reflectance));
+    Vec3::new(rgb.x.max(0.0), rgb.y.max(0.0), rgb.z.max(0.0))
+}
+
+/// Value at `wavelength` of a smooth spectrum for `rgb`: blue, green and
+/// red bands with soft edges, weighted by the channels. The bands add up to
+/// 1 everywhere, so white stays flat and reflectances stay below 1, and
+/// the spectrum turns back into the same colour to within a few percent.
+pub fn rgb_at(rgb: &Vec3, wavelength: f32) -> f32 {
+    let step = |edge: f32| 1.0 / (1.0 + ((edge - wavelength) / EDGE_WIDTH).exp());
+    let (above_blue, red) = (step(BLUE_GREEN), step(GREEN_RED));
+    rgb.z * (1.0 - above_blue) + rgb.y * (above_blue - red) + rgb.x * red
+}
+
+/// Planck's law at `wavelength` in nm for a black body at `temperature`
+/// kelvin, relative to its peak.
+pub fn blackbody(wavelength: f32, temperature: f32) -> f32 {
+    // hc / k in nm K, and Wien's displacement constant in nm K
+    const SECOND_RADIATION: f32 = 1.438_777e7;
+    const WIEN: f32 = 2.897_772e6;
+    let planck = |wavelength: f32| 1.0 / (wavelength.powi(5) * ((SECOND_RADIATION / (wavelength * temperature)).exp() - 1.0));
+    planck(wavelength) / planck(WIEN / temperature)
+}
+
+/// Linear RGB colour of a black body at `temperature` kelvin with unit
+/// luminance, warm below about 6500 K and blue above.
+pub fn blackbody_color(temperature: f32) -> Vec3 {
+    let color = reflectance_to_rgb(|wavelength| blackbody(wavelength, temperature));
+    color.divide(luminance(&color))
+}
+
+/// Colour of `f` integrated against the colour matching functions, before
+/// white balance.
+fn integrate(f: impl Fn(f32) -> f32) -> Vec3 {
+    let (shortest, longest) = VISIBLE;
+    let step = (longest - shortest) / RGB_SAMPLES as f32;
+    let mut xyz = Vec3::new(0.0, 0.0, 0.0);
+    for i in 0..RGB_SAMPLES {
+        let wavelength = shortest + (i as f32 + 0.5) * step;
+        xyz = xyz.add(&xyz_matching(wavelength).multiply(f(wavelength) * step));
+    }
+    xyz_to_rgb(&xyz)
+}
+
+static WHITE: OnceLock<Vec3> = OnceLock::new();
+
+/// Scales each channel so that a flat spectrum of 1 comes out white.
+fn balance(rgb: &Vec3) -> Vec3 {
+    let white = WHITE.get_or_init(|| integrate(|_| 1.0));
+    Vec3::new(rgb.x / white.x, rgb.y / white.y, rgb.z / white.z)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mean colour of `spectrum` over wavelengths stratified across the
+    /// visible range.
+    fn mean_rgb(spectrum: impl Fn(&Wavelengths) -> SampledSpectrum) -> Vec3 {
+        let strata = 256;
+        let mut total = Vec3::new(0.0, 0.0, 0.0);
+        for i in 0..strata {
+            let wavelengths = Wavelengths::sample((i as f32 + 0.5) / strata as f32);
+            total = total.add(&wavelengths.to_rgb(&spectrum(&wavelengths)));
+        }
+        total.divide(strata as f32)
+    }
+
+    #[test]
+    fn white_round_trips() {
+        let white = Vec3::new(1.0, 1.0, 1.0);
+        let rgb = mean_rgb(|wavelengths| SampledSpectrum::from_rgb(&white, wavelengths));
+        for channel in [rgb.x, rgb.y, rgb.z] {
+            assert!((channel - 1.0).abs() < 0.02, "{:?}", rgb);
+        }
+    }
+
+    #[test]
+    fn flat_spectrum_is_white() {
+        let rgb = mean_rgb(|_| SampledSpectrum::new([1.0; SAMPLES]));
+        for channel in [rgb.x, rgb.y, rgb.z] {
+            assert!((channel - 1.0).abs() < 0.02, "{:?}", rgb);
+        }
+    }
+
+    #[test]
+    fn blackbody_at_6500_kelvin_is_nearly_neutral() {
+        // white is a flat spectrum, which is a little warmer than 6500 K,
+        // so the blackbody comes out slightly blue
+        let rgb = blackbody_color(6500.0);
+        assert!((luminance(&rgb) - 1.0).abs() < 1e-4, "{:?}", rgb);
+        for channel in [rgb.x, rgb.y, rgb.z] {
+            assert!((channel - 1.0).abs() < 0.15, "{:?}", rgb);
+        }
+        assert!(rgb.z > rgb.x, "{:?}", rgb);
+    }
+
+    #[test]
+    fn blackbodies_warm_up_as_they_cool() {
+        let warm = blackbody_color(2700.0);
+        let cool = blackbody_color(10000.0);
+        assert!(warm.x > warm.z && cool.z > cool.x, "{:?} {:?}", warm, cool);
+    }
+}
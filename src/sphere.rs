@@ -1,7 +1,10 @@
 use crate::{
-    hitable::{HitRecord, Hitable},
+    aabb::Aabb,
+    hitable::{HitRecord, Hitable, Span},
+    interval::Interval,
     material::Material,
     ray::{self, Ray},
+    roots::solve_quadratic,
     vec3::Vec3,
 };
 
@@ -20,39 +23,73 @@ impl Sphere {
             material,
         }
     }
+
+    fn record(&self, ray: &Ray, t: f32) -> HitRecord {
+        let normal = ray.at(t).sub(&self.center).divide(self.radius);
+        let (u, v) = sphere_uv(&normal);
+        let mut record = HitRecord::new(ray, t, normal, self.material.clone(), u, v);
+        // around the y axis, in the direction u grows; undefined at the poles
+        let tangent = Vec3::new(normal.z, 0.0, -normal.x);
+        record.tangent = (tangent.length_squared() > 1e-12).then(|| tangent.normalize());
+        record
+    }
 }
 
 impl Hitable for Sphere {
     fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
-        let t = hit_sphere(&self.center, self.radius, ray);
+        // the far root is the hit for rays starting inside
+        let (near, far) = hit_sphere(&self.center, self.radius, ray)?;
+        let t = if near >= t_min { near } else { far };
         if t < t_min || t > t_max {
             return None;
         }
-        let point = ray.at(t);
-        let normal = point.sub(&self.center).divide(self.radius);
-        Some(HitRecord {
-            t,
-            point,
-            front_face: ray.direction.dot(&normal) < 0.0,
-            normal: if ray.direction.dot(&normal) < 0.0 {
-                normal
-            } else {
-                normal.multiply(-1.0)
-            },
-            material: self.material.clone(),
-        })
+        Some(self.record(ray, t))
+    }
+
+    // `hit` reports only the first crossing, so both are solved here
+    fn hit_intervals(&self, ray: &Ray, t_min: f32, t_max: f32) -> Vec<Span> {
+        let oc = ray.origin.sub(&self.center);
+        let Some((t0, t1)) = solve_quadratic(
+            ray.direction.length_squared() as f64,
+            2.0 * ray.direction.dot(&oc) as f64,
+            (oc.length_squared() - self.radius * self.radius) as f64,
+        ) else {
+            return vec![];
+        };
+        let (t0, t1) = (t0 as f32, t1 as f32);
+        let range = Interval::new(t_min, t_max);
+        if t1 <= t_min || t0 >= t_max {
+            return vec![];
+        }
+        vec![Span {
+            interval: Interval::new(range.clamp(t0), range.clamp(t1)),
+            entry: range.surrounds(t0).then(|| self.record(ray, t0)),
+            exit: range.surrounds(t1).then(|| self.record(ray, t1)),
+        }]
     }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let radius = Vec3::new(self.radius, self.radius, self.radius);
+        Some(Aabb::new(self.center.sub(&radius), self.center.add(&radius)))
+    }
+}
+
+/// Longitude and latitude of a point on the unit sphere, both in [0, 1].
+pub fn sphere_uv(point: &Vec3) -> (f32, f32) {
+    let theta = (-point.y).clamp(-1.0, 1.0).acos();
+    let phi = (-point.z).atan2(point.x) + std::f32::consts::PI;
+    (phi / (2.0 * std::f32::consts::PI), theta / std::f32::consts::PI)
 }
 
-fn hit_sphere(center: &Vec3, radius: f32, ray: &ray::Ray) -> f32 {
+fn hit_sphere(center: &Vec3, radius: f32, ray: &ray::Ray) -> Option<(f32, f32)> {
     let oc = center.sub(&ray.origin);
     let a = ray.direction.length_squared();
     let h = ray.direction.dot(&oc);
     let c = oc.length_squared() - radius * radius;
     let discriminant = h * h - a * c;
     if discriminant < 0.0 {
-        return -1.0;
-    } else {
-        return (h - discriminant.sqrt()) / a;
+        return None;
     }
+    let root = discriminant.sqrt();
+    Some(((h - root) / a, (h + root) / a))
 }
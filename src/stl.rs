@@ -0,0 +1,87 @@
+use std::{collections::HashMap, fs, io, path::Path};
+
+use crate::{material::Material, mesh::TriangleMesh, vec3::Vec3};
+
+/// Parses a binary or ASCII STL file into a mesh. STL stores every triangle
+/// with its own corners, so identical positions are merged to give a
+/// connected mesh that `TriangleMesh::with_smooth_normals` can work with. The
+/// stored facet normals are ignored in favour of the winding order.
+pub fn parse(data: &[u8], material: Material) -> io::Result<TriangleMesh> {
+    // ASCII files start with "solid" too, but only binary ones match this size
+    let binary_size = data
+        .get(80..84)
+        .map(|count| 84 + 50 * u32::from_le_bytes(count.try_into().unwrap()) as usize);
+    let corners = if binary_size == Some(data.len()) || !data.starts_with(b"solid") {
+        parse_binary(data)?
+    } else {
+        parse_ascii(data)?
+    };
+
+    let mut positions = vec![];
+    let mut lookup: HashMap<[u32; 3], usize> = HashMap::new();
+    let mut indices = vec![];
+    for triangle in corners.chunks_exact(3) {
+        let mut face = [0; 3];
+        for (corner, position) in face.iter_mut().zip(triangle) {
+            let key = [position.x.to_bits(), position.y.to_bits(), position.z.to_bits()];
+            *corner = *lookup.entry(key).or_insert_with(|| {
+                positions.push(*position);
+                positions.len() - 1
+            });
+        }
+        indices.push(face);
+    }
+    Ok(TriangleMesh::new(positions, indices, material))
+}
+
+pub fn load<P: AsRef<Path>>(path: P, material: Material) -> io::Result<TriangleMesh> {
+    parse(&fs::read(path)?, material)
+}
+
+fn parse_binary(data: &[u8]) -> io::Result<Vec<Vec3>> {
+    let count = data
+        .get(80..84)
+        .map(|count| u32::from_le_bytes(count.try_into().unwrap()) as usize)
+        .ok_or_else(|| invalid_data("STL file is too short".to_string()))?;
+    let mut corners = Vec::with_capacity(count * 3);
+    for triangle in 0..count {
+        let record = data
+            .get(84 + triangle * 50..84 + (triangle + 1) * 50)
+            .ok_or_else(|| invalid_data("STL data ends early".to_string()))?;
+        let float = |offset: usize| f32::from_le_bytes(record[offset..offset + 4].try_into().unwrap());
+        // the first 12 bytes hold the facet normal
+        for corner in 0..3 {
+            let offset = 12 + corner * 12;
+            corners.push(Vec3::new(float(offset), float(offset + 4), float(offset + 8)));
+        }
+    }
+    Ok(corners)
+}
+
+fn parse_ascii(data: &[u8]) -> io::Result<Vec<Vec3>> {
+    let text = std::str::from_utf8(data).map_err(|_| invalid_data("STL file is not valid text".to_string()))?;
+    let mut corners = vec![];
+    let mut tokens = text.split_ascii_whitespace();
+    while let Some(token) = tokens.next() {
+        if token != "vertex" {
+            continue;
+        }
+        let mut coordinate = || -> io::Result<f32> {
+            let value = tokens
+                .next()
+                .ok_or_else(|| invalid_data("STL vertex ends early".to_string()))?;
+            value
+                .parse()
+                .map_err(|_| invalid_data(format!("invalid STL coordinate '{}'", value)))
+        };
+        corners.push(Vec3::new(coordinate()?, coordinate()?, coordinate()?));
+    }
+    if corners.len() % 3 != 0 {
+        return Err(invalid_data("STL facet without three vertices".to_string()));
+    }
+    Ok(corners)
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
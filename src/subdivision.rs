@@ -0,0 +1,175 @@
+use std::{collections::HashMap, f32::consts::PI};
+
+use crate::vec3::Vec3;
+
+/// Edges of a mesh keyed by their sorted end points, with the vertex
+/// opposite the edge in each face using it.
+fn edges(indices: &[[usize; 3]]) -> HashMap<(usize, usize), Vec<usize>> {
+    let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for &[a, b, c] in indices {
+        for (from, to, opposite) in [(a, b, c), (b, c, a), (c, a, b)] {
+            edges.entry((from.min(to), from.max(to))).or_default().push(opposite);
+        }
+    }
+    edges
+}
+
+/// Per vertex, all neighbours and the neighbours along crease edges.
+fn neighbours(
+    vertex_count: usize,
+    edges: &HashMap<(usize, usize), Vec<usize>>,
+) -> (Vec<Vec<usize>>, Vec<Vec<usize>>) {
+    let mut all = vec![vec![]; vertex_count];
+    let mut creases = vec![vec![]; vertex_count];
+    for (&(a, b), opposite) in edges {
+        all[a].push(b);
+        all[b].push(a);
+        if opposite.len() != 2 {
+            creases[a].push(b);
+            creases[b].push(a);
+        }
+    }
+    (all, creases)
+}
+
+/// Loop's weight for each neighbour of a smooth vertex of the given valence.
+fn beta(valence: usize) -> f32 {
+    let n = valence as f32;
+    let c = 3.0 / 8.0 + (2.0 * PI / n).cos() / 4.0;
+    (5.0 / 8.0 - c * c) / n
+}
+
+fn average(positions: &[Vec3], vertices: &[usize]) -> Vec3 {
+    vertices
+        .iter()
+        .fold(Vec3::new(0.0, 0.0, 0.0), |sum, &vertex| sum.add(&positions[vertex]))
+        .divide(vertices.len() as f32)
+}
+
+/// One step of Loop subdivision, splitting every triangle into four. Edges
+/// with one face, or more than two, are kept sharp with the boundary rules.
+/// Returns the new positions and faces, plus for each new vertex past the old
+/// ones the edge it was split from, for interpolating other vertex attributes.
+pub fn loop_step(positions: &[Vec3], indices: &[[usize; 3]]) -> (Vec<Vec3>, Vec<[usize; 3]>, Vec<[usize; 2]>) {
+    let edges = edges(indices);
+    let (all, creases) = neighbours(positions.len(), &edges);
+
+    let mut new_positions: Vec<Vec3> = (0..positions.len())
+        .map(|vertex| {
+            let position = positions[vertex];
+            match creases[vertex].len() {
+                0 if !all[vertex].is_empty() => {
+                    let n = all[vertex].len();
+                    let beta = beta(n);
+                    position
+                        .multiply(1.0 - n as f32 * beta)
+                        .add(&average(positions, &all[vertex]).multiply(n as f32 * beta))
+                }
+                2 => position
+                    .multiply(0.75)
+                    .add(&average(positions, &creases[vertex]).multiply(0.25)),
+                // corners and isolated vertices stay put
+                _ => position,
+            }
+        })
+        .collect();
+
+    let mut edge_vertices = HashMap::new();
+    let mut parents = vec![];
+    let mut sorted_edges: Vec<_> = edges.iter().collect();
+    sorted_edges.sort_by_key(|(edge, _)| **edge);
+    for (&(a, b), opposite) in sorted_edges {
+        let midpoint = positions[a].add(&positions[b]).multiply(0.5);
+        let position = if opposite.len() == 2 {
+            midpoint
+                .multiply(0.75)
+                .add(&average(positions, opposite).multiply(0.25))
+        } else {
+            midpoint
+        };
+        edge_vertices.insert((a, b), new_positions.len());
+        new_positions.push(position);
+        parents.push([a, b]);
+    }
+
+    let split = |a: usize, b: usize| edge_vertices[&(a.min(b), a.max(b))];
+    let new_indices = indices
+        .iter()
+        .flat_map(|&[a, b, c]| {
+            let (ab, bc, ca) = (split(a, b), split(b, c), split(c, a));
+            [[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]]
+        })
+        .collect();
+    (new_positions, new_indices, parents)
+}
+
+/// Where each vertex ends up after infinitely many subdivision steps.
+pub fn limit_positions(positions: &[Vec3], indices: &[[usize; 3]]) -> Vec<Vec3> {
+    let edges = edges(indices);
+    let (all, creases) = neighbours(positions.len(), &edges);
+    (0..positions.len())
+        .map(|vertex| {
+            let position = positions[vertex];
+            match creases[vertex].len() {
+                0 if !all[vertex].is_empty() => {
+                    let n = all[vertex].len() as f32;
+                    let chi = 1.0 / (3.0 / (8.0 * beta(all[vertex].len())) + n);
+                    position
+                        .multiply(1.0 - n * chi)
+                        .add(&average(positions, &all[vertex]).multiply(n * chi))
+                }
+                2 => position
+                    .multiply(2.0 / 3.0)
+                    .add(&average(positions, &creases[vertex]).multiply(1.0 / 3.0)),
+                _ => position,
+            }
+        })
+        .collect()
+}
+
+/// Splits every edge longer than `max_length` at its midpoint, over and over
+/// until none are left. Each edge is split the same way in all faces using
+/// it, so no cracks open up. Returns the same as `loop_step`.
+pub fn split_long_edges(
+    positions: &[Vec3],
+    indices: &[[usize; 3]],
+    max_length: f32,
+) -> (Vec<Vec3>, Vec<[usize; 3]>, Vec<[usize; 2]>) {
+    let mut positions = positions.to_vec();
+    let mut indices = indices.to_vec();
+    let mut parents = vec![];
+    loop {
+        let mut midpoints = HashMap::new();
+        for &[a, b, c] in &indices {
+            for (from, to) in [(a, b), (b, c), (c, a)] {
+                let edge = (from.min(to), from.max(to));
+                if !midpoints.contains_key(&edge) && positions[from].sub(&positions[to]).length() > max_length {
+                    midpoints.insert(edge, positions.len());
+                    positions.push(positions[from].add(&positions[to]).multiply(0.5));
+                    parents.push([edge.0, edge.1]);
+                }
+            }
+        }
+        if midpoints.is_empty() {
+            return (positions, indices, parents);
+        }
+
+        let midpoint = |a: usize, b: usize| midpoints.get(&(a.min(b), a.max(b))).copied();
+        indices = indices
+            .iter()
+            .flat_map(|&triangle| {
+                // turn the face so its split edges come first
+                (0..3)
+                    .map(|turn| [triangle[turn], triangle[(turn + 1) % 3], triangle[(turn + 2) % 3]])
+                    .find_map(|[a, b, c]| match (midpoint(a, b), midpoint(b, c), midpoint(c, a)) {
+                        (None, None, None) => Some(vec![[a, b, c]]),
+                        (Some(ab), None, None) => Some(vec![[a, ab, c], [ab, b, c]]),
+                        (Some(ab), Some(bc), None) => Some(vec![[ab, b, bc], [a, ab, bc], [a, bc, c]]),
+                        (Some(ab), Some(bc), Some(ca)) => Some(vec![[a, ab, ca], [ab, b, bc], [ca, bc, c], [ab, bc, ca]]),
+                        _ => None,
+                    })
+                    .unwrap()
+            })
+            .collect();
+    }
+}
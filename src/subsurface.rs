@@ -0,0 +1,215 @@
+use std::io;
+
+use crate::{
+    hitable::Hitable,
+    hitable_list::HitableList,
+    microfacet,
+    ray::Ray,
+    spectrum::{SampledSpectrum, Wavelengths, rgb_at},
+    util::random_double,
+    vec3::Vec3,
+};
+
+/// Most scattering events followed inside an object before the light is
+/// taken as absorbed.
+const MAX_SCATTERING_EVENTS: usize = 256;
+
+/// Translucent material like wax, marble, jade or skin, for closed objects.
+/// Light refracted in through the smooth surface takes a random walk
+/// through the inside, scattering off particles until it finds its way out
+/// or is absorbed (Chiang et al. 2016, as in production renderers).
+#[derive(Debug, Clone, Copy)]
+pub struct Subsurface {
+    /// Colour of the object seen from outside once all the light that finds
+    /// its way back out is added up.
+    pub albedo: Vec3,
+    /// Average distance per channel that light travels between two
+    /// scattering events, in scene units. Longer ones look more translucent.
+    pub mean_free_path: Vec3,
+    pub ior: f32,
+}
+
+impl Subsurface {
+    /// Fails with `InvalidInput` unless every channel of `mean_free_path` is
+    /// positive.
+    pub fn new(albedo: Vec3, mean_free_path: Vec3, ior: f32) -> io::Result<Self> {
+        let channels = [mean_free_path.x, mean_free_path.y, mean_free_path.z];
+        if channels.iter().any(|distance| *distance <= 0.0 || distance.is_nan()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "mean free path must be positive, got ({}, {}, {})",
+                    mean_free_path.x, mean_free_path.y, mean_free_path.z
+                ),
+            ));
+        }
+        Ok(Subsurface {
+            albedo,
+            mean_free_path,
+            ior,
+        })
+    }
+
+    /// Follows light that has refracted in at `entry` heading along
+    /// `direction` through the inside of the object, until it leaves again.
+    /// Returns the ray it leaves along and the weight to multiply the light
+    /// arriving along that ray by, or `None` when it is absorbed.
+    pub fn walk(&self, world: &HitableList, entry: &Vec3, direction: &Vec3) -> Option<(Ray, Vec3)> {
+        let (ray, [red, green, blue]) = self.walk_channels(
+            world,
+            entry,
+            direction,
+            [self.mean_free_path.x, self.mean_free_path.y, self.mean_free_path.z],
+            [self.albedo.x, self.albedo.y, self.albedo.z],
+        )?;
+        Some((ray, Vec3::new(red, green, blue)))
+    }
+
+    /// `walk` for light at `wavelengths`, with the mean free path and albedo
+    /// turned into spectra.
+    pub fn walk_spectral(
+        &self,
+        world: &HitableList,
+        entry: &Vec3,
+        direction: &Vec3,
+        wavelengths: &Wavelengths,
+    ) -> Option<(Ray, SampledSpectrum)> {
+        let (ray, values) = self.walk_channels(
+            world,
+            entry,
+            direction,
+            wavelengths.values.map(|wavelength| rgb_at(&self.mean_free_path, wavelength)),
+            wavelengths.values.map(|wavelength| rgb_at(&self.albedo, wavelength)),
+        )?;
+        Some((ray, SampledSpectrum::new(values)))
+    }
+
+    /// The random walk for channels with their own mean free path and
+    /// albedo.
+    fn walk_channels<const N: usize>(
+        &self,
+        world: &HitableList,
+        entry: &Vec3,
+        direction: &Vec3,
+        mean_free_path: [f32; N],
+        albedo: [f32; N],
+    ) -> Option<(Ray, [f32; N])> {
+        let sigma_t = mean_free_path.map(|distance| 1.0 / distance);
+        let sigma_s: [f32; N] = std::array::from_fn(|i| sigma_t[i] * single_scattering_albedo(albedo[i]));
+
+        let mut position = *entry;
+        let mut direction = direction.normalize();
+        let mut weight = [1.0; N];
+        for _ in 0..MAX_SCATTERING_EVENTS {
+            // distance drawn for one channel, picked in proportion to how much
+            // of it is left, so no channel's weight can blow up
+            let total: f32 = weight.iter().sum();
+            if total <= 0.0 {
+                return None;
+            }
+            let chances = weight.map(|channel| channel / total);
+            let choice = random_double();
+            let mut channel = N - 1;
+            let mut cumulative = 0.0;
+            for (i, chance) in chances.iter().enumerate() {
+                cumulative += chance;
+                if choice < cumulative {
+                    channel = i;
+                    break;
+                }
+            }
+            let distance = -(1.0 - random_double()).ln() / sigma_t[channel];
+            let ray = Ray::new(position, direction);
+
+            let Some(hit) = world.hit(&ray, 1e-4, distance) else {
+                // scattered inside, in a new random direction
+                let transmittance = attenuation(&sigma_t, distance);
+                let pdf = dot(&chances, &std::array::from_fn(|i| sigma_t[i] * transmittance[i]));
+                weight = std::array::from_fn(|i| weight[i] * sigma_s[i] * transmittance[i] / pdf);
+                position = ray.at(distance);
+                direction = Vec3::random_unit();
+                continue;
+            };
+
+            let transmittance = attenuation(&sigma_t, hit.t);
+            let pdf = dot(&chances, &transmittance);
+            weight = std::array::from_fn(|i| weight[i] * transmittance[i] / pdf);
+            position = hit.point;
+
+            // at the surface from inside: out, or reflected back in
+            let cos_theta = -direction.dot(&hit.normal);
+            if random_double() < microfacet::fresnel_dielectric(cos_theta, 1.0 / self.ior) {
+                direction = direction.reflect(&hit.normal);
+                continue;
+            }
+            let out = direction.refract(&hit.normal, self.ior)?;
+            return Some((Ray::new(hit.point, out), weight));
+        }
+        None
+    }
+}
+
+/// Share of each channel left after `distance` through the medium.
+fn attenuation<const N: usize>(sigma_t: &[f32; N], distance: f32) -> [f32; N] {
+    sigma_t.map(|sigma_t| (-sigma_t * distance).exp())
+}
+
+fn dot<const N: usize>(a: &[f32; N], b: &[f32; N]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// Chance of scattering rather than absorbing at each event that gives a
+/// thick slab the multiple scattering `albedo` (Chiang et al. 2016).
+fn single_scattering_albedo(albedo: f32) -> f32 {
+    let albedo = albedo.clamp(0.0, 0.999);
+    1.0 - (4.09712 + 4.20863 * albedo - (9.59217 + 41.6808 * albedo + 17.7126 * albedo * albedo).sqrt()).powi(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{material::Material, sphere::Sphere};
+
+    /// Walks from the top of a unit ball of `subsurface` straight down into
+    /// it, returning the mean weight of the light that gets back out.
+    fn mean_weight(subsurface: Subsurface, walks: usize) -> Vec3 {
+        let mut world = HitableList::new();
+        world.add(Box::new(Sphere::new(
+            Vec3::new(0.0, 0.0, 0.0),
+            1.0,
+            Material::Subsurface(subsurface),
+        )));
+        let mut total = Vec3::new(0.0, 0.0, 0.0);
+        for _ in 0..walks {
+            if let Some((_, weight)) = subsurface.walk(&world, &Vec3::new(0.0, 1.0, 0.0), &Vec3::new(0.0, -1.0, 0.0)) {
+                assert!(weight.x.is_finite() && weight.y.is_finite() && weight.z.is_finite());
+                total = total.add(&weight);
+            }
+        }
+        total.divide(walks as f32)
+    }
+
+    #[test]
+    fn black_medium_absorbs_everything() {
+        let subsurface = Subsurface::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.05, 0.05, 0.05), 1.0).unwrap();
+        let weight = mean_weight(subsurface, 1000);
+        assert!(weight.x < 1e-3 && weight.y < 1e-3 && weight.z < 1e-3, "{:?}", weight);
+    }
+
+    #[test]
+    fn white_medium_returns_nearly_everything() {
+        let subsurface = Subsurface::new(Vec3::new(1.0, 1.0, 1.0), Vec3::new(0.3, 0.3, 0.3), 1.0).unwrap();
+        let weight = mean_weight(subsurface, 2000);
+        for channel in [weight.x, weight.y, weight.z] {
+            assert!(channel > 0.9 && channel < 1.05, "{:?}", weight);
+        }
+    }
+
+    #[test]
+    fn rejects_non_positive_mean_free_paths() {
+        let albedo = Vec3::new(0.5, 0.5, 0.5);
+        assert!(Subsurface::new(albedo, Vec3::new(0.0, 0.1, 0.1), 1.5).is_err());
+        assert!(Subsurface::new(albedo, Vec3::new(0.1, -0.1, 0.1), 1.5).is_err());
+        assert!(Subsurface::new(albedo, Vec3::new(0.1, 0.1, f32::NAN), 1.5).is_err());
+    }
+}
@@ -0,0 +1,171 @@
+use std::{io, path::Path, sync::Arc};
+
+use image::{DynamicImage, ImageBuffer, Luma, Rgb32FImage};
+use noise::{Fbm, NoiseFn, Perlin};
+
+use crate::{hitable::HitRecord, util::random_double, vec3::Vec3};
+
+/// Source of a colour that varies over a surface.
+#[derive(Debug, Clone)]
+pub enum Texture {
+    /// Colours stored per vertex in a mesh, e.g. from a PLY scan. Surfaces
+    /// without vertex colours come out white.
+    VertexColor,
+    /// Bilinearly filtered image in linear colour, repeated outside [0, 1]
+    /// and multiplied by `tint`. UV (0, 0) is the top left corner, as in glTF.
+    Image { image: Arc<Rgb32FImage>, tint: Vec3 },
+}
+
+/// Source of a single number that varies over a surface, e.g. a height for
+/// displacement.
+#[derive(Debug, Clone)]
+pub enum ScalarTexture {
+    /// Bilinearly filtered brightness of an image in [0, 1], looked up by UV
+    /// like `Texture::Image`.
+    Image(Arc<ImageBuffer<Luma<f32>, Vec<f32>>>),
+    /// Fractal noise at the surface point, roughly in [-1, 1], with
+    /// `frequency` cycles per unit.
+    Noise { noise: Box<Fbm<Perlin>>, frequency: f32 },
+}
+
+/// Tangent-space normal map: red along the surface tangent, green along
+/// normal × tangent and blue along the normal, as in glTF.
+#[derive(Debug, Clone)]
+pub struct NormalMap {
+    image: Arc<Rgb32FImage>,
+}
+
+/// Decodes an sRGB image, as used for colour textures, to linear values.
+pub fn srgb_to_linear(image: &DynamicImage) -> Rgb32FImage {
+    let mut linear = image.to_rgb32f();
+    for channel in linear.iter_mut() {
+        *channel = channel.powf(2.2);
+    }
+    linear
+}
+
+impl Texture {
+    pub fn value(&self, hit_record: &HitRecord) -> Vec3 {
+        match self {
+            Texture::VertexColor => hit_record.vertex_color.unwrap_or(Vec3::new(1.0, 1.0, 1.0)),
+            Texture::Image { image, tint } => {
+                let (width, height) = image.dimensions();
+                bilinear(width, height, hit_record.u, hit_record.v, |x, y| {
+                    let [r, g, b] = image.get_pixel(x, y).0;
+                    Vec3::new(r, g, b)
+                })
+                .multiply_vec(tint)
+            }
+        }
+    }
+}
+
+impl NormalMap {
+    /// Normal map from an image, whose values are used as they are.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let image = image::open(path).map_err(io::Error::other)?;
+        Ok(NormalMap::new(Arc::new(image.to_rgb32f())))
+    }
+
+    pub fn new(image: Arc<Rgb32FImage>) -> Self {
+        NormalMap { image }
+    }
+
+    /// Normal at the hit in tangent space, not normalized.
+    pub fn value(&self, hit_record: &HitRecord) -> Vec3 {
+        let (width, height) = self.image.dimensions();
+        bilinear(width, height, hit_record.u, hit_record.v, |x, y| {
+            let [r, g, b] = self.image.get_pixel(x, y).0;
+            Vec3::new(r, g, b)
+        })
+        .multiply(2.0)
+        .sub(&Vec3::new(1.0, 1.0, 1.0))
+    }
+}
+
+impl ScalarTexture {
+    /// Grayscale image texture, taken as linear values.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let image = image::open(path).map_err(io::Error::other)?;
+        Ok(ScalarTexture::Image(Arc::new(image.to_luma32f())))
+    }
+
+    /// Alpha channel of an image, e.g. the cut-out of a leaf in its colour
+    /// texture. Images without alpha are opaque everywhere.
+    pub fn load_alpha<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let image = image::open(path).map_err(io::Error::other)?.to_rgba32f();
+        Ok(ScalarTexture::Image(Arc::new(ImageBuffer::from_fn(
+            image.width(),
+            image.height(),
+            |x, y| Luma([image.get_pixel(x, y).0[3]]),
+        ))))
+    }
+
+    pub fn value(&self, point: &Vec3, (u, v): (f32, f32)) -> f32 {
+        match self {
+            ScalarTexture::Image(image) => {
+                let (width, height) = image.dimensions();
+                bilinear(width, height, u, v, |x, y| {
+                    let value = image.get_pixel(x, y).0[0];
+                    Vec3::new(value, value, value)
+                })
+                .x
+            }
+            ScalarTexture::Noise { noise, frequency } => {
+                let point = point.multiply(*frequency);
+                noise.get([point.x as f64, point.y as f64, point.z as f64]) as f32
+            }
+        }
+    }
+
+    /// Whether a ray is stopped by a surface with this texture as its
+    /// opacity. Values between 0 and 1 stop that fraction of rays at random,
+    /// which averages out to partial transparency.
+    pub fn is_opaque(&self, point: &Vec3, uv: (f32, f32)) -> bool {
+        let alpha = self.value(point, uv);
+        alpha >= 1.0 || (alpha > 0.0 && random_double() < alpha)
+    }
+
+    /// Slope of the texture along `tangent` and `bitangent`: per unit of UV
+    /// for images, which have no scale in space, and per unit of distance
+    /// for noise.
+    pub fn gradient(&self, point: &Vec3, (u, v): (f32, f32), tangent: &Vec3, bitangent: &Vec3) -> (f32, f32) {
+        let here = self.value(point, (u, v));
+        match self {
+            ScalarTexture::Image(image) => {
+                let (du, dv) = (1.0 / image.width() as f32, 1.0 / image.height() as f32);
+                (
+                    (self.value(point, (u + du, v)) - here) / du,
+                    (self.value(point, (u, v + dv)) - here) / dv,
+                )
+            }
+            ScalarTexture::Noise { frequency, .. } => {
+                let step = 1e-3 / frequency;
+                (
+                    (self.value(&point.add(&tangent.multiply(step)), (u, v)) - here) / step,
+                    (self.value(&point.add(&bitangent.multiply(step)), (u, v)) - here) / step,
+                )
+            }
+        }
+    }
+}
+
+/// Bilinear lookup of a `width` by `height` grid of texels at (u, v),
+/// wrapping around at the edges.
+fn bilinear(width: u32, height: u32, u: f32, v: f32, texel: impl Fn(u32, u32) -> Vec3) -> Vec3 {
+    let x = u.rem_euclid(1.0) * width as f32 - 0.5;
+    let y = v.rem_euclid(1.0) * height as f32 - 0.5;
+    let (x0, y0) = (x.floor(), y.floor());
+    let (fx, fy) = (x - x0, y - y0);
+    let texel = |dx: f32, dy: f32| {
+        texel(
+            (x0 + dx).rem_euclid(width as f32) as u32,
+            (y0 + dy).rem_euclid(height as f32) as u32,
+        )
+    };
+    texel(0.0, 0.0)
+        .multiply((1.0 - fx) * (1.0 - fy))
+        .add(&texel(1.0, 0.0).multiply(fx * (1.0 - fy)))
+        .add(&texel(0.0, 1.0).multiply((1.0 - fx) * fy))
+        .add(&texel(1.0, 1.0).multiply(fx * fy))
+}
@@ -0,0 +1,212 @@
+use std::f32::consts::PI;
+
+use crate::{hitable::HitRecord, spectrum, texture::ScalarTexture, vec3::Vec3};
+
+/// Transparent coating a few hundred nanometres thick, like a soap film,
+/// oil on water or the oxide on anodised metal. Light reflected off its top
+/// and off the surface below interferes, so some wavelengths cancel and the
+/// reflection takes on colours that shift with the angle and the thickness.
+#[derive(Debug, Clone)]
+pub struct ThinFilm {
+    /// Thickness in nm.
+    pub thickness: f32,
+    /// Varies the thickness over the surface by `variation` nm times the
+    /// texture's value.
+    pub thickness_texture: Option<ScalarTexture>,
+    pub variation: f32,
+    pub ior: f32,
+}
+
+impl ThinFilm {
+    pub fn new(thickness: f32, ior: f32) -> Self {
+        ThinFilm {
+            thickness,
+            thickness_texture: None,
+            variation: 0.0,
+            ior,
+        }
+    }
+
+    /// Film whose thickness varies by `variation` nm times `texture`.
+    pub fn with_thickness_texture(mut self, texture: ScalarTexture, variation: f32) -> Self {
+        self.thickness_texture = Some(texture);
+        self.variation = variation;
+        self
+    }
+
+    /// Thickness in nm where `hit_record` hit the surface.
+    pub fn thickness_at(&self, hit_record: &HitRecord) -> f32 {
+        let variation = self.thickness_texture.as_ref().map_or(0.0, |texture| {
+            self.variation * texture.value(&hit_record.point, (hit_record.u, hit_record.v))
+        });
+        (self.thickness + variation).max(0.0)
+    }
+
+    /// Reflectance in RGB of the film, `thickness` nm thick, for light
+    /// arriving at `cos_theta` to the normal from a medium of index
+    /// `outside`. `substrate` gives the complex index (n, k) of the surface
+    /// under the film at a wavelength in nm.
+    pub fn reflectance(
+        &self,
+        cos_theta: f32,
+        outside: f32,
+        thickness: f32,
+        substrate: impl Fn(f32) -> (f32, f32),
+    ) -> Vec3 {
+        spectrum::reflectance_to_rgb(|wavelength| {
+            self.reflectance_at(wavelength, cos_theta, outside, thickness, substrate(wavelength))
+        })
+    }
+
+    /// Reflectance at a single `wavelength` in nm, summing the waves bounced
+    /// back and forth inside the film (Airy), averaged over both
+    /// polarizations.
+    pub fn reflectance_at(
+        &self,
+        wavelength: f32,
+        cos_theta: f32,
+        outside: f32,
+        thickness: f32,
+        (n, k): (f32, f32),
+    ) -> f32 {
+        let cos_theta = cos_theta.clamp(0.0, 1.0);
+        let n1 = Complex::real(outside);
+        let n2 = Complex::real(self.ior);
+        let n3 = Complex::new(n, k);
+        // Snell's law, n sin θ, is the same in every layer
+        let sin1 = (1.0 - cos_theta * cos_theta).max(0.0).sqrt() * outside;
+        let cos_in = |index: Complex| {
+            let sin = Complex::real(sin1).div(index);
+            Complex::real(1.0).sub(sin.mul(sin)).sqrt()
+        };
+        let (cos1, cos2, cos3) = (Complex::real(cos_theta), cos_in(n2), cos_in(n3));
+
+        let s12 = fresnel_s(n1, cos1, n2, cos2);
+        let s23 = fresnel_s(n2, cos2, n3, cos3);
+        let p12 = fresnel_p(n1, cos1, n2, cos2);
+        let p23 = fresnel_p(n2, cos2, n3, cos3);
+
+        // phase picked up on the way down through the film and back up
+        let phase = n2.mul(cos2).scale(4.0 * PI * thickness / wavelength);
+        let delay = Complex::new(0.0, 1.0).mul(phase).exp();
+        let airy = |r12: Complex, r23: Complex| {
+            let wave = r23.mul(delay);
+            r12.add(wave).div(Complex::real(1.0).add(r12.mul(wave))).norm_squared()
+        };
+        ((airy(s12, s23) + airy(p12, p23)) / 2.0).clamp(0.0, 1.0)
+    }
+}
+
+/// Amplitude reflection coefficient for light polarized perpendicular to
+/// the plane of incidence.
+fn fresnel_s(n1: Complex, cos1: Complex, n2: Complex, cos2: Complex) -> Complex {
+    let (a, b) = (n1.mul(cos1), n2.mul(cos2));
+    a.sub(b).div(a.add(b))
+}
+
+/// Amplitude reflection coefficient for light polarized in the plane of
+/// incidence.
+fn fresnel_p(n1: Complex, cos1: Complex, n2: Complex, cos2: Complex) -> Complex {
+    let (a, b) = (n2.mul(cos1), n1.mul(cos2));
+    a.sub(b).div(a.add(b))
+}
+
+/// Just enough complex arithmetic for the Fresnel amplitudes of absorbing
+/// layers.
+#[derive(Debug, Clone, Copy)]
+struct Complex {
+    re: f32,
+    im: f32,
+}
+
+impl Complex {
+    fn new(re: f32, im: f32) -> Self {
+        Complex { re, im }
+    }
+
+    fn real(re: f32) -> Self {
+        Complex { re, im: 0.0 }
+    }
+
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+
+    fn div(self, other: Complex) -> Complex {
+        let denominator = other.norm_squared();
+        Complex::new(
+            (self.re * other.re + self.im * other.im) / denominator,
+            (self.im * other.re - self.re * other.im) / denominator,
+        )
+    }
+
+    fn scale(self, factor: f32) -> Complex {
+        Complex::new(self.re * factor, self.im * factor)
+    }
+
+    fn norm_squared(self) -> f32 {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Principal square root.
+    fn sqrt(self) -> Complex {
+        let norm = self.norm_squared().sqrt();
+        let re = ((norm + self.re) / 2.0).max(0.0).sqrt();
+        let im = ((norm - self.re) / 2.0).max(0.0).sqrt();
+        Complex::new(re, if self.im < 0.0 { -im } else { im })
+    }
+
+    fn exp(self) -> Complex {
+        let magnitude = self.re.exp();
+        Complex::new(magnitude * self.im.cos(), magnitude * self.im.sin())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conductor::Conductor, microfacet};
+
+    #[test]
+    fn zero_thickness_film_leaves_a_dielectric_bare() {
+        let film = ThinFilm::new(0.0, 1.33);
+        for cos_theta in [1.0, 0.8, 0.5, 0.2] {
+            let coated = film.reflectance_at(550.0, cos_theta, 1.0, 0.0, (1.5, 0.0));
+            let bare = microfacet::fresnel_dielectric(cos_theta, 1.5);
+            assert!((coated - bare).abs() < 1e-4, "{} {} at {}", coated, bare, cos_theta);
+        }
+    }
+
+    #[test]
+    fn zero_thickness_film_leaves_a_conductor_bare() {
+        // gold around 550 nm
+        let (n, k) = (0.43, 2.455);
+        let bare = Conductor::new(Vec3::new(n, n, n), Vec3::new(k, k, k), 0.0, 0.0);
+        let film = ThinFilm::new(0.0, 1.6);
+        for cos_theta in [1.0, 0.8, 0.5, 0.2] {
+            let coated = film.reflectance_at(550.0, cos_theta, 1.0, 0.0, (n, k));
+            let bare = bare.fresnel(cos_theta).x;
+            assert!((coated - bare).abs() < 1e-3, "{} {} at {}", coated, bare, cos_theta);
+        }
+    }
+
+    #[test]
+    fn quarter_wave_coating_cancels_reflection() {
+        // a film of index sqrt(1.5) a quarter wave thick on glass
+        let ior = 1.5f32.sqrt();
+        let film = ThinFilm::new(550.0 / (4.0 * ior), ior);
+        let coated = film.reflectance_at(550.0, 1.0, 1.0, film.thickness, (1.5, 0.0));
+        assert!(coated < 1e-4, "{}", coated);
+    }
+}
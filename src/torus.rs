@@ -0,0 +1,94 @@
+use std::f32::consts::PI;
+
+use crate::{
+    aabb::Aabb,
+    frame::Frame,
+    hitable::{HitRecord, Hitable},
+    material::Material,
+    ray::Ray,
+    roots::{self, solve_quadratic},
+    vec3::Vec3,
+};
+
+/// Ring around `axis` through `center`. u runs around the axis and v around
+/// the tube, both in [0, 1].
+#[derive(Debug, Clone)]
+pub struct Torus {
+    frame: Frame,
+    major_radius: f32,
+    minor_radius: f32,
+    material: Material,
+}
+
+impl Torus {
+    pub fn new(center: Vec3, axis: Vec3, major_radius: f32, minor_radius: f32, material: Material) -> Self {
+        Torus {
+            frame: Frame::from_axis(center, axis),
+            major_radius,
+            minor_radius,
+            material,
+        }
+    }
+}
+
+impl Hitable for Torus {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
+        let o = self.frame.point_to_local(&ray.origin);
+        let d = self.frame.to_local(&ray.direction);
+        let length = d.length() as f64;
+        let (ox, oy, oz) = (o.x as f64, o.y as f64, o.z as f64);
+        let (dx, dy, dz) = (d.x as f64 / length, d.y as f64 / length, d.z as f64 / length);
+        let major = self.major_radius as f64;
+        let minor = self.minor_radius as f64;
+
+        // only search for roots where the ray is inside the bounding sphere
+        let f = ox * dx + oy * dy + oz * dz;
+        let o_squared = ox * ox + oy * oy + oz * oz;
+        let bound = major + minor;
+        let (enter, exit) = solve_quadratic(1.0, 2.0 * f, o_squared - bound * bound)?;
+        let lo = enter.max(t_min as f64 * length);
+        let hi = exit.min(t_max as f64 * length);
+
+        // (|p|² + R² - r²)² = 4R²(x² + y²) along the ray with unit direction
+        let g = o_squared + major * major - minor * minor;
+        let four_major_squared = 4.0 * major * major;
+        let coefficients = [
+            g * g - four_major_squared * (ox * ox + oy * oy),
+            4.0 * f * g - 2.0 * four_major_squared * (ox * dx + oy * dy),
+            4.0 * f * f + 2.0 * g - four_major_squared * (dx * dx + dy * dy),
+            4.0 * f,
+            1.0,
+        ];
+        let t = *roots::roots_in_interval(&coefficients, lo, hi)
+            .iter()
+            .find(|t| **t > t_min as f64 * length)?;
+        let t = (t / length) as f32;
+
+        let p = o.add(&d.multiply(t));
+        let ring = Vec3::new(p.x, p.y, 0.0).normalize().multiply(self.major_radius);
+        let normal = p.sub(&ring).divide(self.minor_radius);
+        let phi = p.y.atan2(p.x) + PI;
+        let theta = normal.z.atan2(ring.normalize().dot(&normal)) + PI;
+        Some(HitRecord::new(
+            ray,
+            t,
+            self.frame.to_world(&normal),
+            self.material.clone(),
+            phi / (2.0 * PI),
+            theta / (2.0 * PI),
+        ))
+    }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        let extent = self.major_radius + self.minor_radius;
+        let mut corners = vec![];
+        for x in [-extent, extent] {
+            for y in [-extent, extent] {
+                for z in [-self.minor_radius, self.minor_radius] {
+                    corners.push(self.frame.point_to_world(&Vec3::new(x, y, z)));
+                }
+            }
+        }
+        Some(Aabb::from_points(&corners))
+    }
+}
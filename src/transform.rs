@@ -0,0 +1,112 @@
+use crate::vec3::Vec3;
+
+/// Affine transform stored as a row-major 4x4 matrix.
+#[derive(Debug, Clone, Copy)]
+pub struct Transform {
+    pub m: [[f32; 4]; 4],
+}
+
+impl Transform {
+    pub fn identity() -> Self {
+        let mut m = [[0.0; 4]; 4];
+        for (i, row) in m.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        Transform { m }
+    }
+
+    pub fn translate(offset: Vec3) -> Self {
+        let mut transform = Transform::identity();
+        transform.m[0][3] = offset.x;
+        transform.m[1][3] = offset.y;
+        transform.m[2][3] = offset.z;
+        transform
+    }
+
+    pub fn scale(factors: Vec3) -> Self {
+        let mut transform = Transform::identity();
+        transform.m[0][0] = factors.x;
+        transform.m[1][1] = factors.y;
+        transform.m[2][2] = factors.z;
+        transform
+    }
+
+    /// Rotation by `degrees` around `axis`, counterclockwise when looking
+    /// down the axis.
+    pub fn rotate(axis: Vec3, degrees: f32) -> Self {
+        let a = axis.normalize();
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let mut transform = Transform::identity();
+        transform.m[0][0] = a.x * a.x + (1.0 - a.x * a.x) * cos;
+        transform.m[0][1] = a.x * a.y * (1.0 - cos) - a.z * sin;
+        transform.m[0][2] = a.x * a.z * (1.0 - cos) + a.y * sin;
+        transform.m[1][0] = a.x * a.y * (1.0 - cos) + a.z * sin;
+        transform.m[1][1] = a.y * a.y + (1.0 - a.y * a.y) * cos;
+        transform.m[1][2] = a.y * a.z * (1.0 - cos) - a.x * sin;
+        transform.m[2][0] = a.x * a.z * (1.0 - cos) - a.y * sin;
+        transform.m[2][1] = a.y * a.z * (1.0 - cos) + a.x * sin;
+        transform.m[2][2] = a.z * a.z + (1.0 - a.z * a.z) * cos;
+        transform
+    }
+
+    /// Rotation given by the unit quaternion x i + y j + z k + w.
+    pub fn from_quaternion(x: f32, y: f32, z: f32, w: f32) -> Self {
+        let mut transform = Transform::identity();
+        transform.m[0][0] = 1.0 - 2.0 * (y * y + z * z);
+        transform.m[0][1] = 2.0 * (x * y - z * w);
+        transform.m[0][2] = 2.0 * (x * z + y * w);
+        transform.m[1][0] = 2.0 * (x * y + z * w);
+        transform.m[1][1] = 1.0 - 2.0 * (x * x + z * z);
+        transform.m[1][2] = 2.0 * (y * z - x * w);
+        transform.m[2][0] = 2.0 * (x * z - y * w);
+        transform.m[2][1] = 2.0 * (y * z + x * w);
+        transform.m[2][2] = 1.0 - 2.0 * (x * x + y * y);
+        transform
+    }
+
+    /// `self * other`, i.e. `other` is applied first.
+    pub fn multiply(&self, other: &Transform) -> Transform {
+        let mut m = [[0.0; 4]; 4];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..4).map(|k| self.m[i][k] * other.m[k][j]).sum();
+            }
+        }
+        Transform { m }
+    }
+
+    pub fn transform_point(&self, p: &Vec3) -> Vec3 {
+        self.transform_vector(p)
+            .add(&Vec3::new(self.m[0][3], self.m[1][3], self.m[2][3]))
+    }
+
+    pub fn transform_vector(&self, v: &Vec3) -> Vec3 {
+        let m = &self.m;
+        Vec3::new(
+            m[0][0] * v.x + m[0][1] * v.y + m[0][2] * v.z,
+            m[1][0] * v.x + m[1][1] * v.y + m[1][2] * v.z,
+            m[2][0] * v.x + m[2][1] * v.y + m[2][2] * v.z,
+        )
+    }
+
+    /// Normals go through the inverse transpose, which up to a scale factor
+    /// is the cofactor matrix of the linear part.
+    pub fn transform_normal(&self, n: &Vec3) -> Vec3 {
+        let m = &self.m;
+        let column = |i: usize| Vec3::new(m[0][i], m[1][i], m[2][i]);
+        let (c0, c1, c2) = (column(0), column(1), column(2));
+        let cofactor_rows = [c1.cross(&c2), c2.cross(&c0), c0.cross(&c1)];
+        cofactor_rows[0]
+            .multiply(n.x)
+            .add(&cofactor_rows[1].multiply(n.y))
+            .add(&cofactor_rows[2].multiply(n.z))
+            .multiply(self.determinant().signum())
+            .normalize()
+    }
+
+    pub fn determinant(&self) -> f32 {
+        let m = &self.m;
+        let column = |i: usize| Vec3::new(m[0][i], m[1][i], m[2][i]);
+        column(0).dot(&column(1).cross(&column(2)))
+    }
+}
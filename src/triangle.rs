@@ -1,7 +1,9 @@
 use crate::{
+    aabb::Aabb,
     hitable::{HitRecord, Hitable},
     material::Material,
-    ray::{self, Ray},
+    ray::Ray,
+    texture::ScalarTexture,
     vec3::Vec3,
 };
 
@@ -11,11 +13,25 @@ pub struct Triangle {
     b: Vec3,
     c: Vec3,
     material: Material,
+    alpha: Option<ScalarTexture>,
 }
 
 impl Triangle {
     pub fn new(a: Vec3, b: Vec3, c: Vec3, material: Material) -> Self {
-        Triangle { a, b, c, material }
+        Triangle {
+            a,
+            b,
+            c,
+            material,
+            alpha: None,
+        }
+    }
+
+    /// Opacity looked up by the barycentric weights of `b` and `c`. Rays
+    /// pass through where it is 0.
+    pub fn with_alpha(mut self, alpha: ScalarTexture) -> Self {
+        self.alpha = Some(alpha);
+        self
     }
 
     pub fn normal(&self) -> Vec3 {
@@ -27,40 +43,34 @@ impl Triangle {
 
 impl Hitable for Triangle {
     fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<HitRecord> {
-        let intersection = moller_trumbore_intersection(&ray.origin, &ray.direction, self);
-        if let Some(intersection_point) = intersection {
-            let normal = self.normal();
-            let t = intersection_point.sub(&ray.origin).length() / ray.direction.length();
-
-            // flip normal if dot product is negative
-            let dot_product = normal.dot(&ray.direction);
-            let normal = if dot_product < 0.0 {
-                normal
-            } else {
-                normal.multiply(-1.0)
-            };
-
-            if t < t_max && t > t_min {
-                return Some(HitRecord {
-                    point: intersection_point,
-                    normal,
-                    t,
-                    front_face: true,
-                    material: self.material.clone(),
-                });
-            }
+        let intersection = moller_trumbore_intersection(&ray.origin, &ray.direction, &self.a, &self.b, &self.c);
+        if let Some((t, u, v)) = intersection
+            && t < t_max
+            && t > t_min
+            && self.alpha.as_ref().is_none_or(|alpha| alpha.is_opaque(&ray.at(t), (u, v)))
+        {
+            let mut record = HitRecord::new(ray, t, self.normal(), self.material.clone(), u, v);
+            record.tangent = Some(self.b.sub(&self.a).normalize());
+            return Some(record);
         }
         None
     }
+
+    fn bounding_box(&self) -> Option<Aabb> {
+        Some(Aabb::from_points(&[self.a, self.b, self.c]))
+    }
 }
 
-fn moller_trumbore_intersection(
+/// Ray parameter of the hit and the barycentric weights of `b` and `c`.
+pub fn moller_trumbore_intersection(
     origin: &Vec3,
     direction: &Vec3,
-    triangle: &Triangle,
-) -> Option<Vec3> {
-    let e1 = triangle.b.sub(&triangle.a);
-    let e2 = triangle.c.sub(&triangle.a);
+    a: &Vec3,
+    b: &Vec3,
+    c: &Vec3,
+) -> Option<(f32, f32, f32)> {
+    let e1 = b.sub(a);
+    let e2 = c.sub(a);
 
     let ray_cross_e2 = direction.cross(&e2);
     let det = e1.dot(&ray_cross_e2);
@@ -70,7 +80,7 @@ fn moller_trumbore_intersection(
     }
 
     let inv_det = 1.0 / det;
-    let s = origin.sub(&triangle.a);
+    let s = origin.sub(a);
     let u = inv_det * s.dot(&ray_cross_e2);
     if u < 0.0 || u > 1.0 {
         return None;
@@ -86,8 +96,7 @@ fn moller_trumbore_intersection(
 
     if t > f32::EPSILON {
         // ray intersection
-        let intersection_point = origin.add(&direction.multiply(t));
-        return Some(intersection_point);
+        return Some((t, u, v));
     } else {
         // This means that there is a line intersection but not a ray intersection.
         return None;
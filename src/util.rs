@@ -1,3 +1,5 @@
+use crate::vec3::Vec3;
+
 pub fn random_double() -> f32 {
     let mut rng = rand::rng();
     rand::Rng::random_range(&mut rng, 0.0..1.0)
@@ -6,3 +8,8 @@ pub fn random_double() -> f32 {
 pub fn random_double_range(min: f32, max: f32) -> f32 {
     min + random_double() * (max - min)
 }
+
+/// Brightness of a linear RGB colour, with the Rec. 709 weights.
+pub fn luminance(color: &Vec3) -> f32 {
+    0.2126 * color.x + 0.7152 * color.y + 0.0722 * color.z
+}
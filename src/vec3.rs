@@ -103,10 +103,42 @@ impl Vec3 {
         }
     }
 
+    /// Unit vector above the xy plane, more likely the closer it is to z, in
+    /// proportion to its cosine with z.
+    pub fn random_cosine_direction() -> Vec3 {
+        let r = util::random_double().sqrt();
+        let phi = 2.0 * std::f32::consts::PI * util::random_double();
+        Vec3::new(r * phi.cos(), r * phi.sin(), (1.0 - r * r).max(0.0).sqrt())
+    }
+
     pub fn reflect(&self, normal: &Vec3) -> Vec3 {
         self.sub(&normal.multiply(2.0 * self.dot(normal)))
     }
 
+    /// Two unit vectors that form an orthonormal basis with this unit vector.
+    pub fn orthonormal_basis(&self) -> (Vec3, Vec3) {
+        let sign = 1.0f32.copysign(self.z);
+        let a = -1.0 / (sign + self.z);
+        let b = self.x * self.y * a;
+        (
+            Vec3::new(1.0 + sign * self.x * self.x * a, sign * b, -sign * self.x),
+            Vec3::new(b, sign + self.y * self.y * a, -self.y),
+        )
+    }
+
+    /// Refracts a unit direction through a surface whose normal faces against it.
+    /// Returns `None` on total internal reflection.
+    pub fn refract(&self, normal: &Vec3, eta_ratio: f32) -> Option<Vec3> {
+        let cos_theta = self.multiply(-1.0).dot(normal).min(1.0);
+        let sin2_theta_t = eta_ratio * eta_ratio * (1.0 - cos_theta * cos_theta);
+        if sin2_theta_t > 1.0 {
+            return None;
+        }
+        let r_out_perp = self.add(&normal.multiply(cos_theta)).multiply(eta_ratio);
+        let r_out_parallel = normal.multiply(-(1.0 - sin2_theta_t).sqrt());
+        Some(r_out_perp.add(&r_out_parallel))
+    }
+
     pub fn random_in_unit_disk() -> Vec3 {
         loop {
             let p = Vec3::new(